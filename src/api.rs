@@ -0,0 +1,840 @@
+//! # API Trait (API 抽象)
+//!
+//! [`BgmApi`] 覆盖了 [`Client`] 的所有 API 方法，下游代码可以依赖 `&dyn BgmApi` 或 `impl BgmApi`
+//! 而不是具体的 [`Client`]，以便在测试中替换为不访问网络的实现。
+//!
+//! 使用 Builder 的 API (例如 [`Client::get_episodes`]) 在 trait 中接收对应的查询参数结构，
+//! 例如 [`GetEpisodesQuery`]。
+//!
+//! 启用 `test-util` feature 后，[`MockBgmApi`] 提供了可以为每个方法设置返回值的实现。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::api::BgmApi;
+//!
+//! async fn subject_name(api: &dyn BgmApi, subject_id: u64) -> Result<String, DepsError> {
+//!     Ok(api.get_subject(subject_id).await?.name)
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::new();
+//! println!("{}", subject_name(&client, 3559).await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, pin::Pin};
+
+use crate::{
+    client::{pagination::PageOptions, Client},
+    error::*,
+    types::*,
+};
+
+/// Future returned by [`BgmApi`] methods
+pub type ApiFuture<'a, T, E = DepsError> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>;
+
+/// Query of [`BgmApi::search_subjects`] (条目搜索参数)
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct SearchSubjectsQuery {
+    /// 关键词
+    pub keyword: String,
+
+    /// 排序方式
+    pub sort: SortType,
+
+    /// 过滤条件
+    pub filter: SearchSubjectsFilter,
+
+    /// 返回数量
+    pub limit: Option<u64>,
+
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+impl SearchSubjectsQuery {
+    /// 搜索 `keyword`
+    pub fn new(keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 设置排序方式
+    pub fn sort(mut self, sort: SortType) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// 设置过滤条件
+    pub fn filter(mut self, filter: SearchSubjectsFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// 设置返回数量
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 设置偏移量
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Query of [`BgmApi::get_subjects`] (浏览条目参数)
+///
+/// 各项的含义参见 [`GetSubjectsExecutor`](crate::client::subjects::GetSubjectsExecutor)。
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GetSubjectsQuery {
+    /// 条目类型
+    pub r#type: SubjectType,
+
+    /// 条目分类
+    pub cat: Option<SubjectCategory>,
+
+    /// 是否为系列
+    pub series: Option<bool>,
+
+    /// 平台
+    pub platform: Option<String>,
+
+    /// 排序方式
+    pub sort: Option<BrowseSort>,
+
+    /// 标签
+    pub tag: Option<String>,
+
+    /// 年份
+    pub year: Option<u64>,
+
+    /// 月份
+    pub month: Option<u64>,
+
+    /// 返回数量
+    pub limit: Option<u64>,
+
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+impl GetSubjectsQuery {
+    /// 浏览 `type` 类型的条目
+    pub fn new(r#type: SubjectType) -> Self {
+        Self {
+            r#type,
+            cat: None,
+            series: None,
+            platform: None,
+            sort: None,
+            tag: None,
+            year: None,
+            month: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// 设置条目分类
+    pub fn cat(mut self, cat: SubjectCategory) -> Self {
+        self.cat = Some(cat);
+        self
+    }
+
+    /// 设置是否为系列
+    pub fn series(mut self, series: bool) -> Self {
+        self.series = Some(series);
+        self
+    }
+
+    /// 设置平台
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.platform = Some(platform.into());
+        self
+    }
+
+    /// 设置排序方式
+    pub fn sort(mut self, sort: BrowseSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// 设置标签
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// 设置年份
+    pub fn year(mut self, year: u64) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// 设置月份
+    pub fn month(mut self, month: u64) -> Self {
+        self.month = Some(month);
+        self
+    }
+
+    /// 设置返回数量
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 设置偏移量
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Query of [`BgmApi::get_episodes`] (章节列表参数)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GetEpisodesQuery {
+    /// 条目 ID
+    pub subject_id: u64,
+
+    /// 章节类型
+    pub r#type: Option<EpisodeType>,
+
+    /// 返回数量
+    pub limit: Option<u64>,
+
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+impl GetEpisodesQuery {
+    /// 获取条目 `subject_id` 的章节
+    pub fn new(subject_id: u64) -> Self {
+        Self {
+            subject_id,
+            r#type: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// 设置章节类型
+    pub fn r#type(mut self, r#type: EpisodeType) -> Self {
+        self.r#type = Some(r#type);
+        self
+    }
+
+    /// 设置返回数量
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 设置偏移量
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Query of [`BgmApi::get_user_collections`] (用户收藏参数)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GetUserCollectionsQuery {
+    /// 用户名
+    pub username: String,
+
+    /// 条目类型
+    pub subject_type: Option<SubjectType>,
+
+    /// 收藏类型
+    pub r#type: Option<SubjectCollectionType>,
+
+    /// 返回数量
+    pub limit: Option<u64>,
+
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+impl GetUserCollectionsQuery {
+    /// 获取用户 `username` 的收藏
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            subject_type: None,
+            r#type: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// 设置条目类型
+    pub fn subject_type(mut self, subject_type: SubjectType) -> Self {
+        self.subject_type = Some(subject_type);
+        self
+    }
+
+    /// 设置收藏类型
+    pub fn r#type(mut self, r#type: SubjectCollectionType) -> Self {
+        self.r#type = Some(r#type);
+        self
+    }
+
+    /// 设置返回数量
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 设置偏移量
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// # bgm.tv API
+///
+/// 方法与 [`Client`] 上的同名方法一一对应，参见其文档。此 trait 是对象安全的。
+pub trait BgmApi: Send + Sync {
+    /// 参见 [`Client::search_subjects`]
+    fn search_subjects(
+        &self,
+        query: SearchSubjectsQuery,
+    ) -> ApiFuture<'_, SearchSubjects, SearchSubjectsError>;
+
+    /// 参见 [`Client::get_subjects`]
+    fn get_subjects(
+        &self,
+        query: GetSubjectsQuery,
+    ) -> ApiFuture<'_, PagedSubject, GetSubjectsError>;
+
+    /// 参见 [`Client::get_subject`]
+    fn get_subject(&self, subject_id: u64) -> ApiFuture<'_, Subject>;
+
+    /// 参见 [`Client::get_subject_image`]
+    fn get_subject_image(
+        &self,
+        subject_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes>;
+
+    /// 参见 [`Client::get_subject_persons`]
+    fn get_subject_persons(&self, subject_id: u64) -> ApiFuture<'_, Vec<RelatedPerson>>;
+
+    /// 参见 [`Client::get_subject_persons_page`]
+    fn get_subject_persons_page(
+        &self,
+        subject_id: u64,
+        options: PageOptions,
+    ) -> ApiFuture<'_, Paged<RelatedPerson>>;
+
+    /// 参见 [`Client::get_subject_characters`]
+    fn get_subject_characters(&self, subject_id: u64) -> ApiFuture<'_, Vec<RelatedCharacter>>;
+
+    /// 参见 [`Client::get_subject_characters_page`]
+    fn get_subject_characters_page(
+        &self,
+        subject_id: u64,
+        options: PageOptions,
+    ) -> ApiFuture<'_, Paged<RelatedCharacter>>;
+
+    /// 参见 [`Client::get_subject_subjects`]
+    fn get_subject_subjects(&self, subject_id: u64) -> ApiFuture<'_, Vec<SubjectRelation>>;
+
+    /// 参见 [`Client::get_episodes`]
+    fn get_episodes(
+        &self,
+        query: GetEpisodesQuery,
+    ) -> ApiFuture<'_, PagedEpisode, GetEpisodesError>;
+
+    /// 参见 [`Client::get_episode`]
+    fn get_episode(&self, episode_id: u64) -> ApiFuture<'_, Episode>;
+
+    /// 参见 [`Client::get_character`]
+    fn get_character(&self, character_id: u64) -> ApiFuture<'_, CharacterDetail>;
+
+    /// 参见 [`Client::get_character_image`]
+    fn get_character_image(
+        &self,
+        character_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes>;
+
+    /// 参见 [`Client::get_character_subjects`]
+    fn get_character_subjects(&self, character_id: u64) -> ApiFuture<'_, Vec<RelatedSubject>>;
+
+    /// 参见 [`Client::get_character_persons`]
+    fn get_character_persons(&self, character_id: u64) -> ApiFuture<'_, Vec<CharacterPerson>>;
+
+    /// 参见 [`Client::get_person`]
+    fn get_person(&self, person_id: u64) -> ApiFuture<'_, PersonDetail>;
+
+    /// 参见 [`Client::get_person_image`]
+    fn get_person_image(
+        &self,
+        person_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes>;
+
+    /// 参见 [`Client::get_person_subjects`]
+    fn get_person_subjects(&self, person_id: u64) -> ApiFuture<'_, Vec<RelatedSubject>>;
+
+    /// 参见 [`Client::get_person_characters`]
+    fn get_person_characters(&self, person_id: u64) -> ApiFuture<'_, Vec<PersonCharacter>>;
+
+    /// 参见 [`Client::get_user`]
+    fn get_user<'a>(&'a self, username: &'a str) -> ApiFuture<'a, User>;
+
+    /// 参见 [`Client::get_user_avatar`]
+    fn get_user_avatar<'a>(
+        &'a self,
+        username: &'a str,
+        image_type: ImageType,
+    ) -> ApiFuture<'a, bytes::Bytes>;
+
+    /// 参见 [`Client::get_me`]
+    fn get_me(&self) -> ApiFuture<'_, User>;
+
+    /// 参见 [`Client::get_user_collections`]
+    fn get_user_collections(
+        &self,
+        query: GetUserCollectionsQuery,
+    ) -> ApiFuture<'_, PagedUserCollection, GetUserCollectionsError>;
+
+    /// 参见 [`Client::get_user_episode_collections`]
+    fn get_user_episode_collections(
+        &self,
+        subject_id: u64,
+    ) -> ApiFuture<'_, Vec<UserEpisodeCollection>>;
+
+    /// 参见 [`Client::post_user_collection`]
+    fn post_user_collection<'a>(
+        &'a self,
+        subject_id: u64,
+        payload: &'a UserCollectionModifyPayload,
+    ) -> ApiFuture<'a, (), WriteError>;
+
+    /// 参见 [`Client::patch_user_collection`]
+    fn patch_user_collection<'a>(
+        &'a self,
+        subject_id: u64,
+        payload: &'a UserCollectionModifyPayload,
+    ) -> ApiFuture<'a, (), WriteError>;
+
+    /// 参见 [`Client::token_status`]
+    fn token_status(&self) -> ApiFuture<'_, AccessToken>;
+}
+
+impl BgmApi for Client {
+    fn search_subjects(
+        &self,
+        query: SearchSubjectsQuery,
+    ) -> ApiFuture<'_, SearchSubjects, SearchSubjectsError> {
+        let mut builder = Client::search_subjects(self)
+            .keyword(query.keyword)
+            .sort(query.sort)
+            .filter(query.filter);
+        if let Some(limit) = query.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = query.offset {
+            builder = builder.offset(offset);
+        }
+        Box::pin(builder.send())
+    }
+
+    fn get_subjects(
+        &self,
+        query: GetSubjectsQuery,
+    ) -> ApiFuture<'_, PagedSubject, GetSubjectsError> {
+        let mut builder = Client::get_subjects(self).r#type(query.r#type);
+        if let Some(cat) = query.cat {
+            builder = builder.cat(cat);
+        }
+        if let Some(series) = query.series {
+            builder = builder.series(series);
+        }
+        if let Some(platform) = query.platform {
+            builder = builder.platform(platform);
+        }
+        if let Some(sort) = query.sort {
+            builder = builder.sort(sort);
+        }
+        if let Some(tag) = query.tag {
+            builder = builder.tag(tag);
+        }
+        if let Some(year) = query.year {
+            builder = builder.year(year);
+        }
+        if let Some(month) = query.month {
+            builder = builder.month(month);
+        }
+        if let Some(limit) = query.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = query.offset {
+            builder = builder.offset(offset);
+        }
+        Box::pin(builder.send())
+    }
+
+    fn get_subject(&self, subject_id: u64) -> ApiFuture<'_, Subject> {
+        Box::pin(Client::get_subject(self, subject_id))
+    }
+
+    fn get_subject_image(
+        &self,
+        subject_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes> {
+        Box::pin(Client::get_subject_image(self, subject_id, image_type))
+    }
+
+    fn get_subject_persons(&self, subject_id: u64) -> ApiFuture<'_, Vec<RelatedPerson>> {
+        Box::pin(Client::get_subject_persons(self, subject_id))
+    }
+
+    fn get_subject_persons_page(
+        &self,
+        subject_id: u64,
+        options: PageOptions,
+    ) -> ApiFuture<'_, Paged<RelatedPerson>> {
+        Box::pin(Client::get_subject_persons_page(self, subject_id, options))
+    }
+
+    fn get_subject_characters(&self, subject_id: u64) -> ApiFuture<'_, Vec<RelatedCharacter>> {
+        Box::pin(Client::get_subject_characters(self, subject_id))
+    }
+
+    fn get_subject_characters_page(
+        &self,
+        subject_id: u64,
+        options: PageOptions,
+    ) -> ApiFuture<'_, Paged<RelatedCharacter>> {
+        Box::pin(Client::get_subject_characters_page(
+            self, subject_id, options,
+        ))
+    }
+
+    fn get_subject_subjects(&self, subject_id: u64) -> ApiFuture<'_, Vec<SubjectRelation>> {
+        Box::pin(Client::get_subject_subjects(self, subject_id))
+    }
+
+    fn get_episodes(
+        &self,
+        query: GetEpisodesQuery,
+    ) -> ApiFuture<'_, PagedEpisode, GetEpisodesError> {
+        let mut builder = Client::get_episodes(self, query.subject_id);
+        if let Some(r#type) = query.r#type {
+            builder = builder.r#type(r#type);
+        }
+        if let Some(limit) = query.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = query.offset {
+            builder = builder.offset(offset);
+        }
+        Box::pin(builder.send())
+    }
+
+    fn get_episode(&self, episode_id: u64) -> ApiFuture<'_, Episode> {
+        Box::pin(Client::get_episode(self, episode_id))
+    }
+
+    fn get_character(&self, character_id: u64) -> ApiFuture<'_, CharacterDetail> {
+        Box::pin(Client::get_character(self, character_id))
+    }
+
+    fn get_character_image(
+        &self,
+        character_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes> {
+        Box::pin(Client::get_character_image(self, character_id, image_type))
+    }
+
+    fn get_character_subjects(&self, character_id: u64) -> ApiFuture<'_, Vec<RelatedSubject>> {
+        Box::pin(Client::get_character_subjects(self, character_id))
+    }
+
+    fn get_character_persons(&self, character_id: u64) -> ApiFuture<'_, Vec<CharacterPerson>> {
+        Box::pin(Client::get_character_persons(self, character_id))
+    }
+
+    fn get_person(&self, person_id: u64) -> ApiFuture<'_, PersonDetail> {
+        Box::pin(Client::get_person(self, person_id))
+    }
+
+    fn get_person_image(
+        &self,
+        person_id: u64,
+        image_type: ImageType,
+    ) -> ApiFuture<'_, bytes::Bytes> {
+        Box::pin(Client::get_person_image(self, person_id, image_type))
+    }
+
+    fn get_person_subjects(&self, person_id: u64) -> ApiFuture<'_, Vec<RelatedSubject>> {
+        Box::pin(Client::get_person_subjects(self, person_id))
+    }
+
+    fn get_person_characters(&self, person_id: u64) -> ApiFuture<'_, Vec<PersonCharacter>> {
+        Box::pin(Client::get_person_characters(self, person_id))
+    }
+
+    fn get_user<'a>(&'a self, username: &'a str) -> ApiFuture<'a, User> {
+        Box::pin(Client::get_user(self, username))
+    }
+
+    fn get_user_avatar<'a>(
+        &'a self,
+        username: &'a str,
+        image_type: ImageType,
+    ) -> ApiFuture<'a, bytes::Bytes> {
+        Box::pin(Client::get_user_avatar(self, username, image_type))
+    }
+
+    fn get_me(&self) -> ApiFuture<'_, User> {
+        Box::pin(Client::get_me(self))
+    }
+
+    fn get_user_collections(
+        &self,
+        query: GetUserCollectionsQuery,
+    ) -> ApiFuture<'_, PagedUserCollection, GetUserCollectionsError> {
+        let mut builder = Client::get_user_collections(self, query.username);
+        if let Some(subject_type) = query.subject_type {
+            builder = builder.subject_type(subject_type);
+        }
+        if let Some(r#type) = query.r#type {
+            builder = builder.r#type(r#type);
+        }
+        if let Some(limit) = query.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = query.offset {
+            builder = builder.offset(offset);
+        }
+        Box::pin(builder.send())
+    }
+
+    fn get_user_episode_collections(
+        &self,
+        subject_id: u64,
+    ) -> ApiFuture<'_, Vec<UserEpisodeCollection>> {
+        Box::pin(Client::get_user_episode_collections(self, subject_id))
+    }
+
+    fn post_user_collection<'a>(
+        &'a self,
+        subject_id: u64,
+        payload: &'a UserCollectionModifyPayload,
+    ) -> ApiFuture<'a, (), WriteError> {
+        Box::pin(Client::post_user_collection(self, subject_id, payload))
+    }
+
+    fn patch_user_collection<'a>(
+        &'a self,
+        subject_id: u64,
+        payload: &'a UserCollectionModifyPayload,
+    ) -> ApiFuture<'a, (), WriteError> {
+        Box::pin(Client::patch_user_collection(self, subject_id, payload))
+    }
+
+    fn token_status(&self) -> ApiFuture<'_, AccessToken> {
+        Box::pin(Client::token_status(self))
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub use mock::MockBgmApi;
+
+#[cfg(feature = "test-util")]
+mod mock {
+    use std::{fmt, sync::Mutex};
+
+    use super::*;
+
+    macro_rules! mock_bgm_api {
+        ($(
+            $name:ident, $on_name:ident ($($arg:ident: $ty:ty),*) -> $out:ty, $err:ty;
+        )*) => {
+            /// Mock implementation of [`BgmApi`] (模拟的 API)
+            ///
+            /// 通过 `on_xxx` 方法为每个 API 设置处理函数，调用未设置处理函数的 API 会 panic。
+            /// [`calls`](Self::calls) 返回按顺序调用过的 API 名称。
+            ///
+            /// ## Example
+            ///
+            /// ```
+            /// # use bgmtv::prelude::*;
+            /// use bgmtv::api::{BgmApi, MockBgmApi};
+            ///
+            /// # #[tokio::main]
+            /// # async fn main() {
+            /// let api = MockBgmApi::default().on_get_user(|username| {
+            ///     Ok(User {
+            ///         id: 1,
+            ///         username: username.to_string(),
+            ///         nickname: "Sai".to_string(),
+            ///         sign: String::new(),
+            ///     })
+            /// });
+            ///
+            /// assert_eq!(api.get_user("sai").await.unwrap().nickname, "Sai");
+            /// assert_eq!(api.calls(), vec!["get_user"]);
+            /// # }
+            /// ```
+            #[derive(Default)]
+            pub struct MockBgmApi {
+                calls: Mutex<Vec<&'static str>>,
+                $(
+                    #[allow(clippy::type_complexity)]
+                    $name: Option<Box<dyn Fn($($ty),*) -> Result<$out, $err> + Send + Sync>>,
+                )*
+            }
+
+            impl MockBgmApi {
+                /// 按顺序调用过的 API 名称
+                pub fn calls(&self) -> Vec<&'static str> {
+                    self.calls.lock().unwrap().clone()
+                }
+
+                $(
+                    #[doc = concat!("设置 [`BgmApi::", stringify!($name), "`] 的处理函数")]
+                    pub fn $on_name(
+                        mut self,
+                        handler: impl Fn($($ty),*) -> Result<$out, $err> + Send + Sync + 'static,
+                    ) -> Self {
+                        self.$name = Some(Box::new(handler));
+                        self
+                    }
+                )*
+            }
+
+            impl BgmApi for MockBgmApi {
+                $(
+                    fn $name<'a>(&'a self, $($arg: $ty),*) -> ApiFuture<'a, $out, $err> {
+                        self.calls.lock().unwrap().push(stringify!($name));
+                        let Some(handler) = &self.$name else {
+                            panic!(concat!("MockBgmApi::", stringify!($name), " is not mocked"));
+                        };
+                        let res = handler($($arg),*);
+                        Box::pin(async move { res })
+                    }
+                )*
+            }
+
+            impl fmt::Debug for MockBgmApi {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let mocked: Vec<&str> = [$((stringify!($name), self.$name.is_some())),*]
+                        .into_iter()
+                        .filter_map(|(name, mocked)| mocked.then_some(name))
+                        .collect();
+                    f.debug_struct("MockBgmApi").field("mocked", &mocked).finish_non_exhaustive()
+                }
+            }
+        };
+    }
+
+    mock_bgm_api! {
+        search_subjects, on_search_subjects(query: SearchSubjectsQuery) -> SearchSubjects, SearchSubjectsError;
+        get_subjects, on_get_subjects(query: GetSubjectsQuery) -> PagedSubject, GetSubjectsError;
+        get_subject, on_get_subject(subject_id: u64) -> Subject, DepsError;
+        get_subject_image, on_get_subject_image(subject_id: u64, image_type: ImageType) -> bytes::Bytes, DepsError;
+        get_subject_persons, on_get_subject_persons(subject_id: u64) -> Vec<RelatedPerson>, DepsError;
+        get_subject_persons_page, on_get_subject_persons_page(subject_id: u64, options: PageOptions) -> Paged<RelatedPerson>, DepsError;
+        get_subject_characters, on_get_subject_characters(subject_id: u64) -> Vec<RelatedCharacter>, DepsError;
+        get_subject_characters_page, on_get_subject_characters_page(subject_id: u64, options: PageOptions) -> Paged<RelatedCharacter>, DepsError;
+        get_subject_subjects, on_get_subject_subjects(subject_id: u64) -> Vec<SubjectRelation>, DepsError;
+        get_episodes, on_get_episodes(query: GetEpisodesQuery) -> PagedEpisode, GetEpisodesError;
+        get_episode, on_get_episode(episode_id: u64) -> Episode, DepsError;
+        get_character, on_get_character(character_id: u64) -> CharacterDetail, DepsError;
+        get_character_image, on_get_character_image(character_id: u64, image_type: ImageType) -> bytes::Bytes, DepsError;
+        get_character_subjects, on_get_character_subjects(character_id: u64) -> Vec<RelatedSubject>, DepsError;
+        get_character_persons, on_get_character_persons(character_id: u64) -> Vec<CharacterPerson>, DepsError;
+        get_person, on_get_person(person_id: u64) -> PersonDetail, DepsError;
+        get_person_image, on_get_person_image(person_id: u64, image_type: ImageType) -> bytes::Bytes, DepsError;
+        get_person_subjects, on_get_person_subjects(person_id: u64) -> Vec<RelatedSubject>, DepsError;
+        get_person_characters, on_get_person_characters(person_id: u64) -> Vec<PersonCharacter>, DepsError;
+        get_user, on_get_user(username: &str) -> User, DepsError;
+        get_user_avatar, on_get_user_avatar(username: &str, image_type: ImageType) -> bytes::Bytes, DepsError;
+        get_me, on_get_me() -> User, DepsError;
+        get_user_collections, on_get_user_collections(query: GetUserCollectionsQuery) -> PagedUserCollection, GetUserCollectionsError;
+        get_user_episode_collections, on_get_user_episode_collections(subject_id: u64) -> Vec<UserEpisodeCollection>, DepsError;
+        post_user_collection, on_post_user_collection(subject_id: u64, payload: &UserCollectionModifyPayload) -> (), WriteError;
+        patch_user_collection, on_patch_user_collection(subject_id: u64, payload: &UserCollectionModifyPayload) -> (), WriteError;
+        token_status, on_token_status() -> AccessToken, DepsError;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_client_bgm_api() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"total":0,"limit":5,"offset":10,"data":[]}"#,
+        )])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let api: &dyn BgmApi = &client;
+        let page = api
+            .get_episodes(GetEpisodesQuery::new(1).limit(5).offset(10))
+            .await
+            .unwrap();
+        assert_eq!(page.offset, 10);
+
+        let line = &server.requests()[0].line;
+        assert!(line.starts_with("GET /v0/episodes?"));
+        assert!(line.contains("subject_id=1"));
+        assert!(line.contains("limit=5"));
+        assert!(line.contains("offset=10"));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn test_mock_bgm_api() {
+        let api = MockBgmApi::default()
+            .on_get_subject_image(|id, _| Ok(id.to_be_bytes().to_vec().into()))
+            .on_patch_user_collection(|_, payload| match payload.ep_status {
+                Some(_) => Ok(()),
+                None => Err(WriteError::SubjectLocked {
+                    description: "locked".to_string(),
+                }),
+            });
+
+        let api: &dyn BgmApi = &api;
+        assert_eq!(
+            api.get_subject_image(1, ImageType::Large).await.unwrap(),
+            vec![0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert!(api
+            .patch_user_collection(1, &UserCollectionModifyPayload::default().ep_status(1))
+            .await
+            .is_ok());
+        assert!(api
+            .patch_user_collection(1, &UserCollectionModifyPayload::default())
+            .await
+            .is_err());
+    }
+}
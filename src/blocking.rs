@@ -0,0 +1,82 @@
+//! # Blocking (同步客户端)
+//!
+//! 启用 `blocking` feature 后，[`Client`] 在内部的单线程 tokio runtime 上运行异步的
+//! [`client::Client`](crate::client::Client)，供命令行工具等不使用 async 的程序调用。
+//!
+//! 所有 API 都通过 [`Client::call`] 调用，不需要为每个 API 单独提供同步版本。
+//! 不能在 tokio runtime 中调用，否则会 panic。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! # fn main() -> anyhow::Result<()> {
+//! let client = bgmtv::blocking::Client::new(Client::builder().build()?)?;
+//!
+//! let subject = client.call(|client| client.get_subject(3559))?;
+//! let episodes = client.call(|client| client.get_episodes(3559).limit(10).send())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, io};
+
+/// Blocking client (同步客户端)
+///
+/// 参见 [模块文档](self)。
+#[derive(Debug)]
+pub struct Client {
+    inner: crate::client::Client,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Client {
+    /// 使用 `client` 创建同步客户端，创建 tokio runtime 失败时返回错误
+    pub fn new(client: crate::client::Client) -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            inner: client,
+            runtime,
+        })
+    }
+
+    /// 内部的异步客户端
+    pub fn inner(&self) -> &crate::client::Client {
+        &self.inner
+    }
+
+    /// 以阻塞的方式调用异步客户端的 API，返回其结果
+    pub fn call<'a, F, Fut>(&'a self, f: F) -> Fut::Output
+    where
+        F: FnOnce(&'a crate::client::Client) -> Fut,
+        Fut: Future,
+    {
+        self.runtime.block_on(f(&self.inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[test]
+    fn test_blocking_call() {
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server_runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = server_runtime.block_on(MockServer::start(vec![MockResponse::new(200, user)]));
+
+        let client = Client::new(
+            crate::client::Client::builder()
+                .base_url(server.base_url.clone())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let user = client.call(|client| client.get_user("sai")).unwrap();
+        assert_eq!(user.nickname, "Sai");
+        assert_eq!(server.requests().len(), 1);
+    }
+}
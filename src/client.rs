@@ -2,12 +2,95 @@
 //!
 //! 此模块包含了 [`Client`] 结构体、其相关方法的辅助结构体与实现。
 
+use std::{future::Future, pin::Pin};
+
 use derive_builder::{Builder, UninitializedFieldError};
 
 use crate::prelude::*;
 
+pub mod batch;
+#[cfg(feature = "cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub mod cache;
+pub mod characters;
+pub mod collections;
+pub mod config;
 pub mod episodes;
+pub mod hooks;
+pub mod images;
+#[cfg(feature = "metrics")]
+mod instruments;
+pub mod offline;
+pub mod overrides;
+pub mod pagination;
+pub mod persons;
+#[cfg(feature = "recorder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+pub mod recorder;
+pub mod request_id;
+pub mod retry;
+pub mod scheduler;
+pub mod sink;
+pub mod stats;
 pub mod subjects;
+pub mod token;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod transport;
+
+/// Executors (执行器)
+///
+/// 此 mod 重新导出了所有 API 的执行器、Builder 与对应的构建错误，便于在结构体中保存 Builder 时引用。
+///
+/// 命名规则为：API 方法 `xxx` 对应 `XxxExecutor`、`XxxExecutorBuilder` 与 `XxxExecutorBuilderError`。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// use bgmtv::client::executors::GetEpisodesExecutorBuilder;
+///
+/// struct EpisodeQuery<'a> {
+///     builder: GetEpisodesExecutorBuilder<'a>,
+/// }
+///
+/// let client = Client::new();
+/// let query = EpisodeQuery {
+///     builder: client.get_episodes(3559).limit(10),
+/// };
+/// ```
+pub mod executors {
+    pub use super::{
+        collections::{
+            GetUserCollectionsExecutor, GetUserCollectionsExecutorBuilder,
+            GetUserCollectionsExecutorBuilderError,
+        },
+        episodes::{
+            GetEpisodesExecutor, GetEpisodesExecutorBuilder, GetEpisodesExecutorBuilderError,
+        },
+        pagination::{FetchAllOptions, Page, PageOptions, Paginated},
+        subjects::{
+            GetSubjectsExecutor, GetSubjectsExecutorBuilder, GetSubjectsExecutorBuilderError,
+            SearchSubjectsExecutor, SearchSubjectsExecutorBuilder,
+            SearchSubjectsExecutorBuilderError,
+        },
+        ExecutorFuture,
+    };
+}
+
+use hooks::{ImageUrlRewriter, Interceptor, Interceptors, OnRequest, OnResponse};
+use pagination::PageOptions;
+use retry::RetryPolicy;
+use stats::{RequestStats, StatsCounter};
+
+use crate::endpoints::{DEFAULT_BASE_URL, DEFAULT_OAUTH_BASE_URL};
+use crate::time::{Clock, SharedClock};
+
+use token::{SharedTokenProvider, StaticToken, TokenProvider, TokenSlot};
+use transport::{HttpTransport, SharedTransport};
+
+/// Future returned by `into_future` of executor builders, allowing them to be `.await`ed directly
+pub type ExecutorFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 pub(crate) const DEFAULT_USER_AGENT: &str = concat!(
     "duskmoon/bgmtv/",
@@ -29,10 +112,32 @@ pub(crate) const DEFAULT_USER_AGENT: &str = concat!(
 /// 一般情况下，你可以直接使用 [`Client::new`] 来创建一个默认的 [`Client`] 对象用于开发。不过对于生产环境，强烈建议类似下面的示例来创建一个
 /// 具有对应的 user agent 和 token 的 [`Client`] 对象。
 ///
+/// ## Sharing
+///
+/// [`Client`] 是 `Send + Sync` 的，内部的连接池、限流器、缓存与统计都在所有请求间共享，应当在整个应用中复用同一个实例，
+/// 例如放入 [`Arc`](std::sync::Arc) 后在多个任务间共享。执行器、Builder、分页流与各方法返回的 future 也都是 `Send` 的，
+/// 可以在多线程运行时中跨越 `.await` 持有或交给 `tokio::spawn`。
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = std::sync::Arc::new(Client::new());
+///
+/// let task = tokio::spawn({
+///     let client = client.clone();
+///     async move { client.get_subject(3559).await.map(|s| s.name) }
+/// });
+/// # drop(task);
+/// # }
+/// ```
+///
 /// ## Example
 ///
 /// ```
 /// # use bgmtv::prelude::*;
+/// # #[tokio::main]
+/// # async fn main() {
 /// let client = Client::builder()
 ///     .user_agent("xxx/yyy/1.0")
 ///     .token("auth_token")
@@ -41,15 +146,37 @@ pub(crate) const DEFAULT_USER_AGENT: &str = concat!(
 ///
 /// assert_eq!(client.base_url(), "https://api.bgm.tv");
 /// assert_eq!(client.user_agent(), "xxx/yyy/1.0");
-/// assert_eq!(client.token(), Some("auth_token"));
+/// assert_eq!(client.token().await.as_deref(), Some("auth_token"));
+/// # }
 /// ```
 #[derive(Debug, Builder)]
 pub struct Client {
     /// Base URL of the API.
     ///
     /// 默认值为 "<https://api.bgm.tv>"。一般情况下不需要修改。
-    #[builder(default = "https://api.bgm.tv".to_string())]
-    pub(crate) base_url: String,
+    ///
+    /// 在 [`build`](ClientBuilder::build) 时解析为 [`url::Url`]，不是合法的 `http` / `https` URL 时返回错误。
+    /// 可以包含路径前缀，例如 `https://example.com/bgm`，各 API 的路径会拼接在其后。
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Option<String>",
+            build = "parse_base_url(self.base_url.as_deref(), DEFAULT_BASE_URL)?"
+        )
+    )]
+    pub(crate) base_url: url::Url,
+
+    /// Base URL of the OAuth API.
+    ///
+    /// 默认值为 "<https://bgm.tv>"，用于 [`Client::token_status`] 等授权相关 API。
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Option<String>",
+            build = "parse_base_url(self.oauth_base_url.as_deref(), DEFAULT_OAUTH_BASE_URL)?"
+        )
+    )]
+    pub(crate) oauth_base_url: url::Url,
 
     /// User agent.
     ///
@@ -59,41 +186,491 @@ pub struct Client {
     #[builder(default, setter(into, strip_option))]
     pub(crate) user_agent: Option<String>,
 
-    /// Authorization token.
+    /// Default headers.
+    ///
+    /// 附加到每个请求的请求头，通过 [`ClientBuilder::default_header`] 添加。
+    /// 不会覆盖 API 方法自身设置的请求头 (例如 `Accept`)；设置了 [`token`](Self::token) 时 `Authorization` 以 token 为准。
+    #[builder(default, setter(custom))]
+    pub(crate) default_headers: reqwest::header::HeaderMap,
+
+    /// Authorization token provider.
     ///
     /// 用于访问需要授权的 API。如果不需要授权，可以不设置。
+    ///
+    /// 可以通过 [`ClientBuilder::token`] 设置固定的 token，或通过 [`ClientBuilder::token_provider`]
+    /// 设置自定义的 [`TokenProvider`]，后者会在每次请求时被调用。构建后可以通过 [`Client::set_token`] 替换。
+    #[builder(default, setter(custom))]
+    pub(crate) token: TokenSlot,
+
+    /// Total timeout of a request.
+    ///
+    /// 从发起连接到读取完响应体的总超时时间，默认不限制。设置了自定义的 [`client`](Self::client) 时不生效。
+    #[builder(default, setter(strip_option))]
+    pub(crate) timeout: Option<std::time::Duration>,
+
+    /// Connect timeout.
+    ///
+    /// 建立连接的超时时间，默认不限制。设置了自定义的 [`client`](Self::client) 时不生效。
+    #[builder(default, setter(strip_option))]
+    pub(crate) connect_timeout: Option<std::time::Duration>,
+
+    /// Read timeout.
+    ///
+    /// 两次读取数据之间的超时时间，默认不限制。设置了自定义的 [`client`](Self::client) 时不生效。
+    #[builder(default, setter(strip_option))]
+    pub(crate) read_timeout: Option<std::time::Duration>,
+
+    /// Maximum size of JSON response bodies.
+    ///
+    /// 单位为字节，默认不限制。JSON 响应 (包括错误响应) 的响应体超过此大小时停止读取，
+    /// 返回 [`DepsError::ResponseTooLarge`]，避免意外的超大响应耗尽内存。
+    #[builder(default, setter(strip_option))]
+    pub(crate) max_json_size: Option<u64>,
+
+    /// Maximum size of image response bodies.
+    ///
+    /// 单位为字节，默认不限制。图片的响应体超过此大小时停止读取 (或停止写入 `writer`)，
+    /// 返回 [`DepsError::ResponseTooLarge`]。
+    #[builder(default, setter(strip_option))]
+    pub(crate) max_image_size: Option<u64>,
+
+    /// Proxy URL.
+    ///
+    /// 所有请求都通过此代理发送，支持 `http://` 与 `https://` 代理；启用 `socks` feature 后还支持 `socks5://` 与 `socks5h://`。
+    /// URL 无法解析时 [`build`](ClientBuilder::build) 会返回错误。设置了自定义的 [`client`](Self::client) 时不生效。
     #[builder(default, setter(into, strip_option))]
-    pub(crate) token: Option<String>,
+    pub(crate) proxy: Option<String>,
+
+    /// Disable system proxies.
+    ///
+    /// 默认会使用 `HTTP_PROXY` / `HTTPS_PROXY` 等环境变量中的代理，通过 [`ClientBuilder::no_proxy`] 禁用；
+    /// 不影响 [`proxy`](Self::proxy) 设置的代理。
+    #[builder(default, setter(custom))]
+    pub(crate) no_proxy: bool,
 
     /// Internal reqwest client.
     ///
     /// 一般情况下不需要设置。如果需要自定义 [`reqwest::Client`]，可以使用此项。
+    ///
+    /// 默认的 client 使用 [`user_agent`](Self::user_agent)、[`timeout`](Self::timeout)、
+    /// [`connect_timeout`](Self::connect_timeout)、[`read_timeout`](Self::read_timeout) 与 [`proxy`](Self::proxy) 构建。
+    /// 启用 `gzip`、`brotli` 或 `zstd` feature 时，默认的 client 会协商对应的压缩格式并自动解压响应体。
+    /// TLS 实现由 `native-tls` (默认) 与 `rustls` feature 选择，同时启用时使用 rustls。
     #[builder(default = "self.default_client()?")]
     pub(crate) client: reqwest::Client,
+
+    /// Middleware stack.
+    ///
+    /// 设置后，所有请求都通过此 [`reqwest_middleware::ClientWithMiddleware`] 发送，可以复用已有的重试、追踪等中间件。
+    /// 请求仍由 [`client`](Self::client) 构建，因此 user agent 等默认请求头需要在中间件的内部 client 上设置。
+    #[cfg(feature = "middleware")]
+    #[builder(default, setter(custom))]
+    pub(crate) middleware: Option<reqwest_middleware::ClientWithMiddleware>,
+
+    /// HTTP transport.
+    ///
+    /// 设置后，所有请求都通过此 [`HttpTransport`] 发送，而不是 [`client`](Self::client) 或中间件，参见 [`transport`] 模块。
+    /// 请求仍由 [`client`](Self::client) 构建。
+    #[builder(default, setter(custom))]
+    pub(crate) transport: Option<SharedTransport>,
+
+    /// Granted scopes of the token.
+    ///
+    /// 设置后，修改类 API 会在发送请求前检查 token 是否具有所需的权限，权限不足时直接返回
+    /// [`WriteError::InsufficientScope`]。调用 [`Client::token_status`] 后以查询到的权限为准。
+    #[builder(default, setter(strip_option))]
+    pub(crate) scopes: Option<Scopes>,
+
+    /// Default privacy of collection writes.
+    ///
+    /// 设置后，[`Client::post_user_collection`] 新增收藏时，如果请求体未设置
+    /// [`private`](UserCollectionModifyPayload::private) 则使用此值，
+    /// 代替用户同步收藏的应用可以设置为 `true`，避免意外公开用户的私密收藏。
+    /// [`Client::patch_user_collection`] 只修改已有的收藏，不会使用此值，以免改变已有收藏的可见性。
+    #[builder(default, setter(custom))]
+    pub(crate) default_private_collections: Option<bool>,
+
+    /// Clock.
+    ///
+    /// 内部所有的等待都通过此 [`Clock`] 进行，默认为 [`TokioClock`](crate::time::TokioClock)。
+    /// 测试中可以设置为 [`ManualClock`](crate::time::ManualClock) 来快进时间。
+    #[builder(default, setter(custom))]
+    pub(crate) clock: SharedClock,
+
+    /// Request / response hooks.
+    ///
+    /// 参见 [`hooks`] 模块。
+    #[builder(default, setter(custom))]
+    pub(crate) interceptors: Interceptors,
+
+    /// Image URL rewriter.
+    ///
+    /// 改写 API 返回的数据与图片下载方法中的图片 URL，参见 [`hooks`] 模块。
+    #[builder(default, setter(custom))]
+    pub(crate) image_url_rewriter: Option<ImageUrlRewriter>,
+
+    /// Retry policy on rate limiting (`429 Too Many Requests`) and network errors.
+    ///
+    /// 默认不重试，直接返回 [`DepsError::RateLimited`] 或网络错误，参见 [`retry`] 模块。
+    #[builder(default, setter(strip_option))]
+    pub(crate) retry: Option<RetryPolicy>,
+
+    /// Per-endpoint-class overrides.
+    ///
+    /// 为某一类 API 单独设置超时时间、重试策略与限流权重，通过 [`ClientBuilder::endpoint_override`] 设置，参见 [`overrides`] 模块。
+    #[builder(default, setter(custom))]
+    pub(crate) endpoint_overrides: overrides::EndpointOverrides,
+
+    /// Client-side rate limit.
+    ///
+    /// 默认不限流，参见 [`scheduler`] 模块。
+    #[builder(default, setter(custom))]
+    pub(crate) rate_limiter: Option<std::sync::Arc<scheduler::RateLimiter>>,
+
+    /// Response cache.
+    ///
+    /// 默认不缓存，参见 [`cache`] 模块。
+    #[cfg(feature = "cache")]
+    #[builder(default, setter(custom))]
+    pub(crate) cache: Option<std::sync::Arc<cache::ResponseCache>>,
+
+    /// Offline snapshot store.
+    ///
+    /// 默认不保存快照，参见 [`offline`] 模块。
+    #[builder(default, setter(custom))]
+    pub(crate) snapshot_store: Option<offline::SharedSnapshotStore>,
+
+    /// Request statistics.
+    #[builder(setter(skip))]
+    pub(crate) stats: std::sync::Arc<StatsCounter>,
+
+    /// Scopes reported by [`Client::token_status`].
+    #[builder(setter(skip))]
+    pub(crate) reported_scopes: std::sync::RwLock<Option<Scopes>>,
 }
 
 impl ClientBuilder {
+    /// Base URL of the API.
+    ///
+    /// 参见 [`Client::base_url`]。
+    pub fn base_url(&mut self, base_url: impl Into<String>) -> &mut Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Base URL of the OAuth API.
+    pub fn oauth_base_url(&mut self, oauth_base_url: impl Into<String>) -> &mut Self {
+        self.oauth_base_url = Some(oauth_base_url.into());
+        self
+    }
+
+    /// Authorization token.
+    ///
+    /// 设置固定的 token，等价于使用 [`StaticToken`](token::StaticToken) 调用 [`token_provider`](Self::token_provider)。
+    pub fn token(&mut self, token: impl Into<String>) -> &mut Self {
+        self.token_provider(StaticToken(token.into()))
+    }
+
+    /// Default header.
+    ///
+    /// 添加一个附加到每个请求的请求头，可以多次调用，同名的请求头以最后一次为准。
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// use reqwest::header::{HeaderValue, ACCEPT_LANGUAGE};
+    ///
+    /// let client = Client::builder()
+    ///     .default_header(ACCEPT_LANGUAGE, HeaderValue::from_static("zh-CN"))
+    ///     .default_header("x-request-id", HeaderValue::from_static("42"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn default_header(
+        &mut self,
+        name: impl reqwest::header::IntoHeaderName,
+        value: reqwest::header::HeaderValue,
+    ) -> &mut Self {
+        self.default_headers
+            .get_or_insert_with(Default::default)
+            .insert(name, value);
+        self
+    }
+
+    /// Authorization token provider.
+    ///
+    /// 每次请求前都会调用 [`TokenProvider::token`] 获取 token，适用于需要轮换或刷新 token 的场景。
+    pub fn token_provider(&mut self, provider: impl TokenProvider + 'static) -> &mut Self {
+        self.token = Some(TokenSlot::new(Some(SharedTokenProvider(
+            std::sync::Arc::new(provider),
+        ))));
+        self
+    }
+
+    /// Default privacy of collection writes.
+    ///
+    /// 新增收藏时，请求体未设置 `private` 则使用 `private`，参见 [`Client::post_user_collection`]。
+    /// 修改已有收藏的 [`Client::patch_user_collection`] 只在调用方设置了 `private` 时发送此字段。
+    pub fn default_private_collections(&mut self, private: bool) -> &mut Self {
+        self.default_private_collections = Some(Some(private));
+        self
+    }
+
+    /// Clock.
+    ///
+    /// 设置内部等待所使用的 [`Clock`]。
+    pub fn clock(&mut self, clock: impl Clock + 'static) -> &mut Self {
+        self.clock = Some(SharedClock(std::sync::Arc::new(clock)));
+        self
+    }
+
+    /// Interceptor.
+    ///
+    /// 注册一个 [`Interceptor`]，在每次发送请求前后被调用，多个拦截器按注册顺序调用。
+    pub fn interceptor(&mut self, interceptor: impl Interceptor + 'static) -> &mut Self {
+        self.interceptors
+            .get_or_insert_with(Default::default)
+            .0
+            .push(std::sync::Arc::new(interceptor));
+        self
+    }
+
+    /// Request hook.
+    ///
+    /// 注册一个在每次发送请求前调用的钩子，可以修改请求，例如注入请求头。
+    pub fn on_request(
+        &mut self,
+        hook: impl Fn(&'static str, &mut reqwest::Request) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.interceptor(OnRequest(hook))
+    }
+
+    /// Response hook.
+    ///
+    /// 注册一个在每次收到响应后调用的钩子，参数为 API 名称、响应与耗时。
+    pub fn on_response(
+        &mut self,
+        hook: impl Fn(&'static str, &reqwest::Response, std::time::Duration) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.interceptor(OnResponse(hook))
+    }
+
+    /// Image URL rewriter.
+    ///
+    /// 设置改写 [`IMAGE_HOST`](hooks::IMAGE_HOST) 下图片 URL 的钩子，返回 `None` 时保留原 URL，参见 [`hooks`] 模块。
+    pub fn image_url_rewriter(
+        &mut self,
+        rewriter: impl Fn(&url::Url) -> Option<url::Url> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.image_url_rewriter = Some(Some(ImageUrlRewriter(std::sync::Arc::new(rewriter))));
+        self
+    }
+
+    /// Middleware stack.
+    ///
+    /// 通过 [`reqwest_middleware::ClientWithMiddleware`] 发送所有请求，而不是内部的 [`reqwest::Client`]。
+    #[cfg(feature = "middleware")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "middleware")))]
+    pub fn client_middleware(
+        &mut self,
+        middleware: reqwest_middleware::ClientWithMiddleware,
+    ) -> &mut Self {
+        self.middleware = Some(Some(middleware));
+        self
+    }
+
+    /// HTTP transport.
+    ///
+    /// 通过自定义的 [`HttpTransport`] 发送所有请求。
+    pub fn transport(&mut self, transport: impl HttpTransport + 'static) -> &mut Self {
+        self.transport = Some(Some(SharedTransport(std::sync::Arc::new(transport))));
+        self
+    }
+
+    /// Per-endpoint-class override.
+    ///
+    /// 为 `class` 类的 API 设置 [`EndpointOverride`](overrides::EndpointOverride)，同一类别多次设置时以最后一次为准。
+    pub fn endpoint_override(
+        &mut self,
+        class: overrides::EndpointClass,
+        endpoint_override: overrides::EndpointOverride,
+    ) -> &mut Self {
+        self.endpoint_overrides
+            .get_or_insert_with(Default::default)
+            .0
+            .insert(class, endpoint_override);
+        self
+    }
+
+    /// Client-side rate limit.
+    ///
+    /// 所有请求在发送前都会经过同一个限流器，参见 [`scheduler`] 模块。
+    pub fn rate_limit(&mut self, limit: scheduler::RateLimit) -> &mut Self {
+        self.rate_limiter = Some(Some(std::sync::Arc::new(scheduler::RateLimiter::new(
+            limit,
+        ))));
+        self
+    }
+
+    /// Response cache.
+    ///
+    /// 开启内存中的 LRU + TTL 响应缓存，参见 [`cache`] 模块。
+    #[cfg(feature = "cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+    pub fn cache(&mut self, config: cache::CacheConfig) -> &mut Self {
+        self.cache = Some(Some(std::sync::Arc::new(cache::ResponseCache::new(config))));
+        self
+    }
+
+    /// Offline snapshot store.
+    ///
+    /// 设置 [`Client::with_snapshot`] 保存与读取快照所使用的 [`SnapshotStore`](offline::SnapshotStore)。
+    pub fn snapshot_store(&mut self, store: impl offline::SnapshotStore + 'static) -> &mut Self {
+        self.snapshot_store = Some(Some(offline::SharedSnapshotStore(std::sync::Arc::new(
+            store,
+        ))));
+        self
+    }
+
+    /// Disable system proxies.
+    ///
+    /// 不再使用环境变量中的代理。
+    pub fn no_proxy(&mut self) -> &mut Self {
+        self.no_proxy = Some(true);
+        self
+    }
+
     fn default_client(&self) -> Result<reqwest::Client, UninitializedFieldError> {
-        let mut headers = reqwest::header::HeaderMap::new();
-        if let Some(token) = self.token.clone().flatten() {
-            headers.insert(
-                reqwest::header::AUTHORIZATION,
-                reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
-            );
+        let mut builder = reqwest::Client::builder().user_agent(
+            self.user_agent
+                .clone()
+                .flatten()
+                .unwrap_or(DEFAULT_USER_AGENT.to_string()),
+        );
+        // 同时启用两个 TLS feature 时优先使用 rustls，便于在默认 feature 之上追加 `rustls`
+        #[cfg(feature = "rustls")]
+        {
+            builder = builder.use_rustls_tls();
+        }
+        if let Some(timeout) = self.timeout.flatten() {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout.flatten() {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout.flatten() {
+            builder = builder.read_timeout(timeout);
+        }
+        if self.no_proxy.unwrap_or_default() {
+            builder = builder.no_proxy();
+        }
+        if let Some(proxy) = self.proxy.clone().flatten() {
+            let proxy =
+                reqwest::Proxy::all(proxy).map_err(|_| UninitializedFieldError::new("proxy"))?;
+            builder = builder.proxy(proxy);
         }
-        reqwest::Client::builder()
-            .user_agent(
-                self.user_agent
-                    .clone()
-                    .flatten()
-                    .unwrap_or(DEFAULT_USER_AGENT.to_string()),
-            )
-            .default_headers(headers)
+        builder
             .build()
             .map_err(|_| UninitializedFieldError::new("client"))
     }
 }
 
+/// 解析 base URL，并确保路径以 `/` 结尾
+fn parse_base_url(url: Option<&str>, default: &str) -> Result<url::Url, String> {
+    let raw = url.unwrap_or(default);
+    let mut url =
+        url::Url::parse(raw).map_err(|err| format!("Invalid base URL `{}`: {}", raw, err))?;
+    if !matches!(url.scheme(), "http" | "https") || url.cannot_be_a_base() {
+        return Err(format!("Invalid base URL `{}`: not an HTTP URL", raw));
+    }
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(url)
+}
+
+/// 将响应体反序列化为 JSON，所有 JSON 响应的解析都经过此函数
+#[cfg(not(feature = "simd-json"))]
+fn decode_json<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, DepsError> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// 将响应体反序列化为 JSON，所有 JSON 响应的解析都经过此函数
+///
+/// 优先使用 simd-json 解析；simd-json 不支持 [`serde_json::value::RawValue`]，
+/// 解析失败时改用 `serde_json`，同时保证错误信息与未启用此 feature 时一致。
+#[cfg(feature = "simd-json")]
+fn decode_json<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, DepsError> {
+    let mut buf = body.to_vec();
+    match simd_json::serde::from_slice(&mut buf) {
+        Ok(value) => Ok(value),
+        Err(_) => Ok(serde_json::from_slice(body)?),
+    }
+}
+
+/// 会被隐去的敏感查询参数与表单字段
+#[cfg(any(feature = "tracing", feature = "recorder"))]
+const SECRET_PARAMS: &[&str] = &["access_token", "token", "client_secret"];
+
+/// 隐去 `application/x-www-form-urlencoded` 格式内容中 [`SECRET_PARAMS`] 的值
+///
+/// 不含敏感参数时返回 `None`，调用方可以继续使用原始内容。
+#[cfg(any(feature = "tracing", feature = "recorder"))]
+fn redact_form(input: &[u8]) -> Option<String> {
+    let pairs = url::form_urlencoded::parse(input);
+    if !pairs
+        .clone()
+        .any(|(key, _)| SECRET_PARAMS.contains(&key.as_ref()))
+    {
+        return None;
+    }
+    let pairs = pairs.map(|(key, value)| {
+        let value = if SECRET_PARAMS.contains(&key.as_ref()) {
+            "[redacted]".into()
+        } else {
+            value
+        };
+        (key, value)
+    });
+    Some(
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish(),
+    )
+}
+
+/// 复制 URL 并隐去 [`SECRET_PARAMS`] 中的查询参数，用于日志与 fixture 等需要保存 URL 的场景
+#[cfg(any(feature = "tracing", feature = "recorder"))]
+fn redact_url(url: &url::Url) -> url::Url {
+    let mut url = url.clone();
+    if let Some(query) = url.query().and_then(|query| redact_form(query.as_bytes())) {
+        url.set_query(Some(&query));
+    }
+    url
+}
+
+/// 请求未压缩的响应体
+///
+/// 图片本身已经是压缩格式，再次压缩没有收益，还会使 `Content-Length` 与 `Range` 失效。
+/// 启用 `gzip` 等 feature 后 JSON 请求会协商压缩，图片请求则通过此函数声明 `Accept-Encoding: identity`。
+fn identity_encoding(req: &mut reqwest::Request) {
+    req.headers_mut()
+        .entry(reqwest::header::ACCEPT_ENCODING)
+        .or_insert(reqwest::header::HeaderValue::from_static("identity"));
+}
+
+/// 检查已知的响应体大小是否超过 `limit`
+fn check_size(size: Option<u64>, limit: u64) -> Result<(), DepsError> {
+    match size {
+        Some(size) if size > limit => Err(DepsError::ResponseTooLarge { limit }),
+        _ => Ok(()),
+    }
+}
+
+/// 在 `base` 的路径后依次追加百分号编码后的 `segments`
+fn join_segments(base: &url::Url, segments: &[&str]) -> Result<url::Url, DepsError> {
+    Ok(crate::endpoints::join(base, segments)?)
+}
+
 impl Default for Client {
     fn default() -> Self {
         Self::new()
@@ -115,8 +692,22 @@ impl Client {
     }
 
     /// Get the base URL of the API.
+    ///
+    /// 返回的 URL 不包含末尾的 `/`。
     pub fn base_url(&self) -> &str {
-        &self.base_url
+        self.base_url.as_str().trim_end_matches('/')
+    }
+
+    /// 拼接 API 的路径，例如 `["v0", "users", username]`
+    ///
+    /// 每一段都会被百分号编码，因此用户名等参数中的空格、`/` 与非 ASCII 字符不会破坏路径。
+    pub(crate) fn api_url(&self, segments: &[&str]) -> Result<url::Url, DepsError> {
+        join_segments(&self.base_url, segments)
+    }
+
+    /// 拼接 OAuth API 的路径，参见 [`api_url`](Self::api_url)
+    pub(crate) fn oauth_url(&self, segments: &[&str]) -> Result<url::Url, DepsError> {
+        join_segments(&self.oauth_base_url, segments)
     }
 
     /// Get the internal reqwest client.
@@ -130,125 +721,527 @@ impl Client {
     }
 
     /// Get the token.
-    pub fn token(&self) -> Option<&str> {
-        self.token.as_deref()
+    ///
+    /// 调用 [`TokenProvider`] 获取当前的 token。
+    pub async fn token(&self) -> Option<String> {
+        match self.token.get() {
+            Some(provider) => provider.0.token().await,
+            None => None,
+        }
     }
-}
 
-/// # Subjects Resource (条目资源)
-///
-/// | API                                         | Description      | Methods                                                    |
-/// | :------------------------------------------ | :--------------- | :--------------------------------------------------------- |
-/// | `POST /v0/search/subjects`                  | 条目搜索         | [`search_subjects`](Client::search_subjects)               |
-/// | `GET  /v0/subjects`                         | 浏览条目         | [`get_subjects`](Client::get_subjects)                     |
-/// | `GET  /v0/subjects/{subject_id}`            | 获取条目         | [`get_subject`](Client::get_subject)                       |
-/// | `GET  /v0/subjects/{subject_id}/image`      | 获取条目图片     | [`get_subject_image`](Client::get_subject_image)           |
-/// | `GET  /v0/subjects/{subject_id}/persons`    | 获取条目相关人物 | [`get_subject_persons`](Client::get_subject_persons)       |
-/// | `GET  /v0/subjects/{subject_id}/characters` | 获取条目相关角色 | [`get_subject_characters`](Client::get_subject_characters) |
-/// | `GET  /v0/subjects/{subject_id}/subjects`   | 获取条目相关条目 | [`get_subject_subjects`](Client::get_subject_subjects)     |
-impl Client {
-    /// # 条目搜索 `POST /v0/search/subjects`
+    /// Replace the token.
     ///
-    /// 返回一个 Builder 模式的 [`SearchSubjectsExecutorBuilder`](subjects::SearchSubjectsExecutorBuilder), 用于构建请求参数并发送请求
+    /// 在运行中替换为新的固定 token，之后发送的请求都会使用新的 token，已经在发送中的请求不受影响。
+    /// 连接池与限流器会被保留，适用于定期轮换 token 的服务；响应缓存会被清空，
+    /// 避免新 token 读到之前用户的 [`get_me`](Self::get_me)、收藏等数据。
     ///
     /// ## Example
     ///
     /// ```
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
-    /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
-    /// let subjects = client.search_subjects()
-    ///     .keyword("魔法禁书目录")
-    ///     .sort(SortType::Match)
-    ///     .limit(1)
-    ///     .offset(0)
-    ///     .filter(
-    ///         SearchSubjectsFilter::builder()
-    ///         .r#type(SubjectType::Anime)
-    ///         .build()?
-    ///     )
-    ///     .send()
-    ///     .await?;
+    /// # async fn main() {
+    /// let client = Client::builder().token("old_token").build().unwrap();
     ///
-    /// assert_eq!(subjects.data[0].id, 1014);
-    /// assert_eq!(subjects.data[0].name, "とある魔術の禁書目録");
-    /// # Ok(())
+    /// client.set_token("new_token");
+    /// assert_eq!(client.token().await.as_deref(), Some("new_token"));
     /// # }
     /// ```
-    pub fn search_subjects(&self) -> subjects::SearchSubjectsExecutorBuilder {
-        subjects::SearchSubjectsExecutor::builder(self)
+    pub fn set_token(&self, token: impl Into<String>) {
+        self.set_token_provider(StaticToken(token.into()));
     }
 
-    /// # 浏览条目 `GET /v0/subjects`
-    ///
-    /// 返回一个 Builder 模式的 [`GetSubjectsExecutorBuilder`](subjects::GetSubjectsExecutorBuilder), 用于构建请求参数并发送请求
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// # use bgmtv::prelude::*;
-    /// # #[tokio::main]
-    /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
-    /// let subjects = client.get_subjects()
-    ///     .r#type(SubjectType::Book)
-    ///     .cat(SubjectCategory::Book(SubjectBookCategory::Novel))
-    ///     .sort("date")
-    ///     .year(2023)
-    ///     .limit(1)
-    ///     .send()
-    ///     .await?;
+    /// Replace the token provider.
     ///
-    /// assert_eq!(subjects.data[0].id, 469252);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_subjects(&self) -> subjects::GetSubjectsExecutorBuilder {
-        subjects::GetSubjectsExecutor::builder(self)
+    /// 与 [`set_token`](Self::set_token) 相同，但替换为自定义的 [`TokenProvider`]。
+    pub fn set_token_provider(&self, provider: impl TokenProvider + 'static) {
+        self.token
+            .set(Some(SharedTokenProvider(std::sync::Arc::new(provider))));
+        self.token_changed();
     }
 
-    /// # 获取条目 `GET /v0/subjects/{subject_id}`
-    ///
-    /// ## Arguments
-    ///
-    /// * `subject_id` - 条目 ID
-    ///
-    /// ## Example
+    /// Remove the token.
     ///
-    /// ```
-    /// # use bgmtv::prelude::*;
-    /// # #[tokio::main]
-    /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
-    /// let subject = client.get_subject(3559).await?;
-    ///
-    /// assert_eq!(subject.name, "とある魔術の禁書目録");
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn get_subject(&self, subject_id: u64) -> Result<Subject, DepsError> {
-        let url = format!("{}/v0/subjects/{}", self.base_url, subject_id);
+    /// 之后发送的请求都不再携带 token，响应缓存会被清空。
+    pub fn clear_token(&self) {
+        self.token.set(None);
+        self.token_changed();
+    }
 
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+    /// token 被替换后清空以 URL 为键的响应缓存，其中可能包含之前用户的私有数据，
+    /// 并丢弃 [`token_status`](Self::token_status) 查询到的权限
+    fn token_changed(&self) {
+        #[cfg(feature = "cache")]
+        self.clear_cache();
+        self.set_reported_scopes(None);
+    }
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+    fn set_reported_scopes(&self, scopes: Option<Scopes>) {
+        *self
+            .reported_scopes
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = scopes;
+    }
 
-        let subject: Subject = res.json().await?;
+    /// Get the granted scopes.
+    ///
+    /// 优先返回最近一次 [`token_status`](Self::token_status) 查询到的权限，否则返回 [`ClientBuilder::scopes`] 声明的权限。
+    pub fn scopes(&self) -> Option<Scopes> {
+        self.reported_scopes
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .or(self.scopes)
+    }
 
-        Ok(subject)
+    /// Get the default privacy of collection writes set by [`ClientBuilder::default_private_collections`].
+    pub fn default_private_collections(&self) -> Option<bool> {
+        self.default_private_collections
     }
 
-    /// # 获取条目图片 `GET /v0/subjects/{subject_id}/image`
+    /// 检查 token 是否具有 `required` 中的所有权限
     ///
-    /// ## Arguments
+    /// 权限来自 [`scopes`](Self::scopes)，既未声明也未通过 [`token_status`](Self::token_status) 查询到权限时不做检查。
+    pub fn check_scopes(&self, required: Scopes) -> Result<(), WriteError> {
+        match self.scopes() {
+            Some(granted) if !granted.contains(required) => Err(WriteError::InsufficientScope {
+                description: format!("missing scopes: {}", (required - granted).names()),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 检查条目是否可以被修改
     ///
-    /// * `subject_id` - 条目 ID
-    /// * `type` - 图片类型
+    /// 条目已被锁定 (`locked` 为 `true`) 时返回 [`WriteError::SubjectLocked`]，服务器会拒绝对其的修改。
+    pub fn check_unlocked(&self, subject: &Subject) -> Result<(), WriteError> {
+        if subject.is_locked() {
+            return Err(WriteError::SubjectLocked {
+                description: format!("subject {} is locked", subject.id),
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the clock.
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.0.as_ref()
+    }
+
+    /// Clear the response cache.
+    ///
+    /// 清空所有缓存的响应，未开启缓存时不做任何事。
+    #[cfg(feature = "cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Get the request statistics.
+    ///
+    /// 返回此 [`Client`] 发出请求的统计快照，包括请求总数、各 API 的请求数、下载字节数与缓存命中数。
+    pub fn stats(&self) -> RequestStats {
+        self.stats.snapshot()
+    }
+
+    /// 发送请求
+    ///
+    /// 所有 API 方法都通过此方法发送请求，发送前会附加 [`default_headers`](Self::default_headers)，
+    /// 并从 [`TokenProvider`] 获取 token 附加到请求头
+    /// (仅限发往 bgm.tv API 的请求，图片 CDN 等其他域名不会收到 token)，
+    /// 并在发送前后调用注册的 [`Interceptor`]。
+    ///
+    /// `endpoint` 为 API 名称，例如 `GET /v0/subjects/{subject_id}`，用于请求统计。
+    ///
+    /// 响应为 `429 Too Many Requests` 时，根据 [`RetryPolicy`] 等待 `Retry-After` 后重试，
+    /// 或返回 [`DepsError::RateLimited`]；网络错误时根据 [`RetryPolicy`] 与请求是否幂等决定是否重试。
+    ///
+    /// 每次调用会附加 `X-Request-Id` 请求头，参见 [`request_id`] 模块。
+    ///
+    /// 启用 `tracing` feature 时，整个调用（包括重试）在一个 `bgmtv.request` span 中执行。
+    pub(crate) async fn execute(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+    ) -> Result<reqwest::Response, DepsError> {
+        let request_id = request_id::ensure(&mut req);
+
+        #[cfg(feature = "tracing")]
+        let res = {
+            use tracing::Instrument;
+
+            let span = trace::span(endpoint, &req, &request_id);
+            self.execute_attempts(endpoint, req, &request_id)
+                .instrument(span)
+                .await
+        };
+
+        #[cfg(not(feature = "tracing"))]
+        let res = self.execute_attempts(endpoint, req, &request_id).await;
+
+        let mut res = res?;
+        res.extensions_mut()
+            .insert(request_id::RequestId(request_id));
+        Ok(res)
+    }
+
+    async fn execute_attempts(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+        request_id: &str,
+    ) -> Result<reqwest::Response, DepsError> {
+        for (name, value) in &self.default_headers {
+            if !req.headers().contains_key(name) {
+                req.headers_mut().insert(name, value.clone());
+            }
+        }
+
+        if let Some(token) = self.token().await.filter(|_| self.is_api_origin(req.url())) {
+            req.headers_mut().insert(
+                reqwest::header::AUTHORIZATION,
+                reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))?,
+            );
+        }
+
+        let overrides = self.endpoint_overrides.get(endpoint);
+        if let Some(timeout) = overrides.timeout {
+            *req.timeout_mut() = Some(timeout);
+        }
+        let retry = overrides.retry.or(self.retry);
+        let idempotent = retry::is_idempotent(endpoint, req.method());
+
+        let priority = scheduler::current_priority();
+        let mut history = retry::RetryHistory::default();
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                let weight = overrides.rate_weight.unwrap_or(1);
+                limiter.acquire(priority, self.clock(), weight).await;
+            }
+
+            let next = retry.and_then(|_| req.try_clone());
+
+            self.stats.record_request(endpoint);
+            self.interceptors.on_request(endpoint, &mut req);
+            #[cfg(feature = "tracing")]
+            trace::request(&req, attempt);
+            let start = self.clock().now();
+            let res = self.send(req).await;
+            #[cfg(feature = "metrics")]
+            let res = res.inspect_err(|err| instruments::error(endpoint, err));
+            let res = match res {
+                Ok(res) => res,
+                Err(err) => {
+                    let delay =
+                        retry.and_then(|policy| policy.error_delay(attempt, &err, idempotent));
+                    match (delay, next) {
+                        (Some(delay), Some(next)) => {
+                            history.push(None, Some(delay));
+                            self.clock().sleep(delay).await;
+                            req = next;
+                            attempt += 1;
+                            continue;
+                        }
+                        _ => {
+                            history.push(None, None);
+                            retry::track(&history);
+                            if history.retries() == 0 {
+                                return Err(err);
+                            }
+                            return Err(DepsError::Network {
+                                error: Box::new(err),
+                                history,
+                                request_id: Some(request_id.to_string()),
+                            });
+                        }
+                    }
+                }
+            };
+            let elapsed = self.clock().now() - start;
+            #[cfg(feature = "metrics")]
+            instruments::response(endpoint, &res, elapsed);
+            #[cfg(feature = "tracing")]
+            trace::response(&res, elapsed, attempt);
+            self.interceptors.on_response(endpoint, &res, elapsed);
+            if res.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                history.push(Some(res.status().as_u16()), None);
+                retry::track(&history);
+                return Ok(res);
+            }
+
+            let retry_after = retry::retry_after(res.headers(), self.clock().system_time());
+            let delay =
+                retry.and_then(|policy| policy.rate_limit_delay(attempt, retry_after, idempotent));
+            match (delay, next) {
+                (Some(delay), Some(next)) => {
+                    history.push(Some(res.status().as_u16()), Some(delay));
+                    self.clock().sleep(delay).await;
+                    req = next;
+                    attempt += 1;
+                }
+                _ => {
+                    history.push(Some(res.status().as_u16()), None);
+                    retry::track(&history);
+                    return Err(DepsError::RateLimited {
+                        retry_after,
+                        history,
+                        request_id: Some(request_id.to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    /// `url` 是否与 [`base_url`](Self::base_url) 或 OAuth API 同源，只有同源的请求会附加 token
+    fn is_api_origin(&self, url: &url::Url) -> bool {
+        [&self.base_url, &self.oauth_base_url]
+            .iter()
+            .any(|base| base.origin() == url.origin())
+    }
+
+    /// 通过中间件或内部的 [`reqwest::Client`] 发送请求
+    async fn send(&self, req: reqwest::Request) -> Result<reqwest::Response, DepsError> {
+        if let Some(transport) = &self.transport {
+            let mut req = req;
+            if !req.headers().contains_key(reqwest::header::USER_AGENT) {
+                req.headers_mut().insert(
+                    reqwest::header::USER_AGENT,
+                    reqwest::header::HeaderValue::from_str(self.user_agent())?,
+                );
+            }
+            return transport.0.execute(req).await;
+        }
+
+        #[cfg(feature = "middleware")]
+        if let Some(middleware) = &self.middleware {
+            return Ok(middleware.execute(req).await?);
+        }
+
+        Ok(self.client.execute(req).await?)
+    }
+
+    /// 获取可能在服务端分页的列表的一页，同时兼容未分页的列表与分页结果两种响应
+    async fn get_relation_page<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &'static str,
+        segments: &[&str],
+        options: PageOptions,
+    ) -> Result<Paged<T>, DepsError> {
+        let url = self.api_url(segments)?;
+
+        let mut req = self.client.get(url);
+        if let Some(limit) = options.limit {
+            req = req.query(&[("limit", limit)]);
+        }
+        if let Some(offset) = options.offset {
+            req = req.query(&[("offset", offset)]);
+        }
+
+        let res: ListOrPaged<T> = self.execute_json(endpoint, req.build()?).await?;
+        Ok(res.into_paged(options.limit, options.offset))
+    }
+
+    /// 获取可能在服务端分页的列表的全部数据
+    async fn get_relation_all<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &'static str,
+        segments: &[&str],
+    ) -> Result<Vec<T>, DepsError> {
+        let mut items = Vec::new();
+        let mut options = PageOptions::default();
+        loop {
+            let page: Paged<T> = self.get_relation_page(endpoint, segments, options).await?;
+            let (has_next, next_offset) = (page.has_next(), page.next_offset());
+            items.extend(page.data);
+            if !has_next {
+                return Ok(items);
+            }
+            options = options.offset(next_offset);
+        }
+    }
+
+    /// 发送请求并将响应体反序列化为 JSON
+    ///
+    /// 所有返回 JSON 的 API 都通过此方法发送请求：设置 `Accept` 请求头、检查状态码、解析错误响应体，
+    /// 然后反序列化响应体。
+    pub(crate) async fn execute_json<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+    ) -> Result<T, DepsError> {
+        req.headers_mut().insert(
+            reqwest::header::ACCEPT,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| req.method() == reqwest::Method::GET)
+        {
+            let body = self.execute_cached(cache, endpoint, req).await?;
+            return self.decode_json(&body);
+        }
+
+        let res = self.execute(endpoint, req).await?;
+        let res = self.check_status(res).await?;
+        let body = self.read_bytes(res, self.max_json_size).await?;
+
+        self.decode_json(&body)
+    }
+
+    /// 将响应体反序列化为 JSON，并改写其中的图片 URL
+    fn decode_json<T: serde::de::DeserializeOwned>(&self, body: &[u8]) -> Result<T, DepsError> {
+        let Some(rewriter) = &self.image_url_rewriter else {
+            return decode_json(body);
+        };
+
+        let mut value = decode_json(body)?;
+        rewriter.rewrite_json(&mut value);
+        // 重新序列化而不是 `from_value`，`RawValue` 不支持从 `Value` 反序列化
+        decode_json(&serde_json::to_vec(&value)?)
+    }
+
+    /// 改写图片下载方法所请求的 URL
+    pub(crate) fn image_url<'a>(&self, url: &'a str) -> std::borrow::Cow<'a, str> {
+        match &self.image_url_rewriter {
+            Some(rewriter) => rewriter.rewrite_url(url),
+            None => url.into(),
+        }
+    }
+
+    /// 发送请求并返回响应体
+    ///
+    /// 用于返回二进制数据 (例如图片) 的 API，会检查状态码并解析错误响应体，
+    /// 响应体的大小受 [`max_image_size`](Self::max_image_size) 限制。
+    pub(crate) async fn execute_bytes(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+    ) -> Result<bytes::Bytes, DepsError> {
+        identity_encoding(&mut req);
+        let res = self.execute(endpoint, req).await?;
+        let res = self.check_status(res).await?;
+
+        self.read_bytes(res, self.max_image_size).await
+    }
+
+    /// 发送请求并丢弃响应体
+    ///
+    /// 用于成功时返回 `204 No Content` 的修改类 API，会检查状态码并解析错误响应体。
+    pub(crate) async fn execute_empty(
+        &self,
+        endpoint: &'static str,
+        req: reqwest::Request,
+    ) -> Result<(), DepsError> {
+        let res = self.execute(endpoint, req).await?;
+        let res = self.check_status(res).await?;
+
+        self.read_bytes(res, self.max_json_size).await?;
+        Ok(())
+    }
+
+    /// 检查响应状态码
+    ///
+    /// 非 2xx 状态码时读取错误响应体并解析为 [`BgmApiError`]。
+    async fn check_status(&self, res: reqwest::Response) -> Result<reqwest::Response, DepsError> {
+        let status = res.status();
+
+        if status.is_client_error() || status.is_server_error() {
+            let request_id = res
+                .extensions()
+                .get::<request_id::RequestId>()
+                .map(|id| id.0.clone());
+            let body = self.read_bytes(res, self.max_json_size).await?;
+            let err = BgmApiError::from_response_body(status.as_u16(), &body);
+            return Err(BgmApiError { request_id, ..err }.into());
+        }
+
+        Ok(res)
+    }
+
+    /// 读取响应体并统计下载字节数
+    ///
+    /// 响应体超过 `limit` 字节时停止读取并返回 [`DepsError::ResponseTooLarge`]。
+    async fn read_bytes(
+        &self,
+        mut res: reqwest::Response,
+        limit: Option<u64>,
+    ) -> Result<bytes::Bytes, DepsError> {
+        let Some(limit) = limit else {
+            let bytes = res.bytes().await?;
+            self.stats.record_bytes(bytes.len() as u64);
+            return Ok(bytes);
+        };
+
+        check_size(res.content_length(), limit)?;
+        let mut body = bytes::BytesMut::new();
+        while let Some(chunk) = res.chunk().await? {
+            self.stats.record_bytes(chunk.len() as u64);
+            check_size(Some((body.len() + chunk.len()) as u64), limit)?;
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.freeze())
+    }
+}
+
+/// # Subjects Resource (条目资源)
+///
+/// | API                                         | Description      | Methods                                                    |
+/// | :------------------------------------------ | :--------------- | :--------------------------------------------------------- |
+/// | `POST /v0/search/subjects`                  | 条目搜索         | [`search_subjects`](Client::search_subjects), [`browse_by_tag`](Client::browse_by_tag) |
+/// | `GET  /v0/subjects`                         | 浏览条目         | [`get_subjects`](Client::get_subjects)                     |
+/// | `GET  /v0/subjects/{subject_id}`            | 获取条目         | [`get_subject`](Client::get_subject), [`get_subjects_by_ids`](Client::get_subjects_by_ids), [`get_subject_full`](Client::get_subject_full) |
+/// | `GET  /v0/subjects/{subject_id}/image`      | 获取条目图片     | [`get_subject_image`](Client::get_subject_image), [`download_subject_image`](Client::download_subject_image), [`save_subject_image`](Client::save_subject_image) |
+/// | `GET  /v0/subjects/{subject_id}/persons`    | 获取条目相关人物 | [`get_subject_persons`](Client::get_subject_persons), [`get_subject_persons_page`](Client::get_subject_persons_page) |
+/// | `GET  /v0/subjects/{subject_id}/characters` | 获取条目相关角色 | [`get_subject_characters`](Client::get_subject_characters), [`get_subject_characters_page`](Client::get_subject_characters_page) |
+/// | `GET  /v0/subjects/{subject_id}/subjects`   | 获取条目相关条目 | [`get_subject_subjects`](Client::get_subject_subjects)     |
+impl Client {
+    /// # 条目搜索 `POST /v0/search/subjects`
+    ///
+    /// 返回一个 Builder 模式的 [`SearchSubjectsExecutorBuilder`](subjects::SearchSubjectsExecutorBuilder), 用于构建请求参数并发送请求
+    ///
+    /// Builder 实现了 [`IntoFuture`](std::future::IntoFuture)，可以省略 `send()` 直接 `.await`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let subjects = client.search_subjects()
+    ///     .keyword("魔法禁书目录")
+    ///     .sort(SortType::Match)
+    ///     .limit(1)
+    ///     .offset(0)
+    ///     .filter(
+    ///         SearchSubjectsFilter::builder()
+    ///         .r#type(SubjectType::Book)
+    ///         .build()?
+    ///     )
+    ///     .send()
+    ///     .await?;
+    ///
+    /// assert_eq!(subjects.data[0].id, 3559);
+    /// assert_eq!(subjects.data[0].name, "とある魔術の禁書目録");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_subjects(&self) -> subjects::SearchSubjectsExecutorBuilder<'_> {
+        subjects::SearchSubjectsExecutor::builder(self)
+    }
+
+    /// # 浏览条目 `GET /v0/subjects`
+    ///
+    /// 返回一个 Builder 模式的 [`GetSubjectsExecutorBuilder`](subjects::GetSubjectsExecutorBuilder), 用于构建请求参数并发送请求
+    ///
+    /// Builder 实现了 [`IntoFuture`](std::future::IntoFuture)，可以省略 `send()` 直接 `.await`
     ///
     /// ## Example
     ///
@@ -256,17 +1249,120 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let subjects = client.get_subjects()
+    ///     .r#type(SubjectType::Book)
+    ///     .cat(SubjectCategory::Book(SubjectBookCategory::Novel))
+    ///     .sort(BrowseSort::Date)
+    ///     .year(2004)
+    ///     .limit(1)
+    ///     .send()
+    ///     .await?;
+    ///
+    /// assert_eq!(subjects.data[0].id, 3559);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_subjects(&self) -> subjects::GetSubjectsExecutorBuilder<'_> {
+        subjects::GetSubjectsExecutor::builder(self)
+    }
+
+    /// # 按标签浏览条目 `POST /v0/search/subjects`
+    ///
+    /// 浏览条目 API 不支持按标签过滤，此方法返回设置了条目类型与 [`tag`](subjects::GetSubjectsExecutorBuilder::tag) 的
+    /// [`GetSubjectsExecutorBuilder`](subjects::GetSubjectsExecutorBuilder)，通过条目搜索 API 以空关键词加标签过滤查询，
+    /// 再获取每个条目的详情。可以继续设置年月、`limit` 与 `offset`，或通过 `pages()` / `items()` 逐页获取。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
     /// # let client = Client::new();
-    /// let image: Vec<u8> = client.get_subject_image(3559, ImageType::Small).await?;
+    /// let first = client.browse_by_tag("原创", SubjectType::Anime).limit(10).await?;
+    /// println!("{} subjects tagged 原创", first.total);
+    ///
+    /// let executor = client.browse_by_tag("原创", SubjectType::Anime).build()?;
+    /// let mut items = std::pin::pin!(executor.items());
+    /// while let Some(subject) = items.try_next().await? {
+    ///     println!("{}", subject.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn browse_by_tag(
+        &self,
+        tag: impl Into<String>,
+        subject_type: SubjectType,
+    ) -> subjects::GetSubjectsExecutorBuilder<'_> {
+        self.get_subjects().r#type(subject_type).tag(tag)
+    }
+
+    /// # 获取条目 `GET /v0/subjects/{subject_id}`
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let subject = client.get_subject(3559).await?;
+    ///
+    /// assert_eq!(subject.name, "とある魔術の禁書目録");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_subject(
+        &self,
+        subject_id: impl Into<SubjectId>,
+    ) -> Result<Subject, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let url = self.api_url(&["v0", "subjects", &subject_id.to_string()])?;
+
+        let req = self.client.get(url).build()?;
+
+        self.execute_json("GET /v0/subjects/{subject_id}", req)
+            .await
+    }
+
+    /// # 获取条目图片 `GET /v0/subjects/{subject_id}/image`
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    /// * `type` - 图片类型
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let image: bytes::Bytes = client.get_subject_image(3559, ImageType::Small).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_subject_image(
         &self,
-        subject_id: u64,
+        subject_id: impl Into<SubjectId>,
         image_type: ImageType,
-    ) -> Result<Vec<u8>, DepsError> {
-        let url = format!("{}/v0/subjects/{}/image", self.base_url, subject_id);
+    ) -> Result<bytes::Bytes, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let url = self.api_url(&["v0", "subjects", &subject_id.to_string(), "image"])?;
 
         let req = self
             .client
@@ -274,15 +1370,14 @@ impl Client {
             .query(&[("type", image_type)])
             .build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let image = res.bytes().await?;
-
-        Ok(image.to_vec())
+        self.execute_bytes("GET /v0/subjects/{subject_id}/image", req)
+            .await
     }
 
     /// # 获取条目相关人物 `GET /v0/subjects/{subject_id}/persons`
     ///
+    /// 返回全部相关人物。此 API 目前返回未分页的列表，如果服务端改为分页返回，会自动依次获取所有分页。
+    ///
     /// ## Arguments
     ///
     /// * `subject_id` - 条目 ID
@@ -293,35 +1388,73 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let persons = client.get_subject_persons(3559).await?;
     ///
-    /// let person = persons.iter().find(|p| p.id == 3608);
+    /// let person = persons.iter().find(|p| p.id == 4736);
     /// assert_eq!(person.map(|p| p.name.as_str()), Some("鎌池和馬"));
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_subject_persons(
         &self,
-        subject_id: u64,
+        subject_id: impl Into<SubjectId>,
     ) -> Result<Vec<RelatedPerson>, DepsError> {
-        let url = format!("{}/v0/subjects/{}/persons", self.base_url, subject_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
-
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let persons: Vec<RelatedPerson> = res.json().await?;
+        let subject_id: SubjectId = subject_id.into();
+
+        let id = subject_id.to_string();
+        self.get_relation_all(
+            "GET /v0/subjects/{subject_id}/persons",
+            &["v0", "subjects", &id, "persons"],
+        )
+        .await
+    }
 
-        Ok(persons)
+    /// # 分页获取条目相关人物 `GET /v0/subjects/{subject_id}/persons`
+    ///
+    /// 服务端分页时直接返回对应的分页，否则在本地按 `options` 截取。
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    /// * `options` - 分页参数
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// use bgmtv::client::pagination::PageOptions;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let page = client
+    ///     .get_subject_persons_page(3559, PageOptions::default().limit(10))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_subject_persons_page(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        options: PageOptions,
+    ) -> Result<Paged<RelatedPerson>, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let id = subject_id.to_string();
+        self.get_relation_page(
+            "GET /v0/subjects/{subject_id}/persons",
+            &["v0", "subjects", &id, "persons"],
+            options,
+        )
+        .await
     }
 
     /// # 获取条目相关角色 `GET /v0/subjects/{subject_id}/characters`
     ///
+    /// 返回全部相关角色。此 API 目前返回未分页的列表，如果服务端改为分页返回，会自动依次获取所有分页。
+    ///
     /// ## Arguments
     ///
     /// * `subject_id` - 条目 ID
@@ -332,7 +1465,8 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let characters = client.get_subject_characters(3559).await.expect("Failed to get subject characters");
     ///
     /// let character = characters.iter().find(|c| c.id == 3498);
@@ -342,21 +1476,40 @@ impl Client {
     /// ```
     pub async fn get_subject_characters(
         &self,
-        subject_id: u64,
+        subject_id: impl Into<SubjectId>,
     ) -> Result<Vec<RelatedCharacter>, DepsError> {
-        let url = format!("{}/v0/subjects/{}/characters", self.base_url, subject_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
-
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let characters: Vec<RelatedCharacter> = res.json().await?;
+        let subject_id: SubjectId = subject_id.into();
+
+        let id = subject_id.to_string();
+        self.get_relation_all(
+            "GET /v0/subjects/{subject_id}/characters",
+            &["v0", "subjects", &id, "characters"],
+        )
+        .await
+    }
 
-        Ok(characters)
+    /// # 分页获取条目相关角色 `GET /v0/subjects/{subject_id}/characters`
+    ///
+    /// 服务端分页时直接返回对应的分页，否则在本地按 `options` 截取。
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    /// * `options` - 分页参数
+    pub async fn get_subject_characters_page(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        options: PageOptions,
+    ) -> Result<Paged<RelatedCharacter>, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let id = subject_id.to_string();
+        self.get_relation_page(
+            "GET /v0/subjects/{subject_id}/characters",
+            &["v0", "subjects", &id, "characters"],
+            options,
+        )
+        .await
     }
 
     /// # 获取条目相关条目 `GET /v0/subjects/{subject_id}/subjects`
@@ -371,31 +1524,27 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let subjects = client.get_subject_subjects(3559).await?;
     ///
-    /// let subject = subjects.iter().find(|s| s.id == 3582);
-    /// assert_eq!(subject.map(|s| s.name.as_str()), Some("とある魔術の禁書目録外伝 とある科学の超電磁砲"));
+    /// let subject = subjects.iter().find(|s| s.id == 14514);
+    /// assert_eq!(subject.map(|s| s.name_cn.as_str()), Some("魔法禁书目录"));
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_subject_subjects(
         &self,
-        subject_id: u64,
+        subject_id: impl Into<SubjectId>,
     ) -> Result<Vec<SubjectRelation>, DepsError> {
-        let url = format!("{}/v0/subjects/{}/subjects", self.base_url, subject_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let subject_id: SubjectId = subject_id.into();
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let url = self.api_url(&["v0", "subjects", &subject_id.to_string(), "subjects"])?;
 
-        let subjects: Vec<SubjectRelation> = res.json().await?;
+        let req = self.client.get(url).build()?;
 
-        Ok(subjects)
+        self.execute_json("GET /v0/subjects/{subject_id}/subjects", req)
+            .await
     }
 }
 
@@ -403,8 +1552,8 @@ impl Client {
 ///
 /// | API                             | Description  | Methods                                |
 /// | :------------------------------ | :----------- | :------------------------------------- |
-/// | `GET /v0/episodes`              | 获取章节列表 | [`get_episodes`](Client::get_episodes) |
-/// | `GET /v0/episodes/{episode_id}` | 获取章节信息 | [`get_episode`](Client::get_episode)   |
+/// | `GET /v0/episodes`              | 获取章节列表 | [`get_episodes`](Client::get_episodes), [`episodes_of_subjects`](Client::episodes_of_subjects) |
+/// | `GET /v0/episodes/{episode_id}` | 获取章节信息 | [`get_episode`](Client::get_episode), [`get_episodes_by_ids`](Client::get_episodes_by_ids) |
 impl Client {
     /// # 获取章节列表 `GET /v0/episodes`
     ///
@@ -416,26 +1565,38 @@ impl Client {
     ///
     /// 返回一个 Builder 模式的 [`GetEpisodesExecutorBuilder`](episodes::GetEpisodesExecutorBuilder), 用于构建请求参数并发送请求
     ///
+    /// Builder 实现了 [`IntoFuture`](std::future::IntoFuture)，可以省略 `send()` 直接 `.await`
+    ///
     /// ## Example
     ///
     /// ```
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
-    /// let episodes = client.get_episodes(1014)
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let episodes = client.get_episodes(3559)
     ///     .r#type(EpisodeType::MainStory)
     ///     .limit(1)
     ///     .send()
     ///     .await?;
     ///
-    /// assert_eq!(episodes.data[0].id, 1731);
-    /// assert_eq!(episodes.data[0].name, "学園都市");
+    /// assert_eq!(episodes.data[0].id, 8);
+    /// assert_eq!(episodes.data[0].airdate, "2008-10-04");
+    ///
+    /// // 等同于上面的写法
+    /// let episodes = client.get_episodes(3559).limit(1).await?;
+    /// assert_eq!(episodes.data[0].id, 8);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_episodes(&self, subject_id: u64) -> episodes::GetEpisodesExecutorBuilder {
-        episodes::GetEpisodesExecutor::builder(self, subject_id)
+    pub fn get_episodes(
+        &self,
+        subject_id: impl Into<SubjectId>,
+    ) -> episodes::GetEpisodesExecutorBuilder<'_> {
+        let subject_id: SubjectId = subject_id.into();
+
+        episodes::GetEpisodesExecutor::builder(self, subject_id.0)
     }
 
     /// # 获取章节信息 `GET /v0/episodes/{episode_id}`
@@ -450,27 +1611,26 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
-    /// let episode = client.get_episode(1731).await?;
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let episode = client.get_episode(8).await?;
     ///
-    /// assert_eq!(episode.name, "学園都市");
+    /// assert_eq!(episode.airdate, "2008-10-04");
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_episode(&self, episode_id: u64) -> Result<Episode, DepsError> {
-        let url = format!("{}/v0/episodes/{}", self.base_url, episode_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+    pub async fn get_episode(
+        &self,
+        episode_id: impl Into<EpisodeId>,
+    ) -> Result<Episode, DepsError> {
+        let episode_id: EpisodeId = episode_id.into();
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let url = self.api_url(&["v0", "episodes", &episode_id.to_string()])?;
 
-        let episode: Episode = res.json().await?;
+        let req = self.client.get(url).build()?;
 
-        Ok(episode)
+        self.execute_json("GET /v0/episodes/{episode_id}", req)
+            .await
     }
 }
 
@@ -478,10 +1638,10 @@ impl Client {
 ///
 /// | API                                           | Description      | Methods                                                    |
 /// | :-------------------------------------------- | :--------------- | :--------------------------------------------------------- |
-/// | `GET  /v0/characters/{character_id}`          | 获取角色信息     | [`get_character`](Client::get_character)                   |
-/// | `GET  /v0/characters/{character_id}/image`    | 获取角色图片     | [`get_character_image`](Client::get_character_image)       |
-/// | `GET  /v0/characters/{character_id}/subjects` | 获取角色相关条目 | [`get_character_subjects`](Client::get_character_subjects) |
-/// | `GET  /v0/characters/{character_id}/persons`  | 获取角色相关人物 | [`get_character_persons`](Client::get_character_persons)   |
+/// | `GET  /v0/characters/{character_id}`          | 获取角色信息     | [`get_character`](Client::get_character), [`get_characters_by_ids`](Client::get_characters_by_ids) |
+/// | `GET  /v0/characters/{character_id}/image`    | 获取角色图片     | [`get_character_image`](Client::get_character_image), [`download_character_image`](Client::download_character_image), [`save_character_image`](Client::save_character_image) |
+/// | `GET  /v0/characters/{character_id}/subjects` | 获取角色相关条目 | [`get_character_subjects`](Client::get_character_subjects), [`character_appearances`](Client::character_appearances) |
+/// | `GET  /v0/characters/{character_id}/persons`  | 获取角色相关人物 | [`get_character_persons`](Client::get_character_persons), [`character_appearances`](Client::character_appearances) |
 impl Client {
     /// # 获取角色信息 `GET /v0/characters/{character_id}`
     ///
@@ -495,27 +1655,26 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let character = client.get_character(3498).await?;
     ///
     /// assert_eq!(character.name, "上条当麻");
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_character(&self, character_id: u64) -> Result<CharacterDetail, DepsError> {
-        let url = format!("{}/v0/characters/{}", self.base_url, character_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+    pub async fn get_character(
+        &self,
+        character_id: impl Into<CharacterId>,
+    ) -> Result<CharacterDetail, DepsError> {
+        let character_id: CharacterId = character_id.into();
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let url = self.api_url(&["v0", "characters", &character_id.to_string()])?;
 
-        let character: CharacterDetail = res.json().await?;
+        let req = self.client.get(url).build()?;
 
-        Ok(character)
+        self.execute_json("GET /v0/characters/{character_id}", req)
+            .await
     }
 
     /// # 获取角色图片 `GET /v0/characters/{character_id}/image`
@@ -531,17 +1690,20 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let image = client.get_character_image(3498, ImageType::Small).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_character_image(
         &self,
-        character_id: u64,
+        character_id: impl Into<CharacterId>,
         image_type: ImageType,
-    ) -> Result<Vec<u8>, DepsError> {
-        let url = format!("{}/v0/characters/{}/image", self.base_url, character_id);
+    ) -> Result<bytes::Bytes, DepsError> {
+        let character_id: CharacterId = character_id.into();
+
+        let url = self.api_url(&["v0", "characters", &character_id.to_string(), "image"])?;
 
         let req = self
             .client
@@ -549,11 +1711,8 @@ impl Client {
             .query(&[("type", image_type)])
             .build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let image = res.bytes().await?;
-
-        Ok(image.to_vec())
+        self.execute_bytes("GET /v0/characters/{character_id}/image", req)
+            .await
     }
 
     /// # 获取角色相关条目 `GET /v0/characters/{character_id}/subjects`
@@ -568,7 +1727,8 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let subjects = client.get_character_subjects(3498).await?;
     ///
     /// let subject = subjects.iter().find(|s| s.id == 3559);
@@ -578,21 +1738,16 @@ impl Client {
     /// ```
     pub async fn get_character_subjects(
         &self,
-        character_id: u64,
+        character_id: impl Into<CharacterId>,
     ) -> Result<Vec<RelatedSubject>, DepsError> {
-        let url = format!("{}/v0/characters/{}/subjects", self.base_url, character_id);
+        let character_id: CharacterId = character_id.into();
 
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let url = self.api_url(&["v0", "characters", &character_id.to_string(), "subjects"])?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let req = self.client.get(url).build()?;
 
-        let subjects: Vec<RelatedSubject> = res.json().await?;
-
-        Ok(subjects)
+        self.execute_json("GET /v0/characters/{character_id}/subjects", req)
+            .await
     }
 
     /// # 获取角色相关人物 `GET /v0/characters/{character_id}/persons`
@@ -607,7 +1762,8 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client= Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let persons = client.get_character_persons(3498).await?;
     ///
     /// let person = persons.iter().find(|p| p.subject_id == 1014);
@@ -617,21 +1773,16 @@ impl Client {
     /// ```
     pub async fn get_character_persons(
         &self,
-        character_id: u64,
+        character_id: impl Into<CharacterId>,
     ) -> Result<Vec<CharacterPerson>, DepsError> {
-        let url = format!("{}/v0/characters/{}/persons", self.base_url, character_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let character_id: CharacterId = character_id.into();
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let url = self.api_url(&["v0", "characters", &character_id.to_string(), "persons"])?;
 
-        let persons: Vec<CharacterPerson> = res.json().await?;
+        let req = self.client.get(url).build()?;
 
-        Ok(persons)
+        self.execute_json("GET /v0/characters/{character_id}/persons", req)
+            .await
     }
 }
 
@@ -639,9 +1790,9 @@ impl Client {
 ///
 /// | API                                       | Description      | Methods                                                  |
 /// | :---------------------------------------- | :--------------- | :------------------------------------------------------- |
-/// | `GET  /v0/persons/{person_id}`            | 获取人物信息     | [`get_person`](Client::get_person)                       |
-/// | `GET  /v0/persons/{person_id}/image`      | 获取人物图片     | [`get_person_image`](Client::get_person_image)           |
-/// | `GET  /v0/persons/{person_id}/subjects`   | 获取人物相关条目 | [`get_person_subjects`](Client::get_person_subjects)     |
+/// | `GET  /v0/persons/{person_id}`            | 获取人物信息     | [`get_person`](Client::get_person), [`get_persons_by_ids`](Client::get_persons_by_ids) |
+/// | `GET  /v0/persons/{person_id}/image`      | 获取人物图片     | [`get_person_image`](Client::get_person_image), [`download_person_image`](Client::download_person_image), [`save_person_image`](Client::save_person_image) |
+/// | `GET  /v0/persons/{person_id}/subjects`   | 获取人物相关条目 | [`get_person_subjects`](Client::get_person_subjects), [`person_timeline`](Client::person_timeline) |
 /// | `GET  /v0/persons/{person_id}/characters` | 获取人物相关角色 | [`get_person_characters`](Client::get_person_characters) |
 impl Client {
     /// # 获取人物信息 `GET /v0/persons/{person_id}`
@@ -656,27 +1807,25 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client= Client::new();
-    /// let person = client.get_person(3608).await?;
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let person = client.get_person(1).await?;
     ///
-    /// assert_eq!(person.name, "鎌池和馬");
+    /// assert_eq!(person.name, "水樹奈々");
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_person(&self, person_id: u64) -> Result<PersonDetail, DepsError> {
-        let url = format!("{}/v0/persons/{}", self.base_url, person_id);
-
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+    pub async fn get_person(
+        &self,
+        person_id: impl Into<PersonId>,
+    ) -> Result<PersonDetail, DepsError> {
+        let person_id: PersonId = person_id.into();
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let url = self.api_url(&["v0", "persons", &person_id.to_string()])?;
 
-        let person: PersonDetail = res.json().await?;
+        let req = self.client.get(url).build()?;
 
-        Ok(person)
+        self.execute_json("GET /v0/persons/{person_id}", req).await
     }
 
     /// # 获取人物图片 `GET /v0/persons/{person_id}/image`
@@ -692,17 +1841,20 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client= Client::new();
-    /// let image: Vec<u8> = client.get_person_image(3608, ImageType::Small).await?;
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let image: bytes::Bytes = client.get_person_image(1, ImageType::Small).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_person_image(
         &self,
-        person_id: u64,
+        person_id: impl Into<PersonId>,
         image_type: ImageType,
-    ) -> Result<Vec<u8>, DepsError> {
-        let url = format!("{}/v0/persons/{}/image", self.base_url, person_id);
+    ) -> Result<bytes::Bytes, DepsError> {
+        let person_id: PersonId = person_id.into();
+
+        let url = self.api_url(&["v0", "persons", &person_id.to_string(), "image"])?;
 
         let req = self
             .client
@@ -710,11 +1862,8 @@ impl Client {
             .query(&[("type", image_type)])
             .build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let image = res.bytes().await?;
-
-        Ok(image.to_vec())
+        self.execute_bytes("GET /v0/persons/{person_id}/image", req)
+            .await
     }
 
     /// # 获取人物相关条目 `GET /v0/persons/{person_id}/subjects`
@@ -729,8 +1878,9 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client= Client::new();
-    /// let subjects = client.get_person_subjects(3608).await?;
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let subjects = client.get_person_subjects(1).await?;
     ///
     /// let subject = subjects.iter().find(|s| s.id == 1014);
     /// assert_eq!(subject.map(|s| s.name.as_str()), Some("とある魔術の禁書目録"));
@@ -739,21 +1889,16 @@ impl Client {
     /// ```
     pub async fn get_person_subjects(
         &self,
-        person_id: u64,
+        person_id: impl Into<PersonId>,
     ) -> Result<Vec<RelatedSubject>, DepsError> {
-        let url = format!("{}/v0/persons/{}/subjects", self.base_url, person_id);
+        let person_id: PersonId = person_id.into();
 
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let url = self.api_url(&["v0", "persons", &person_id.to_string(), "subjects"])?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let req = self.client.get(url).build()?;
 
-        let subjects: Vec<RelatedSubject> = res.json().await?;
-
-        Ok(subjects)
+        self.execute_json("GET /v0/persons/{person_id}/subjects", req)
+            .await
     }
 
     /// # 获取人物相关角色 `GET /v0/persons/{person_id}/characters`
@@ -768,31 +1913,27 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client= Client::new();
-    /// let characters = client.get_person_characters(5015).await?;
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
+    /// let characters = client.get_person_characters(1).await?;
     ///
-    /// let character = characters.iter().find(|c| c.id == 3498);
-    /// assert_eq!(character.map(|c| c.name.as_str()), Some("上条当麻"));
+    /// let character = characters.iter().find(|c| c.id == 3575);
+    /// assert_eq!(character.map(|c| c.name.as_str()), Some("神裂火織"));
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_person_characters(
         &self,
-        person_id: u64,
+        person_id: impl Into<PersonId>,
     ) -> Result<Vec<PersonCharacter>, DepsError> {
-        let url = format!("{}/v0/persons/{}/characters", self.base_url, person_id);
+        let person_id: PersonId = person_id.into();
 
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let url = self.api_url(&["v0", "persons", &person_id.to_string(), "characters"])?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let req = self.client.get(url).build()?;
 
-        let characters: Vec<PersonCharacter> = res.json().await?;
-
-        Ok(characters)
+        self.execute_json("GET /v0/persons/{person_id}/characters", req)
+            .await
     }
 }
 
@@ -801,7 +1942,7 @@ impl Client {
 /// | API                               | Description  | Methods                                      |
 /// | :-------------------------------- | :----------- | :------------------------------------------- |
 /// | `GET /v0/users/{username}`        | 获取用户信息 | [`get_user`](Client::get_user)               |
-/// | `GET /v0/users/{username}/avatar` | 获取用户头像 | [`get_user_avatar`](Client::get_user_avatar) |
+/// | `GET /v0/users/{username}/avatar` | 获取用户头像 | [`get_user_avatar`](Client::get_user_avatar), [`download_user_avatar`](Client::download_user_avatar) |
 /// | `GET /v0/me`                      | 获取当前用户 | [`get_me`](Client::get_me)                   |
 impl Client {
     /// # 获取用户信息 `GET /v0/users/{username}`
@@ -816,7 +1957,8 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let user = client.get_user("sai").await?;
     ///
     /// assert_eq!(user.username, "sai");
@@ -825,19 +1967,11 @@ impl Client {
     /// # }
     /// ```
     pub async fn get_user(&self, username: &str) -> Result<User, DepsError> {
-        let url = format!("{}/v0/users/{}", self.base_url, username);
+        let url = self.api_url(&["v0", "users", username])?;
 
-        let req = self
-            .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .build()?;
+        let req = self.client.get(url).build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let user: User = res.json().await?;
-
-        Ok(user)
+        self.execute_json("GET /v0/users/{username}", req).await
     }
 
     /// # 获取用户头像 `GET /v0/users/{username}/avatar`
@@ -853,7 +1987,8 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let client = Client::new();
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let client = server.client()?;
     /// let image = client.get_user_avatar("sai", ImageType::Small).await?;
     /// # Ok(())
     /// # }
@@ -862,8 +1997,8 @@ impl Client {
         &self,
         username: &str,
         image_type: ImageType,
-    ) -> Result<Vec<u8>, DepsError> {
-        let url = format!("{}/v0/users/{}/avatar", self.base_url, username);
+    ) -> Result<bytes::Bytes, DepsError> {
+        let url = self.api_url(&["v0", "users", username, "avatar"])?;
 
         let req = self
             .client
@@ -871,11 +2006,8 @@ impl Client {
             .query(&[("type", image_type)])
             .build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
-
-        let image = res.bytes().await?;
-
-        Ok(image.to_vec())
+        self.execute_bytes("GET /v0/users/{username}/avatar", req)
+            .await
     }
 
     /// # 获取当前用户 `GET /v0/me`
@@ -892,8 +2024,11 @@ impl Client {
     /// # use bgmtv::prelude::*;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to test get_me");
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let token = "snapshot_token";
     /// let client = Client::builder()
+    /// #   .base_url(server.base_url())
+    /// #   .oauth_base_url(server.base_url())
     ///     .token(token)
     ///     .build()?;
     /// let user = client.get_me().await?;
@@ -901,32 +2036,448 @@ impl Client {
     /// # }
     /// ```
     pub async fn get_me(&self) -> Result<User, DepsError> {
-        let url = format!("{}/v0/me", self.base_url);
+        let url = self.api_url(&["v0", "me"])?;
+
+        let req = self.client.get(url).build()?;
+
+        self.execute_json("GET /v0/me", req).await
+    }
+}
+
+/// # Collections Resource (收藏资源)
+///
+/// | API                                                   | Description  | Methods                                                                                                                  |
+/// | :---------------------------------------------------- | :----------- | :----------------------------------------------------------------------------------------------------------------------- |
+/// | `GET   /v0/users/{username}/collections`              | 获取用户收藏 | [`get_user_collections`](Client::get_user_collections), [`collection_stats`](Client::collection_stats)                   |
+/// | `GET   /v0/users/-/collections/{subject_id}/episodes` | 获取章节收藏 | [`get_user_episode_collections`](Client::get_user_episode_collections)                                                   |
+/// | `POST  /v0/users/-/collections/{subject_id}`          | 新增条目收藏 | [`post_user_collection`](Client::post_user_collection)                                                                   |
+/// | `PATCH /v0/users/-/collections/{subject_id}`          | 修改条目收藏 | [`patch_user_collection`](Client::patch_user_collection), [`patch_subject_collection`](Client::patch_subject_collection) |
+impl Client {
+    /// # 获取用户收藏 `GET /v0/users/{username}/collections`
+    ///
+    /// 收藏按最后修改时间从新到旧排列，获取仅自己可见的收藏需要提供对应用户的 token。
+    ///
+    /// ## Arguments
+    ///
+    /// * `username` - 用户名
+    ///
+    /// ## Returns
+    ///
+    /// 返回一个 Builder 模式的 [`GetUserCollectionsExecutorBuilder`](collections::GetUserCollectionsExecutorBuilder), 用于构建请求参数并发送请求
+    ///
+    /// Builder 实现了 [`IntoFuture`](std::future::IntoFuture)，可以省略 `send()` 直接 `.await`
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let collections = client
+    ///     .get_user_collections("sai")
+    ///     .subject_type(SubjectType::Anime)
+    ///     .limit(10)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_user_collections(
+        &self,
+        username: impl Into<String>,
+    ) -> collections::GetUserCollectionsExecutorBuilder<'_> {
+        collections::GetUserCollectionsExecutor::builder(self, username)
+    }
+
+    /// # 获取章节收藏 `GET /v0/users/-/collections/{subject_id}/episodes`
+    ///
+    /// 获取当前用户在条目中所有章节的收藏状态，会依次获取全部分页。
+    ///
+    /// <div class="warning">
+    ///
+    /// 此方法需要提供 token。
+    ///
+    /// </div>
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to test get_user_episode_collections");
+    /// let client = Client::builder()
+    ///     .token(token)
+    ///     .build()?;
+    /// let episodes = client.get_user_episode_collections(3559).await?;
+    /// let done = episodes
+    ///     .iter()
+    ///     .filter(|e| e.r#type == EpisodeCollectionType::Done)
+    ///     .count();
+    /// println!("{} episodes watched", done);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_user_episode_collections(
+        &self,
+        subject_id: impl Into<SubjectId>,
+    ) -> Result<Vec<UserEpisodeCollection>, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        self.get_relation_all(
+            "GET /v0/users/-/collections/{subject_id}/episodes",
+            &[
+                "v0",
+                "users",
+                "-",
+                "collections",
+                &subject_id.to_string(),
+                "episodes",
+            ],
+        )
+        .await
+    }
+
+    /// # 修改条目收藏 `PATCH /v0/users/-/collections/{subject_id}`
+    ///
+    /// <div class="warning">
+    ///
+    /// 此方法需要提供具有 `write_collection` 权限的 token。
+    ///
+    /// </div>
+    ///
+    /// ## Arguments
+    ///
+    /// * `subject_id` - 条目 ID
+    /// * `payload` - 修改内容，为 `None` 的字段不会被修改；`private` 为 `None` 时不会改变收藏的可见性，
+    ///   也不会使用 [`ClientBuilder::default_private_collections`]
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to test patch_user_collection");
+    /// let client = Client::builder()
+    ///     .token(token)
+    ///     .build()?;
+    /// client
+    ///     .patch_user_collection(
+    ///         3559,
+    ///         &UserCollectionModifyPayload::default().ep_status(12),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn patch_user_collection(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        payload: &UserCollectionModifyPayload,
+    ) -> Result<(), WriteError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        self.check_scopes(Scopes::WRITE_COLLECTION)?;
+
+        let url = self.api_url(&["v0", "users", "-", "collections", &subject_id.to_string()])?;
+
+        let req = self.client.patch(url).json(payload).build()?;
+
+        self.execute_empty("PATCH /v0/users/-/collections/{subject_id}", req)
+            .await
+            .map_err(WriteError::from_deps_error)
+    }
+
+    /// # 新增条目收藏 `POST /v0/users/-/collections/{subject_id}`
+    ///
+    /// <div class="warning">
+    ///
+    /// 此方法需要提供具有 `write_collection` 权限的 token。
+    ///
+    /// </div>
+    ///
+    /// 收藏不存在时新增收藏，已存在时修改收藏。`payload` 的 `private` 为 `None` 时使用
+    /// [`ClientBuilder::default_private_collections`] 设置的默认值，因此修改已有收藏时请使用
+    /// [`patch_user_collection`](Client::patch_user_collection)，以免改变其可见性。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to test post_user_collection");
+    /// let client = Client::builder()
+    ///     .token(token)
+    ///     .default_private_collections(true)
+    ///     .build()?;
+    /// client
+    ///     .post_user_collection(
+    ///         3559,
+    ///         &UserCollectionModifyPayload::default().r#type(SubjectCollectionType::Wish),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_user_collection(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        payload: &UserCollectionModifyPayload,
+    ) -> Result<(), WriteError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        self.check_scopes(Scopes::WRITE_COLLECTION)?;
+
+        let url = self.api_url(&["v0", "users", "-", "collections", &subject_id.to_string()])?;
+
+        let req = match (payload.private, self.default_private_collections) {
+            (None, Some(private)) => {
+                let payload = payload.clone().private(private);
+                self.client.post(url).json(&payload).build()?
+            }
+            _ => self.client.post(url).json(payload).build()?,
+        };
+
+        self.execute_empty("POST /v0/users/-/collections/{subject_id}", req)
+            .await
+            .map_err(WriteError::from_deps_error)
+    }
+
+    /// # 修改条目收藏 `PATCH /v0/users/-/collections/{subject_id}`
+    ///
+    /// 与 [`patch_user_collection`](Client::patch_user_collection) 相同，但在发送请求前通过
+    /// [`check_unlocked`](Client::check_unlocked) 检查已获取的 `subject`，已被锁定时直接返回
+    /// [`WriteError::SubjectLocked`]。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to test patch_subject_collection");
+    /// let client = Client::builder()
+    ///     .token(token)
+    ///     .build()?;
+    /// let subject = client.get_subject(3559).await?;
+    /// client
+    ///     .patch_subject_collection(
+    ///         &subject,
+    ///         &UserCollectionModifyPayload::default().ep_status(12),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn patch_subject_collection(
+        &self,
+        subject: &Subject,
+        payload: &UserCollectionModifyPayload,
+    ) -> Result<(), WriteError> {
+        self.check_unlocked(subject)?;
+        self.patch_user_collection(subject.id, payload).await
+    }
+}
+
+/// # OAuth Resource (授权资源)
+///
+/// | API                        | Description    | Methods                                  |
+/// | :------------------------- | :------------- | :--------------------------------------- |
+/// | `POST /oauth/token_status` | 查询 token 信息 | [`token_status`](Client::token_status) |
+impl Client {
+    /// # 查询 token 信息 `POST /oauth/token_status`
+    ///
+    /// 此 API 位于 [`oauth_base_url`](ClientBuilder::oauth_base_url) 下，返回当前 token 的用户、过期时间与授予的权限。
+    /// token 通过表单请求体发送，不会出现在 URL 以及日志中；未设置 token 时返回 [`DepsError::MissingToken`]。
+    ///
+    /// 响应中包含权限时会记录下来，之后的 [`check_scopes`](Client::check_scopes) 以此为准，替换 token 后失效。
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let server = bgmtv::test_server::TestServer::start().await?;
+    /// # let token = "snapshot_token";
+    /// let client = Client::builder()
+    /// #   .base_url(server.base_url())
+    /// #   .oauth_base_url(server.base_url())
+    ///     .token(token)
+    ///     .build()?;
+    /// let status = client.token_status().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn token_status(&self) -> Result<AccessToken, DepsError> {
+        let url = self.oauth_url(&["oauth", "token_status"])?;
+        let token = self.token().await.ok_or(DepsError::MissingToken)?;
 
         let req = self
             .client
-            .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
+            .post(url)
+            .form(&[("access_token", token)])
             .build()?;
 
-        let res = self.client.execute(req).await?.error_for_status()?;
+        let status: AccessToken = self.execute_json("POST /oauth/token_status", req).await?;
+        if !status.scope.is_empty() {
+            self.set_reported_scopes(Some(status.scope));
+        }
+        Ok(status)
+    }
+}
 
-        let user: User = res.json().await?;
+/// 为 GET API 生成返回原始 JSON 的 `_raw` 方法
+macro_rules! raw_endpoints {
+    ($($name:ident($($arg:ident: $ty:ty),*) => $typed:ident, $endpoint:literal, [$($segment:expr),*];)*) => {
+        /// # Raw JSON (原始 JSON)
+        ///
+        /// 每个返回 JSON 的 GET API 都有对应的 `_raw` 方法，返回未经类型转换的 [`serde_json::Value`]，
+        /// 用于访问类型中尚未包含的字段，同时复用 token、URL 与重试等逻辑。
+        /// 使用 Builder 的 API 可以通过执行器的 `send_raw` 方法获取原始 JSON。
+        ///
+        /// ## Example
+        ///
+        /// ```no_run
+        /// # use bgmtv::prelude::*;
+        /// # #[tokio::main]
+        /// # async fn main() -> anyhow::Result<()> {
+        /// # let client = Client::new();
+        /// let subject = client.get_subject_raw(3559).await?;
+        /// println!("{}", subject["meta_tags"]);
+        /// # Ok(())
+        /// # }
+        /// ```
+        impl Client {
+            $(
+                #[doc = concat!("# `", $endpoint, "` (原始 JSON)\n\n参见 [`", stringify!($typed), "`](Client::", stringify!($typed), ")")]
+                pub async fn $name(&self, $($arg: $ty),*) -> Result<serde_json::Value, DepsError> {
+                    let url = self.api_url(&[$($segment),*])?;
+
+                    let req = self.client.get(url).build()?;
+
+                    self.execute_json($endpoint, req).await
+                }
+            )*
+        }
+    };
+}
 
-        Ok(user)
-    }
+raw_endpoints! {
+    get_subject_raw(subject_id: impl Into<SubjectId>) => get_subject, "GET /v0/subjects/{subject_id}", ["v0", "subjects", &Into::<SubjectId>::into(subject_id).to_string()];
+    get_subject_persons_raw(subject_id: impl Into<SubjectId>) => get_subject_persons, "GET /v0/subjects/{subject_id}/persons", ["v0", "subjects", &Into::<SubjectId>::into(subject_id).to_string(), "persons"];
+    get_subject_characters_raw(subject_id: impl Into<SubjectId>) => get_subject_characters, "GET /v0/subjects/{subject_id}/characters", ["v0", "subjects", &Into::<SubjectId>::into(subject_id).to_string(), "characters"];
+    get_subject_subjects_raw(subject_id: impl Into<SubjectId>) => get_subject_subjects, "GET /v0/subjects/{subject_id}/subjects", ["v0", "subjects", &Into::<SubjectId>::into(subject_id).to_string(), "subjects"];
+    get_episode_raw(episode_id: impl Into<EpisodeId>) => get_episode, "GET /v0/episodes/{episode_id}", ["v0", "episodes", &Into::<EpisodeId>::into(episode_id).to_string()];
+    get_character_raw(character_id: impl Into<CharacterId>) => get_character, "GET /v0/characters/{character_id}", ["v0", "characters", &Into::<CharacterId>::into(character_id).to_string()];
+    get_character_subjects_raw(character_id: impl Into<CharacterId>) => get_character_subjects, "GET /v0/characters/{character_id}/subjects", ["v0", "characters", &Into::<CharacterId>::into(character_id).to_string(), "subjects"];
+    get_character_persons_raw(character_id: impl Into<CharacterId>) => get_character_persons, "GET /v0/characters/{character_id}/persons", ["v0", "characters", &Into::<CharacterId>::into(character_id).to_string(), "persons"];
+    get_person_raw(person_id: impl Into<PersonId>) => get_person, "GET /v0/persons/{person_id}", ["v0", "persons", &Into::<PersonId>::into(person_id).to_string()];
+    get_person_subjects_raw(person_id: impl Into<PersonId>) => get_person_subjects, "GET /v0/persons/{person_id}/subjects", ["v0", "persons", &Into::<PersonId>::into(person_id).to_string(), "subjects"];
+    get_person_characters_raw(person_id: impl Into<PersonId>) => get_person_characters, "GET /v0/persons/{person_id}/characters", ["v0", "persons", &Into::<PersonId>::into(person_id).to_string(), "characters"];
+    get_user_raw(username: &str) => get_user, "GET /v0/users/{username}", ["v0", "users", username];
+    get_me_raw() => get_me, "GET /v0/me", ["v0", "me"];
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
+    /// 公开的类型、执行器、流与 future 都可以在多线程运行时中跨越 `.await` 持有或在任务间共享
     #[test]
-    fn test_client_build() {
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        fn assert_send<T: Send>(_: T) {}
+        use std::future::IntoFuture;
+
+        assert_send_sync::<Client>();
+        assert_send_sync::<ClientBuilder>();
+        assert_send_sync::<ClientBuilderError>();
+        assert_send_sync::<DepsError>();
+        assert_send_sync::<GetSubjectsError>();
+        assert_send_sync::<GetEpisodesError>();
+        assert_send_sync::<GetUserCollectionsError>();
+        assert_send_sync::<SearchSubjectsError>();
+        assert_send_sync::<WriteError>();
+        assert_send_sync::<retry::RetryHistory>();
+        assert_send_sync::<stats::RequestStats>();
+        assert_send_sync::<scheduler::Scheduler<'static>>();
+        assert_send_sync::<images::MemoryImageCache>();
+        assert_send_sync::<images::PrefetchReport>();
+        #[cfg(feature = "fs")]
+        assert_send_sync::<sink::FsMediaSink>();
+        #[cfg(feature = "s3")]
+        assert_send_sync::<sink::s3::S3MediaSink>();
+        assert_send_sync::<batch::BatchResults<Subject>>();
+        assert_send_sync::<offline::MemorySnapshotStore>();
+        assert_send_sync::<offline::FileSnapshotStore>();
+        assert_send_sync::<executors::GetEpisodesExecutor<'static>>();
+        assert_send_sync::<executors::GetEpisodesExecutorBuilder<'static>>();
+        assert_send_sync::<executors::GetSubjectsExecutor<'static>>();
+        assert_send_sync::<executors::GetSubjectsExecutorBuilder<'static>>();
+        assert_send_sync::<executors::SearchSubjectsExecutor<'static>>();
+        assert_send_sync::<executors::SearchSubjectsExecutorBuilder<'static>>();
+        assert_send_sync::<executors::GetUserCollectionsExecutor<'static>>();
+        assert_send_sync::<executors::GetUserCollectionsExecutorBuilder<'static>>();
+
+        // future 与流是惰性的，构造后直接丢弃不会发出请求
+        let client = Client::new();
+        let episodes = client.get_episodes(3559).build().unwrap();
+        let collections = client.get_user_collections("sai").build().unwrap();
+        let cache = images::MemoryImageCache::default();
+        let no_images: [&Images; 0] = [];
+        assert_send(client.get_subject(3559));
+        assert_send(client.get_subject(SubjectId(3559)));
+        assert_send(client.get_episode(EpisodeId(8)));
+        assert_send(client.get_subject_full(3559, Default::default()));
+        assert_send(client.get_subjects_by_ids([3559], 4));
+        assert_send(client.episodes_of_subjects([3559], Default::default()));
+        assert_send(client.get_episodes(3559).into_future());
+        assert_send(
+            client
+                .get_subjects()
+                .r#type(SubjectType::Anime)
+                .into_future(),
+        );
+        assert_send(episodes.send());
+        assert_send(episodes.fetch_all());
+        assert_send(episodes.items());
+        assert_send(collections.pages());
+        assert_send(client.collection_stats("sai"));
+        assert_send(client.with_retry_history(client.get_me()));
+        assert_send(client.scheduler().background(client.get_me()));
+        assert_send(client.prefetch_images(
+            no_images,
+            ImageType::Small,
+            &cache,
+            images::PrefetchOptions::default(),
+        ));
+        #[cfg(feature = "fs")]
+        assert_send(client.prefetch_images_to(
+            no_images,
+            ImageType::Small,
+            &sink::FsMediaSink::new("images"),
+            images::PrefetchOptions::default(),
+        ));
+        assert_send(client.download_subject_image(3559, ImageType::Small, &mut Vec::new(), None));
+        #[cfg(feature = "fs")]
+        assert_send(client.save_subject_image(3559, ImageType::Small, "3559.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_client_build() {
         let client = Client::new();
         assert_eq!(client.base_url(), "https://api.bgm.tv");
         assert_eq!(client.user_agent(), DEFAULT_USER_AGENT);
-        assert!(client.token().is_none());
+        assert!(client.token().await.is_none());
 
         let client = Client::builder()
             .user_agent("test_user_agent")
@@ -935,6 +2486,883 @@ mod tests {
             .unwrap();
         assert_eq!(client.base_url(), "https://api.bgm.tv");
         assert_eq!(client.user_agent(), "test_user_agent");
-        assert_eq!(client.token(), Some("test_token"));
+        assert_eq!(client.token().await.as_deref(), Some("test_token"));
+    }
+
+    #[test]
+    fn test_base_url() {
+        let client = Client::builder()
+            .base_url("https://example.com/bgm")
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "https://example.com/bgm");
+        assert_eq!(
+            client.api_url(&["v0", "subjects", "1"]).unwrap().as_str(),
+            "https://example.com/bgm/v0/subjects/1"
+        );
+        assert_eq!(
+            client.api_url(&["v0", "users", "a b/ä"]).unwrap().as_str(),
+            "https://example.com/bgm/v0/users/a%20b%2F%C3%A4"
+        );
+
+        for invalid in ["api.bgm.tv", "ftp://api.bgm.tv", "mailto:a@b.c"] {
+            let err = Client::builder().base_url(invalid).build().unwrap_err();
+            assert!(err.to_string().contains(invalid));
+        }
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let body = br#"{"total":1,"limit":10,"offset":0,"data":[{"id":1,"name":"a"}]}"#;
+
+        let paged: Paged<serde_json::Value> = decode_json(body).unwrap();
+        assert_eq!(paged.data[0]["name"], "a");
+
+        // simd-json 不支持 `RawValue`，需要回退到 serde_json
+        let raw: PagedRaw = decode_json(body).unwrap();
+        assert_eq!(raw.data[0].get(), r#"{"id":1,"name":"a"}"#);
+
+        let err = decode_json::<Paged<serde_json::Value>>(b"{").unwrap_err();
+        assert!(matches!(err, DepsError::Serialize(_)));
+    }
+
+    #[cfg(any(feature = "tracing", feature = "recorder"))]
+    #[test]
+    fn test_redact_url() {
+        let url = url::Url::parse("https://bgm.tv/oauth?access_token=secret&a=1&client_secret=s2")
+            .unwrap();
+        assert_eq!(
+            redact_url(&url).as_str(),
+            "https://bgm.tv/oauth?access_token=%5Bredacted%5D&a=1&client_secret=%5Bredacted%5D"
+        );
+
+        let url = url::Url::parse("https://api.bgm.tv/v0/subjects?type=2").unwrap();
+        assert_eq!(redact_url(&url), url);
+    }
+
+    #[tokio::test]
+    async fn test_token_provider() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        struct RotatingToken(AtomicU64);
+
+        impl TokenProvider for RotatingToken {
+            fn token(&self) -> token::TokenFuture<'_> {
+                let n = self.0.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async move { Some(format!("token_{}", n)) })
+            }
+        }
+
+        let client = Client::builder()
+            .token_provider(RotatingToken(AtomicU64::new(0)))
+            .build()
+            .unwrap();
+        assert_eq!(client.token().await.as_deref(), Some("token_0"));
+        assert_eq!(client.token().await.as_deref(), Some("token_1"));
+    }
+
+    #[tokio::test]
+    async fn test_token_status_form() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let status = r#"{"access_token":"secret","client_id":"app","user_id":1,"expires":0}"#;
+        let server = MockServer::start(vec![MockResponse::new(200, status)]).await;
+
+        let client = Client::builder()
+            .oauth_base_url(server.base_url.clone())
+            .token("secret")
+            .build()
+            .unwrap();
+        let status = client.token_status().await.unwrap();
+        assert_eq!(status.user_id, 1);
+        assert!(!format!("{:?}", status).contains("secret"));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line, "POST /oauth/token_status HTTP/1.1");
+        assert_eq!(requests[0].body, b"access_token=secret");
+
+        client.clear_token();
+        assert!(matches!(
+            client.token_status().await,
+            Err(DepsError::MissingToken)
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_token_status_scopes() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let status =
+            r#"{"access_token":"secret","client_id":"app","user_id":1,"expires":0,"scope":"read"}"#;
+        let server = MockServer::start(vec![MockResponse::new(200, status)]).await;
+
+        let client = Client::builder()
+            .oauth_base_url(server.base_url.clone())
+            .token("secret")
+            .scopes(Scopes::all())
+            .build()
+            .unwrap();
+        assert!(client.check_scopes(Scopes::WRITE_COLLECTION).is_ok());
+
+        client.token_status().await.unwrap();
+        assert_eq!(client.scopes(), Some(Scopes::READ));
+        assert!(matches!(
+            client.check_scopes(Scopes::WRITE_COLLECTION),
+            Err(WriteError::InsufficientScope { .. })
+        ));
+
+        client.set_token("other");
+        assert_eq!(client.scopes(), Some(Scopes::all()));
+    }
+
+    #[tokio::test]
+    async fn test_set_token() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","user_group":1,"avatar":{"large":"","medium":"","small":""},"sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user),
+            MockResponse::new(200, user),
+            MockResponse::new(200, user),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("old_token")
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        client.set_token("new_token");
+        client.get_user("sai").await.unwrap();
+        client.clear_token();
+        client.get_user("sai").await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer old_token")
+        );
+        assert_eq!(
+            requests[1].header("authorization"),
+            Some("Bearer new_token")
+        );
+        assert_eq!(requests[2].header("authorization"), None);
+        assert!(!client.snapshot_config().has_token);
+    }
+
+    #[tokio::test]
+    async fn test_execute_json_pipeline() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(200, r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#),
+            MockResponse::new(
+                404,
+                r#"{"title":"Not Found","description":"resource can't be found in the database or has been removed","details":{}}"#,
+            ),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("test_token")
+            .build()
+            .unwrap();
+
+        let user = client.get_user("sai").await.unwrap();
+        assert_eq!(user.id, 1);
+
+        let err = client.get_episodes(0).send().await.unwrap_err();
+        assert!(matches!(
+            err,
+            GetEpisodesError::Api(BgmApiError { status: 404, .. })
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line, "GET /v0/users/sai HTTP/1.1");
+        assert_eq!(requests[0].header("accept"), Some("application/json"));
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer test_token")
+        );
+        assert_eq!(requests[1].header("accept"), Some("application/json"));
+
+        assert_eq!(client.stats().total_requests, 2);
+    }
+
+    #[tokio::test]
+    async fn test_response_size_limits() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user),
+            MockResponse::new(200, vec![0u8; 64]),
+            MockResponse::new(200, vec![0u8; 16]),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .max_json_size(16)
+            .max_image_size(32)
+            .build()
+            .unwrap();
+
+        let err = client.get_user("sai").await.unwrap_err();
+        assert!(matches!(err, DepsError::ResponseTooLarge { limit: 16 }));
+
+        let err = client
+            .get_user_avatar("sai", ImageType::Large)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DepsError::ResponseTooLarge { limit: 32 }));
+
+        let avatar = client
+            .get_user_avatar("sai", ImageType::Large)
+            .await
+            .unwrap();
+        assert_eq!(avatar.len(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_accept_encoding() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user),
+            MockResponse::new(200, vec![0u8; 16]),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        client
+            .get_user_avatar("sai", ImageType::Large)
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        #[cfg(feature = "gzip")]
+        assert!(requests[0]
+            .header("accept-encoding")
+            .is_some_and(|encoding| encoding.contains("gzip")));
+        assert_eq!(requests[1].header("accept-encoding"), Some("identity"));
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        use crate::{
+            mock::{MockResponse, MockServer},
+            time::ManualClock,
+        };
+
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}"),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+            MockResponse::new(429, "{}"),
+            MockResponse::new(429, "{}"),
+        ])
+        .await;
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock)
+            .retry(RetryPolicy::default().max_retries(1))
+            .build()
+            .unwrap();
+
+        let err = client.get_user("sai").await.unwrap_err();
+        let not_found = err.request_id().unwrap().to_string();
+        assert!(matches!(err, DepsError::Api(_)));
+        let err = client.get_user("sai").await.unwrap_err();
+        let rate_limited = err.request_id().unwrap().to_string();
+        assert!(matches!(err, DepsError::RateLimited { .. }));
+        assert_ne!(not_found, rate_limited);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("x-request-id"), Some(not_found.as_str()));
+        assert_eq!(requests[1].header("x-request-id"), Some(not_found.as_str()));
+        assert_eq!(
+            requests[2].header("x-request-id"),
+            Some(rate_limited.as_str())
+        );
+        assert_eq!(
+            requests[3].header("x-request-id"),
+            Some(rate_limited.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relation_pagination() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let person = |id: u64| {
+            format!(r#"{{"id":{id},"name":"","type":1,"career":[],"relation":"","eps":""}}"#)
+        };
+        let list = format!("[{},{},{}]", person(1), person(2), person(3));
+        let server = MockServer::start(vec![
+            MockResponse::new(200, list.clone()),
+            MockResponse::new(200, list),
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"total":3,"limit":2,"offset":0,"data":[{},{}]}}"#,
+                    person(1),
+                    person(2)
+                ),
+            ),
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"total":3,"limit":2,"offset":2,"data":[{}]}}"#,
+                    person(3)
+                ),
+            ),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+        let ids = |persons: &[RelatedPerson]| persons.iter().map(|p| p.id).collect::<Vec<_>>();
+
+        let persons = client.get_subject_persons(1).await.unwrap();
+        assert_eq!(ids(&persons), vec![1, 2, 3]);
+
+        let page = client
+            .get_subject_persons_page(1, PageOptions::default().limit(1).offset(1))
+            .await
+            .unwrap();
+        assert_eq!((page.total, page.offset), (3, 1));
+        assert_eq!(ids(&page.data), vec![2]);
+
+        let persons = client.get_subject_persons(1).await.unwrap();
+        assert_eq!(ids(&persons), vec![1, 2, 3]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[1].line.contains("limit=1&offset=1"));
+        assert!(requests[3].line.contains("offset=2"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_endpoints() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(200, r#"{"id":1,"meta_tags":["TV"]}"#),
+            MockResponse::new(
+                200,
+                r#"{"total":0,"limit":100,"offset":0,"data":[],"extra":1}"#,
+            ),
+            MockResponse::new(
+                200,
+                r#"{"total":1,"limit":100,"offset":0,"data":[{"id":7,"name":"x","unmodeled":{"a":1}}]}"#,
+            ),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("test_token")
+            .build()
+            .unwrap();
+
+        let subject = client.get_subject_raw(1).await.unwrap();
+        assert_eq!(subject["meta_tags"][0], "TV");
+
+        let episodes = client
+            .get_episodes(1)
+            .build()
+            .unwrap()
+            .send_raw()
+            .await
+            .unwrap();
+        assert_eq!(episodes["extra"], 1);
+
+        let subjects = client
+            .get_subjects()
+            .r#type(SubjectType::Anime)
+            .build()
+            .unwrap()
+            .send_lazy()
+            .await
+            .unwrap();
+        assert_eq!(
+            subjects.data[0].get(),
+            r#"{"id":7,"name":"x","unmodeled":{"a":1}}"#
+        );
+
+        #[derive(serde::Deserialize)]
+        struct Slim {
+            id: u64,
+        }
+        let slim = subjects.decode::<Slim>().unwrap();
+        assert_eq!((slim.total, slim.data[0].id), (1, 7));
+
+        let requests = server.requests();
+        assert!(requests[0].line.starts_with("GET /v0/subjects/1 "));
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer test_token")
+        );
+        assert!(requests[1].line.contains("subject_id=1"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_retry() {
+        use crate::{
+            mock::{MockResponse, MockServer},
+            time::ManualClock,
+        };
+
+        let body = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").header("retry-after", "2"),
+            MockResponse::new(429, "{}"),
+            MockResponse::new(200, body),
+            MockResponse::new(429, "{}").header("retry-after", "2"),
+        ])
+        .await;
+
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock.clone())
+            .retry(RetryPolicy::default())
+            .build()
+            .unwrap();
+
+        let user = client.get_user("sai").await.unwrap();
+        assert_eq!(user.username, "sai");
+        assert_eq!(
+            clock.slept(),
+            vec![Duration::from_secs(2), Duration::from_secs(1)]
+        );
+        assert_eq!(client.stats().total_requests, 3);
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.get_user("sai").await,
+            Err(DepsError::RateLimited { retry_after, history, .. })
+                if retry_after == Some(Duration::from_secs(2)) && history.len() == 1
+        ));
+
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").header("retry-after", "2"),
+            MockResponse::new(200, body),
+            MockResponse::new(429, "{}").header("retry-after", "3"),
+            MockResponse::new(429, "{}"),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock.clone())
+            .retry(RetryPolicy::default().max_retries(1))
+            .build()
+            .unwrap();
+
+        let (user, histories) = client
+            .with_retry_history(async {
+                let user = client.get_user("sai").await;
+                (user, client.get_user("sai").await)
+            })
+            .await;
+        assert!(user.0.is_ok());
+        let Err(DepsError::RateLimited { history, .. }) = user.1 else {
+            panic!("expected rate limited error");
+        };
+        assert_eq!(
+            history
+                .attempts()
+                .iter()
+                .map(|a| a.status)
+                .collect::<Vec<_>>(),
+            vec![Some(429), Some(429)]
+        );
+        assert_eq!(history.retries(), 1);
+        assert_eq!(histories.len(), 2);
+        assert_eq!(histories[0].retries(), 1);
+        assert_eq!(histories[0].total_delay(), Duration::from_secs(2));
+        assert_eq!(histories[0].attempts()[1].status, Some(200));
+        assert_eq!(histories[1], history);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_http_date() {
+        use crate::{
+            mock::{MockResponse, MockServer},
+            time::ManualClock,
+        };
+
+        let body = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").header("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            MockResponse::new(200, body),
+        ])
+        .await;
+
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2015 07:27:30 GMT").unwrap();
+        let clock = ManualClock::at(now);
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock.clone())
+            .retry(RetryPolicy::default().max_delay(Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        assert_eq!(clock.slept(), vec![Duration::from_secs(30)]);
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry() {
+        use crate::{
+            mock::{MockResponse, MockServer},
+            time::ManualClock,
+        };
+        use retry::WriteRetry;
+
+        // 绑定后立即关闭的端口，连接总是被拒绝
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_url = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+
+        let clock = ManualClock::new();
+        let client = |base_url: &str, retry: RetryPolicy| {
+            Client::builder()
+                .base_url(base_url.to_string())
+                .clock(clock.clone())
+                .retry(retry)
+                .build()
+                .unwrap()
+        };
+        let payload = UserCollectionModifyPayload::default().ep_status(1);
+
+        let refused = client(&closed_url, RetryPolicy::default().max_retries(2));
+        let (res, histories) = refused
+            .with_retry_history(async {
+                let _ = refused.get_user("sai").await;
+                refused.patch_user_collection(1, &payload).await
+            })
+            .await;
+        let Err(WriteError::Network { error, history, .. }) = res else {
+            panic!("expected a network error after retries");
+        };
+        assert!(matches!(*error, DepsError::Reqwest(ref err) if err.is_connect()));
+        assert_eq!(history.len(), 3);
+        assert_eq!(histories[0].len(), 3);
+        assert_eq!(histories[1].len(), 3);
+
+        let refused = client(
+            &closed_url,
+            RetryPolicy::default().writes(WriteRetry::Never),
+        );
+        let (_, histories) = refused
+            .with_retry_history(refused.patch_user_collection(1, &payload))
+            .await;
+        assert_eq!(histories[0].len(), 1);
+
+        let server = MockServer::start(vec![MockResponse::new(429, "{}")]).await;
+        let limited = client(&server.base_url, RetryPolicy::default());
+        assert!(matches!(
+            limited.patch_user_collection(1, &payload).await,
+            Err(WriteError::RateLimited { history, .. }) if history.len() == 1
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_patch_user_collection() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(204, ""),
+            MockResponse::new(
+                400,
+                r#"{"title":"Bad Request","description":"subject is locked","details":{}}"#,
+            ),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("test_token")
+            .build()
+            .unwrap();
+
+        let payload = UserCollectionModifyPayload {
+            ep_status: Some(3),
+            ..Default::default()
+        };
+        client.patch_user_collection(1, &payload).await.unwrap();
+        assert!(matches!(
+            client.patch_user_collection(2, &payload).await,
+            Err(WriteError::SubjectLocked { .. })
+        ));
+
+        let mut subject = crate::mock::subject();
+        subject.id = 3;
+        subject.locked = true;
+        assert!(matches!(
+            client.patch_subject_collection(&subject, &payload).await,
+            Err(WriteError::SubjectLocked { .. })
+        ));
+        subject.locked = false;
+        assert!(client.check_unlocked(&subject).is_ok());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].line, "PATCH /v0/users/-/collections/1 HTTP/1.1");
+        assert_eq!(requests[0].body, br#"{"ep_status":3}"#);
+    }
+
+    #[tokio::test]
+    async fn test_default_private_collections() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(204, ""),
+            MockResponse::new(204, ""),
+            MockResponse::new(204, ""),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("test_token")
+            .default_private_collections(true)
+            .build()
+            .unwrap();
+
+        let payload = UserCollectionModifyPayload::default().ep_status(3);
+        client.post_user_collection(1, &payload).await.unwrap();
+        client
+            .post_user_collection(1, &payload.clone().private(false))
+            .await
+            .unwrap();
+        client.patch_user_collection(1, &payload).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line, "POST /v0/users/-/collections/1 HTTP/1.1");
+        assert_eq!(requests[0].body, br#"{"ep_status":3,"private":true}"#);
+        assert_eq!(requests[1].body, br#"{"ep_status":3,"private":false}"#);
+        assert_eq!(requests[2].line, "PATCH /v0/users/-/collections/1 HTTP/1.1");
+        assert_eq!(requests[2].body, br#"{"ep_status":3}"#);
+    }
+
+    #[tokio::test]
+    async fn test_transport() {
+        use std::sync::{Arc, Mutex};
+
+        use transport::TransportFuture;
+
+        #[derive(Clone, Default)]
+        struct Canned(Arc<Mutex<Vec<String>>>);
+
+        impl HttpTransport for Canned {
+            fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+                self.0.lock().unwrap().push(format!(
+                    "{} {} {:?}",
+                    req.method(),
+                    req.url(),
+                    req.headers().get(reqwest::header::AUTHORIZATION)
+                ));
+                Box::pin(async {
+                    let res = http::Response::builder()
+                        .status(200)
+                        .body(r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#)
+                        .unwrap();
+                    Ok(reqwest::Response::from(res))
+                })
+            }
+        }
+
+        let transport = Canned::default();
+        let client = Client::builder()
+            .token("test_token")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let user = client.get_user("sai").await.unwrap();
+        assert_eq!(user.username, "sai");
+        assert_eq!(
+            *transport.0.lock().unwrap(),
+            vec![r#"GET https://api.bgm.tv/v0/users/sai Some("Bearer test_token")"#]
+        );
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    async fn test_client_middleware() {
+        use futures_util::future::BoxFuture;
+        use reqwest_middleware::{Next, Result};
+
+        use crate::mock::{MockResponse, MockServer};
+
+        fn tag<'a>(
+            mut req: reqwest::Request,
+            extensions: &'a mut http::Extensions,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<reqwest::Response>> {
+            req.headers_mut().insert(
+                "x-middleware",
+                reqwest::header::HeaderValue::from_static("1"),
+            );
+            Box::pin(next.run(req, extensions))
+        }
+
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#,
+        )])
+        .await;
+
+        let middleware = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(tag)
+            .build();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .client_middleware(middleware)
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        assert_eq!(server.requests()[0].header("x-middleware"), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        let client = Client::builder()
+            .base_url(base_url)
+            .timeout(Duration::from_millis(100))
+            .connect_timeout(Duration::from_secs(1))
+            .build()
+            .unwrap();
+
+        let err = client.get_subject(1).await.unwrap_err();
+        assert!(matches!(err, DepsError::Reqwest(err) if err.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn test_proxy() {
+        use crate::mock::{MockResponse, MockServer};
+
+        let proxy = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#,
+        )])
+        .await;
+
+        let client = Client::builder()
+            .base_url("http://api.bgm.invalid".to_string())
+            .no_proxy()
+            .proxy(proxy.base_url.clone())
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        assert!(proxy.requests()[0]
+            .line
+            .starts_with("GET http://api.bgm.invalid/v0/users/sai"));
+
+        assert!(Client::builder().proxy("not a url").build().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_default_headers() {
+        use reqwest::header::{HeaderValue, ACCEPT, ACCEPT_LANGUAGE, AUTHORIZATION};
+
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#,
+        )])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("token")
+            .default_header(ACCEPT_LANGUAGE, HeaderValue::from_static("zh-CN"))
+            .default_header(ACCEPT, HeaderValue::from_static("text/plain"))
+            .default_header(AUTHORIZATION, HeaderValue::from_static("Basic xxx"))
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        let req = &server.requests()[0];
+        assert_eq!(req.header("accept-language"), Some("zh-CN"));
+        assert_eq!(req.header("accept"), Some("application/json"));
+        assert_eq!(req.header("authorization"), Some("Bearer token"));
+    }
+
+    #[tokio::test]
+    async fn test_hooks() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#,
+        )])
+        .await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .on_request(|_, req| {
+                req.headers_mut()
+                    .insert("x-hook", reqwest::header::HeaderValue::from_static("1"));
+            })
+            .on_response(move |endpoint, res, _| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((endpoint, res.status().as_u16()));
+            })
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        assert_eq!(server.requests()[0].header("x-hook"), Some("1"));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("GET /v0/users/{username}", 200)]
+        );
+    }
+
+    #[test]
+    fn test_check_scopes() {
+        let client = Client::new();
+        assert!(client.check_scopes(Scopes::WRITE_COLLECTION).is_ok());
+
+        let client = Client::builder().scopes(Scopes::READ).build().unwrap();
+        assert!(client.check_scopes(Scopes::READ).is_ok());
+        assert!(matches!(
+            client.check_scopes(Scopes::READ | Scopes::WRITE_COLLECTION),
+            Err(WriteError::InsufficientScope { description }) if description == "missing scopes: write_collection"
+        ));
     }
 }
@@ -0,0 +1,373 @@
+//! # Batch Fetching (批量获取)
+//!
+//! [`Client::get_subjects_by_ids`] 等方法按 ID 批量获取条目、章节、角色与人物，同时进行的请求数量受 `concurrency` 限制。
+//! 结果按传入 ID 的顺序返回，单个 ID 获取失败不会中断其余的请求，失败的 ID 与错误可以通过 [`BatchResults::failed`] 获取。
+//!
+//! [`Client::episodes_of_subjects`] 则以流的形式返回多个条目的全部章节，适合放送提醒一类需要遍历大量条目的程序。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let results = client.get_subjects_by_ids([3559, 975, 0], 4).await;
+//! for (id, err) in results.failed() {
+//!     eprintln!("failed to fetch {}: {}", id, err);
+//! }
+//! let subjects: Vec<_> = results.into_ok().collect();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use futures_core::Stream;
+use futures_util::{stream, StreamExt};
+
+use super::{
+    pagination::{self, Paginated},
+    scheduler::{self, RateLimit, RateLimiter},
+    Client,
+};
+use crate::{error::*, types::*};
+
+/// Batch results (批量获取的结果)
+///
+/// 按传入 ID 的顺序保存每个 ID 的结果。
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BatchResults<T> {
+    /// 每个 ID 与其结果
+    pub results: Vec<(u64, Result<T, DepsError>)>,
+}
+
+impl<T> BatchResults<T> {
+    /// 是否全部获取成功
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// 获取成功的数据，按 ID 的顺序排列
+    pub fn ok(&self) -> impl Iterator<Item = &T> {
+        self.results
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok())
+    }
+
+    /// 获取失败的 ID 与错误
+    pub fn failed(&self) -> impl Iterator<Item = (u64, &DepsError)> {
+        self.results
+            .iter()
+            .filter_map(|(id, result)| result.as_ref().err().map(|err| (*id, err)))
+    }
+
+    /// 丢弃错误，只保留获取成功的数据
+    pub fn into_ok(self) -> impl Iterator<Item = T> {
+        self.results
+            .into_iter()
+            .filter_map(|(_, result)| result.ok())
+    }
+
+    /// 全部获取成功时返回所有数据，否则返回第一个错误
+    pub fn into_result(self) -> Result<Vec<T>, DepsError> {
+        self.results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+/// 以最多 `concurrency` 个同时进行的请求对每个 ID 调用 `fetch`，结果保持 ID 的顺序
+async fn batch<T, F, Fut>(
+    ids: impl IntoIterator<Item = u64>,
+    concurrency: usize,
+    fetch: F,
+) -> BatchResults<T>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<T, DepsError>>,
+{
+    let results = futures_util::stream::iter(ids)
+        .map(|id| {
+            let fut = fetch(id);
+            async move { (id, fut.await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    BatchResults { results }
+}
+
+/// # Batch fetching methods for [`Client`].
+impl Client {
+    /// 按 ID 批量获取条目，参见 [`get_subject`](Client::get_subject)
+    pub async fn get_subjects_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> BatchResults<Subject> {
+        batch(ids, concurrency, |id| self.get_subject(id)).await
+    }
+
+    /// 按 ID 批量获取章节，参见 [`get_episode`](Client::get_episode)
+    pub async fn get_episodes_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> BatchResults<Episode> {
+        batch(ids, concurrency, |id| self.get_episode(id)).await
+    }
+
+    /// 按 ID 批量获取角色，参见 [`get_character`](Client::get_character)
+    pub async fn get_characters_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> BatchResults<CharacterDetail> {
+        batch(ids, concurrency, |id| self.get_character(id)).await
+    }
+
+    /// 按 ID 批量获取人物，参见 [`get_person`](Client::get_person)
+    pub async fn get_persons_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> BatchResults<PersonDetail> {
+        batch(ids, concurrency, |id| self.get_person(id)).await
+    }
+}
+
+/// [`Client::episodes_of_subjects`] 的选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EpisodeStreamOptions {
+    /// 同时获取章节的条目数量，默认为 4
+    pub concurrency: usize,
+
+    /// 章节类型，默认获取全部类型
+    pub episode_type: Option<EpisodeType>,
+
+    /// 此次获取的限流，默认每秒 4 个请求；与 [`ClientBuilder::rate_limit`](super::ClientBuilder::rate_limit) 同时生效
+    pub rate_limit: Option<RateLimit>,
+
+    /// 每一页章节的超时时间，默认不限制；超时的条目返回 [`DepsError::Timeout`](crate::error::DepsError::Timeout)
+    pub timeout_per_page: Option<Duration>,
+}
+
+impl Default for EpisodeStreamOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            episode_type: None,
+            rate_limit: Some(RateLimit::new(4, Duration::from_secs(1))),
+            timeout_per_page: None,
+        }
+    }
+}
+
+impl EpisodeStreamOptions {
+    /// 设置同时获取章节的条目数量
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// 只获取某一类型的章节
+    pub fn episode_type(mut self, episode_type: EpisodeType) -> Self {
+        self.episode_type = Some(episode_type);
+        self
+    }
+
+    /// 设置此次获取的限流，`None` 表示只使用 [`Client`] 的限流
+    pub fn rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// 设置每一页章节的超时时间
+    pub fn timeout_per_page(mut self, timeout: Duration) -> Self {
+        self.timeout_per_page = Some(timeout);
+        self
+    }
+}
+
+/// # Episode streaming methods for [`Client`].
+impl Client {
+    /// 获取多个条目的全部章节
+    ///
+    /// 以最多 [`EpisodeStreamOptions::concurrency`] 个条目同时获取，每个条目的章节获取完毕后按顺序返回
+    /// `(subject_id, episode)`，不同条目之间的顺序不确定。某个条目获取失败时返回 `(subject_id, Err(_))`，
+    /// 此前已获取的章节仍会返回，其余条目不受影响。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// use bgmtv::client::batch::EpisodeStreamOptions;
+    /// use futures_util::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::builder().build()?;
+    ///
+    /// let mut episodes = client.episodes_of_subjects(
+    ///     [3559, 975],
+    ///     EpisodeStreamOptions::default().episode_type(EpisodeType::MainStory),
+    /// );
+    /// while let Some((subject_id, episode)) = episodes.next().await {
+    ///     let episode = episode?;
+    ///     println!("{}: {} {}", subject_id, episode.sort, episode.airdate);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn episodes_of_subjects(
+        &self,
+        subject_ids: impl IntoIterator<Item = u64>,
+        options: EpisodeStreamOptions,
+    ) -> impl Stream<Item = (u64, Result<Episode, GetEpisodesError>)> + Send + '_ {
+        let limiter = options
+            .rate_limit
+            .map(|limit| Arc::new(RateLimiter::new(limit)));
+        let subject_ids: Vec<_> = subject_ids.into_iter().collect();
+
+        stream::iter(subject_ids)
+            .map(move |subject_id| {
+                let limiter = limiter.clone();
+                async move {
+                    let (episodes, err) = self
+                        .subject_episodes(subject_id, options, limiter.as_deref())
+                        .await;
+                    let episodes = episodes.into_iter().map(Ok).chain(err.map(Err));
+                    stream::iter(episodes.map(move |episode| (subject_id, episode)))
+                }
+            })
+            .buffer_unordered(options.concurrency.max(1))
+            .flatten()
+    }
+
+    /// 依次获取一个条目的所有分页，返回已获取的章节与中途的错误
+    async fn subject_episodes(
+        &self,
+        subject_id: u64,
+        options: EpisodeStreamOptions,
+        limiter: Option<&RateLimiter>,
+    ) -> (Vec<Episode>, Option<GetEpisodesError>) {
+        let mut builder = self.get_episodes(subject_id).limit(100);
+        if let Some(episode_type) = options.episode_type {
+            builder = builder.r#type(episode_type);
+        }
+        if let Some(timeout) = options.timeout_per_page {
+            builder = builder.timeout_per_page(timeout);
+        }
+        let mut executor = match builder.build() {
+            Ok(executor) => executor,
+            Err(err) => return (Vec::new(), Some(err.into())),
+        };
+
+        let mut episodes = Vec::new();
+        loop {
+            if let Some(limiter) = limiter {
+                limiter
+                    .acquire(scheduler::current_priority(), self.clock(), 1)
+                    .await;
+            }
+            let page = match pagination::fetch_page(&executor).await {
+                Ok(page) => page,
+                Err(err) => return (episodes, Some(err)),
+            };
+            let (has_next, next_offset) = (page.has_next(), page.next_offset());
+            episodes.extend(page.data);
+            if !has_next {
+                return (episodes, None);
+            }
+            executor.set_offset(next_offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_get_episodes_by_ids() {
+        let episode = |id: u64| serde_json::to_string(&crate::mock::episode(id)).unwrap();
+        let server = MockServer::start(vec![
+            MockResponse::new(200, episode(1)),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+            MockResponse::new(200, episode(3)),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let results = client.get_episodes_by_ids([1, 2, 3], 1).await;
+        assert!(!results.is_complete());
+        assert_eq!(
+            results.ok().map(|episode| episode.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(
+            results.failed().map(|(id, _)| id).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(
+            results
+                .results
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(results.into_result().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_episodes_of_subjects() {
+        let page = |ids: &[u64], total: u64, offset: u64| {
+            let data: Vec<_> = ids
+                .iter()
+                .map(|id| serde_json::to_string(&crate::mock::episode(*id)).unwrap())
+                .collect();
+            format!(
+                r#"{{"total":{total},"limit":100,"offset":{offset},"data":[{}]}}"#,
+                data.join(",")
+            )
+        };
+        let server = MockServer::start(vec![
+            MockResponse::new(200, page(&[1], 2, 0)),
+            MockResponse::new(200, page(&[2], 2, 1)),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let episodes: Vec<_> = client
+            .episodes_of_subjects(
+                [7, 8],
+                EpisodeStreamOptions::default()
+                    .concurrency(1)
+                    .episode_type(EpisodeType::MainStory)
+                    .rate_limit(None),
+            )
+            .collect()
+            .await;
+        assert_eq!(episodes.len(), 3);
+        assert_eq!(episodes[0].0, 7);
+        assert_eq!(episodes[1].1.as_ref().unwrap().id, 2);
+        assert!(matches!(episodes[2], (8, Err(_))));
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[1].line,
+            "GET /v0/episodes?subject_id=7&type=0&limit=100&offset=1 HTTP/1.1"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! # Response Cache (响应缓存)
+//!
+//! 启用 `cache` feature 后，可以通过 [`ClientBuilder::cache`](super::ClientBuilder::cache) 为 [`Client`](super::Client)
+//! 开启内存中的 LRU + TTL 缓存。所有返回 JSON 的 `GET` API 的响应会以请求 URL 为键缓存，
+//! 在 TTL 内重复请求相同的 URL 时直接返回缓存的结果，不再发送请求。
+//!
+//! 缓存中保存的是响应体，命中时重新反序列化。命中次数会计入 [`RequestStats::cache_hits`](super::stats::RequestStats::cache_hits)。
+//!
+//! 响应中包含 `ETag` 或 `Last-Modified` 时，过期的缓存不会立即丢弃，而是带上 `If-None-Match` / `If-Modified-Since`
+//! 发送条件请求；服务器返回 `304 Not Modified` 时直接使用缓存的响应体并刷新有效期，大幅减少重复抓取时的流量。
+//!
+//! ## Example
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::cache::CacheConfig;
+//!
+//! let client = Client::builder()
+//!     .cache(CacheConfig::default().capacity(1024).ttl(Duration::from_secs(3600)))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
+};
+
+use super::Client;
+use crate::error::DepsError;
+
+/// Cache configuration (缓存配置)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheConfig {
+    /// 最多缓存的响应数量，超出时淘汰最久未使用的响应
+    pub capacity: usize,
+
+    /// 缓存的有效期
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            ttl: Duration::from_secs(600),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// 设置最多缓存的响应数量
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// 设置缓存的有效期
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+/// 条件请求所需的校验信息
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    /// 从响应头中读取 `ETag` 与 `Last-Modified`
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: get(ETAG),
+            last_modified: get(LAST_MODIFIED),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// 为请求添加 `If-None-Match` 与 `If-Modified-Since`
+    fn apply(&self, headers: &mut HeaderMap) -> Result<(), DepsError> {
+        if let Some(etag) = &self.etag {
+            headers.insert(IF_NONE_MATCH, etag.parse()?);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.parse()?);
+        }
+        Ok(())
+    }
+}
+
+struct Entry {
+    body: Bytes,
+    validators: Validators,
+    inserted: Instant,
+    last_used: u64,
+}
+
+/// 缓存查询结果
+pub(crate) enum Lookup {
+    /// 未过期的响应体
+    Fresh(Bytes),
+
+    /// 已过期但可以通过条件请求重新验证的响应体
+    Stale(Bytes, Validators),
+
+    /// 没有可用的缓存
+    Miss,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    tick: u64,
+}
+
+/// LRU + TTL 响应缓存
+pub(crate) struct ResponseCache {
+    config: CacheConfig,
+    state: Mutex<State>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Default::default(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 查询 `key` 对应的缓存
+    ///
+    /// 过期且没有校验信息的缓存会被删除。
+    pub(crate) fn get(&self, key: &str, now: Instant) -> Lookup {
+        let mut state = self.state();
+        state.tick += 1;
+        let tick = state.tick;
+
+        let Some(entry) = state.entries.get_mut(key) else {
+            return Lookup::Miss;
+        };
+        entry.last_used = tick;
+        if now.saturating_duration_since(entry.inserted) < self.config.ttl {
+            return Lookup::Fresh(entry.body.clone());
+        }
+        if !entry.validators.is_empty() {
+            return Lookup::Stale(entry.body.clone(), entry.validators.clone());
+        }
+        state.entries.remove(key);
+        Lookup::Miss
+    }
+
+    /// 服务器确认缓存未修改后，刷新 `key` 的有效期
+    pub(crate) fn revalidate(&self, key: &str, now: Instant) {
+        if let Some(entry) = self.state().entries.get_mut(key) {
+            entry.inserted = now;
+        }
+    }
+
+    /// 缓存 `key` 对应的响应体
+    pub(crate) fn insert(&self, key: String, body: Bytes, validators: Validators, now: Instant) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state();
+        state.tick += 1;
+        let tick = state.tick;
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.config.capacity {
+            let lru = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(lru) = lru {
+                state.entries.remove(&lru);
+            }
+        }
+
+        state.entries.insert(
+            key,
+            Entry {
+                body,
+                validators,
+                inserted: now,
+                last_used: tick,
+            },
+        );
+    }
+
+    /// 清空缓存
+    pub(crate) fn clear(&self) {
+        self.state().entries.clear();
+    }
+}
+
+impl Client {
+    /// 通过缓存发送 `GET` 请求并返回响应体
+    pub(super) async fn execute_cached(
+        &self,
+        cache: &ResponseCache,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+    ) -> Result<Bytes, DepsError> {
+        let key = req.url().to_string();
+
+        let stale = match cache.get(&key, self.clock().now()) {
+            Lookup::Fresh(body) => {
+                self.stats.record_cache_hit();
+                return Ok(body);
+            }
+            Lookup::Stale(body, validators) => {
+                validators.apply(req.headers_mut())?;
+                Some(body)
+            }
+            Lookup::Miss => None,
+        };
+
+        let res = self.execute(endpoint, req).await?;
+        if let (StatusCode::NOT_MODIFIED, Some(body)) = (res.status(), stale) {
+            self.read_bytes(res, self.max_json_size).await?;
+            cache.revalidate(&key, self.clock().now());
+            self.stats.record_cache_hit();
+            return Ok(body);
+        }
+
+        let res = self.check_status(res).await?;
+        let validators = Validators::from_headers(res.headers());
+        let body = self.read_bytes(res, self.max_json_size).await?;
+
+        cache.insert(key, body.clone(), validators, self.clock().now());
+        Ok(body)
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("config", &self.config)
+            .field("len", &self.state().entries.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::Client,
+        mock::{MockResponse, MockServer},
+        time::ManualClock,
+    };
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache = ResponseCache::new(CacheConfig::default().capacity(2));
+        let now = Instant::now();
+
+        let insert = |key: &str| {
+            cache.insert(
+                key.to_string(),
+                Bytes::from(key.to_string()),
+                Validators::default(),
+                now,
+            )
+        };
+        let is_fresh = |key: &str| matches!(cache.get(key, now), Lookup::Fresh(_));
+
+        insert("a");
+        insert("b");
+        assert!(is_fresh("a"));
+        insert("c");
+
+        assert!(is_fresh("a"));
+        assert!(!is_fresh("b"));
+        assert!(is_fresh("c"));
+    }
+
+    #[tokio::test]
+    async fn test_client_cache() {
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user),
+            MockResponse::new(200, user),
+        ])
+        .await;
+
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .cache(CacheConfig::default().ttl(Duration::from_secs(60)))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        client.get_user("sai").await.unwrap();
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(client.stats().cache_hits, 1);
+
+        clock.advance(Duration::from_secs(60));
+        client.get_user("sai").await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_cleared_on_token_swap() {
+        let me = |username: &str| {
+            format!(
+                r#"{{"id":1,"username":"{}","nickname":"","user_group":1,"avatar":{{"large":"","medium":"","small":""}},"sign":""}}"#,
+                username
+            )
+        };
+        let server = MockServer::start(vec![
+            MockResponse::new(200, me("alice")),
+            MockResponse::new(200, me("bob")),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .token("alice_token")
+            .cache(CacheConfig::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_me().await.unwrap().username, "alice");
+        client.set_token("bob_token");
+        assert_eq!(client.get_me().await.unwrap().username, "bob");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].header("authorization"),
+            Some("Bearer bob_token")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conditional_request() {
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user).header("etag", "\"v1\""),
+            MockResponse::new(304, ""),
+        ])
+        .await;
+
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .cache(CacheConfig::default().ttl(Duration::from_secs(60)))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        client.get_user("sai").await.unwrap();
+        clock.advance(Duration::from_secs(60));
+        let user = client.get_user("sai").await.unwrap();
+        assert_eq!(user.username, "sai");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header("if-none-match"), Some("\"v1\""));
+        assert_eq!(client.stats().cache_hits, 1);
+    }
+}
@@ -0,0 +1,147 @@
+//! # Characters Resource (角色资源)
+
+use std::collections::HashMap;
+
+use super::Client;
+use crate::{error::*, types::*};
+
+/// 角色在一个条目中的出场
+///
+/// 由 [`Client::character_appearances`] 构建。
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct CharacterAppearance {
+    /// 相关条目，[`RelatedSubject::staff`] 为角色在条目中的定位，例如 `主角`
+    pub subject: RelatedSubject,
+
+    /// 在该条目中为角色配音的人物，按 API 返回的顺序排列
+    pub voice_actors: Vec<CharacterPerson>,
+}
+
+impl CharacterAppearance {
+    /// 创建一次出场
+    pub fn new(subject: RelatedSubject, voice_actors: Vec<CharacterPerson>) -> Self {
+        Self {
+            subject,
+            voice_actors,
+        }
+    }
+
+    /// 配音人物的名称
+    pub fn voice_actor_names(&self) -> impl Iterator<Item = &str> {
+        self.voice_actors.iter().map(|person| person.name.as_str())
+    }
+}
+
+/// # Aggregate character methods for [`Client`].
+impl Client {
+    /// 获取角色的出场条目及各条目中的配音人物
+    ///
+    /// 并发请求 [`get_character_subjects`](Client::get_character_subjects) 与
+    /// [`get_character_persons`](Client::get_character_persons)，按条目合并为 [`CharacterAppearance`]，
+    /// 顺序与 `get_character_subjects` 一致。不在相关条目中的人物会被忽略。任意一个请求失败时返回其错误。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::new();
+    /// for appearance in client.character_appearances(3498).await? {
+    ///     let names: Vec<_> = appearance.voice_actor_names().collect();
+    ///     println!("{}: {}", appearance.subject.name, names.join(", "));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn character_appearances(
+        &self,
+        character_id: impl Into<CharacterId>,
+    ) -> Result<Vec<CharacterAppearance>, DepsError> {
+        let character_id: CharacterId = character_id.into();
+
+        let (subjects, persons) = futures_util::try_join!(
+            self.get_character_subjects(character_id),
+            self.get_character_persons(character_id),
+        )?;
+
+        let mut voice_actors: HashMap<u64, Vec<CharacterPerson>> = HashMap::new();
+        for person in persons {
+            voice_actors
+                .entry(person.subject_id)
+                .or_default()
+                .push(person);
+        }
+
+        Ok(subjects
+            .into_iter()
+            .map(|subject| {
+                let persons = voice_actors.remove(&subject.id).unwrap_or_default();
+                CharacterAppearance::new(subject, persons)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::transport::{HttpTransport, TransportFuture};
+
+    /// 按路径返回固定响应的传输层
+    struct Routes(Vec<(&'static str, String)>);
+
+    impl HttpTransport for Routes {
+        fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+            let body = self
+                .0
+                .iter()
+                .find(|(path, _)| *path == req.url().path())
+                .map(|(_, body)| body.clone())
+                .unwrap_or_default();
+            Box::pin(async move {
+                let res = http::Response::builder().status(200).body(body).unwrap();
+                Ok(reqwest::Response::from(res))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_character_appearances() {
+        let related = |id: u64| {
+            format!(
+                r#"{{"id":{},"type":2,"staff":"主角","name":"s{}","name_cn":"","image":""}}"#,
+                id, id
+            )
+        };
+        let person = |id: u64, subject_id: u64| {
+            format!(
+                r#"{{"id":{},"name":"p{}","type":1,"images":null,"subject_id":{},"subject_type":2,"subject_name":"","subject_name_cn":"","staff":null}}"#,
+                id, id, subject_id
+            )
+        };
+        let client = Client::builder()
+            .transport(Routes(vec![
+                (
+                    "/v0/characters/7/subjects",
+                    format!("[{},{}]", related(1), related(2)),
+                ),
+                (
+                    "/v0/characters/7/persons",
+                    format!("[{},{},{}]", person(10, 2), person(11, 2), person(12, 3)),
+                ),
+            ]))
+            .build()
+            .unwrap();
+
+        let appearances = client.character_appearances(7).await.unwrap();
+        assert_eq!(
+            appearances
+                .iter()
+                .map(|a| (a.subject.id, a.voice_actor_names().collect::<Vec<_>>()))
+                .collect::<Vec<_>>(),
+            vec![(1, vec![]), (2, vec!["p10", "p11"])]
+        );
+    }
+}
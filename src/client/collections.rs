@@ -0,0 +1,339 @@
+//! # Collections Resource (收藏资源)
+
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    ops::Deref,
+};
+
+use derive_builder::Builder;
+use futures_core::Stream;
+
+use super::{
+    pagination::{self, FetchAllOptions, Paginated},
+    Client, ExecutorFuture,
+};
+use crate::{error::*, sealed::Sealed, types::*};
+
+/// # 获取用户收藏执行器
+///
+/// 此结构用于构建请求参数并发送请求
+#[derive(Clone, Debug, Builder)]
+#[builder(pattern = "owned", setter(strip_option))]
+pub struct GetUserCollectionsExecutor<'a> {
+    #[doc(hidden)]
+    client: &'a Client,
+
+    /// 用户名
+    #[builder(setter(into))]
+    username: String,
+
+    /// 条目类型
+    #[builder(default)]
+    subject_type: Option<SubjectType>,
+
+    /// 收藏类型
+    #[builder(default)]
+    r#type: Option<SubjectCollectionType>,
+
+    /// 返回数量
+    #[builder(default)]
+    limit: Option<u64>,
+
+    /// 偏移量
+    #[builder(default)]
+    offset: Option<u64>,
+
+    /// 分页的超时时间
+    ///
+    /// 仅对 `pages()`、`items()` 与 `fetch_all()` 中的每一页生效，参见 [`pagination`](super::pagination) 模块。
+    #[builder(default)]
+    timeout_per_page: Option<std::time::Duration>,
+}
+
+impl Deref for GetUserCollectionsExecutor<'_> {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl GetUserCollectionsExecutor<'_> {
+    /// 返回一个 Builder 模式的 [`GetUserCollectionsExecutorBuilder`], 用于构建请求参数
+    pub(super) fn builder(
+        client: &Client,
+        username: impl Into<String>,
+    ) -> GetUserCollectionsExecutorBuilder<'_> {
+        GetUserCollectionsExecutorBuilder::default()
+            .username(username)
+            .client(client)
+    }
+
+    /// 发送请求
+    ///
+    /// 根据构建的请求参数发送请求，并返回收藏列表
+    pub async fn send(&self) -> Result<PagedUserCollection, GetUserCollectionsError> {
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回原始的 JSON，用于访问 [`UserSubjectCollection`] 尚未包含的字段
+    pub async fn send_raw(&self) -> Result<serde_json::Value, GetUserCollectionsError> {
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回未解析的分页结果，参见 [`PagedRaw`]
+    pub async fn send_lazy(&self) -> Result<PagedRaw, GetUserCollectionsError> {
+        Ok(self.send_as().await?)
+    }
+
+    async fn send_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, DepsError> {
+        let url = self.api_url(&["v0", "users", &self.username, "collections"])?;
+
+        let req = self
+            .client()
+            .get(url)
+            .query(&[("subject_type", self.subject_type)])
+            .query(&[("type", self.r#type)])
+            .query(&[("limit", self.limit)])
+            .query(&[("offset", self.offset)])
+            .build()?;
+
+        self.client
+            .execute_json("GET /v0/users/{username}/collections", req)
+            .await
+    }
+}
+
+impl<'a> GetUserCollectionsExecutor<'a> {
+    /// 逐页获取收藏
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据
+    pub fn pages(
+        &self,
+    ) -> impl Stream<Item = Result<PagedUserCollection, GetUserCollectionsError>> + Send + 'a {
+        pagination::pages(self.clone())
+    }
+
+    /// 逐条获取收藏
+    ///
+    /// 与 [`pages`](Self::pages) 相同，但返回每一条数据
+    pub fn items(
+        &self,
+    ) -> impl Stream<Item = Result<UserSubjectCollection, GetUserCollectionsError>> + Send + 'a
+    {
+        pagination::items(self.clone())
+    }
+
+    /// 获取全部收藏
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据，并返回所有数据
+    pub async fn fetch_all(&self) -> Result<Vec<UserSubjectCollection>, GetUserCollectionsError> {
+        self.fetch_all_with(FetchAllOptions::default()).await
+    }
+
+    /// 获取全部收藏，可以限制数量与设置分页间的等待时间
+    ///
+    /// 参见 [`FetchAllOptions`]
+    pub async fn fetch_all_with(
+        &self,
+        options: FetchAllOptions,
+    ) -> Result<Vec<UserSubjectCollection>, GetUserCollectionsError> {
+        pagination::fetch_all(self.clone(), self.client.clock(), options).await
+    }
+}
+
+impl Sealed for GetUserCollectionsExecutor<'_> {}
+
+impl Paginated for GetUserCollectionsExecutor<'_> {
+    type Page = PagedUserCollection;
+    type Error = GetUserCollectionsError;
+
+    fn set_offset(&mut self, offset: u64) {
+        self.offset = Some(offset);
+    }
+
+    fn timeout_per_page(&self) -> Option<std::time::Duration> {
+        self.timeout_per_page
+    }
+
+    fn clock(&self) -> &dyn crate::time::Clock {
+        self.client.clock()
+    }
+
+    fn fetch_page(
+        &self,
+    ) -> impl Future<Output = Result<PagedUserCollection, GetUserCollectionsError>> + Send {
+        self.send()
+    }
+}
+
+impl GetUserCollectionsExecutorBuilder<'_> {
+    /// 发送请求
+    ///
+    /// 此方法会先调用 [`build`](GetUserCollectionsExecutorBuilder::build) 方法构建请求参数，然后发送请求
+    pub async fn send(self) -> Result<PagedUserCollection, GetUserCollectionsError> {
+        self.build()?.send().await
+    }
+}
+
+impl<'a> IntoFuture for GetUserCollectionsExecutorBuilder<'a> {
+    type Output = Result<PagedUserCollection, GetUserCollectionsError>;
+    type IntoFuture = ExecutorFuture<'a, Self::Output>;
+
+    /// 等同于 [`send`](GetUserCollectionsExecutorBuilder::send)
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+/// Collection statistics (收藏统计)
+///
+/// 由 [`Client::collection_stats`] 或 [`CollectionStats::from_collections`] 计算。
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct CollectionStats {
+    /// 收藏总数
+    pub total: u64,
+
+    /// 各收藏类型的数量
+    pub by_status: HashMap<SubjectCollectionType, u64>,
+
+    /// 各条目类型的数量
+    pub by_type: HashMap<SubjectType, u64>,
+
+    /// 已评分的收藏数量
+    pub rated: u64,
+
+    /// 平均评分，没有评分时为 `None`
+    pub mean_score: Option<f64>,
+
+    /// 评分分布，下标 `0` 为 1 分的数量，下标 `9` 为 10 分的数量
+    pub score_histogram: [u64; 10],
+
+    /// 各标签出现的次数
+    pub tags: HashMap<String, u64>,
+}
+
+impl CollectionStats {
+    /// 统计 `collections`
+    pub fn from_collections<'a>(
+        collections: impl IntoIterator<Item = &'a UserSubjectCollection>,
+    ) -> Self {
+        let mut stats = Self::default();
+        let mut score_sum = 0u64;
+        for collection in collections {
+            stats.total += 1;
+            *stats.by_status.entry(collection.r#type).or_default() += 1;
+            *stats.by_type.entry(collection.subject_type).or_default() += 1;
+            if (1..=10).contains(&collection.rate) {
+                stats.rated += 1;
+                score_sum += u64::from(collection.rate);
+                stats.score_histogram[usize::from(collection.rate) - 1] += 1;
+            }
+            for tag in &collection.tags {
+                *stats.tags.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        stats.mean_score = (stats.rated > 0).then(|| score_sum as f64 / stats.rated as f64);
+        stats
+    }
+
+    /// 某收藏类型的数量
+    pub fn status(&self, status: SubjectCollectionType) -> u64 {
+        self.by_status.get(&status).copied().unwrap_or_default()
+    }
+
+    /// 某条目类型的数量
+    pub fn subject_type(&self, subject_type: SubjectType) -> u64 {
+        self.by_type.get(&subject_type).copied().unwrap_or_default()
+    }
+
+    /// 出现次数最多的 `n` 个标签，次数相同时按标签排序
+    pub fn top_tags(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut tags: Vec<_> = self
+            .tags
+            .iter()
+            .map(|(tag, count)| (tag.as_str(), *count))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        tags.truncate(n);
+        tags
+    }
+}
+
+/// # Collection statistics methods for [`Client`].
+impl Client {
+    /// 统计用户的全部收藏
+    ///
+    /// 依次获取 [`get_user_collections`](Client::get_user_collections) 的所有分页，
+    /// 并按收藏类型、条目类型、评分与标签汇总，参见 [`CollectionStats`]。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let stats = client.collection_stats("sai").await?;
+    /// println!("{} collections, mean score {:?}", stats.total, stats.mean_score);
+    /// for (tag, count) in stats.top_tags(10) {
+    ///     println!("{}: {}", tag, count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collection_stats(
+        &self,
+        username: impl Into<String>,
+    ) -> Result<CollectionStats, GetUserCollectionsError> {
+        let collections = self
+            .get_user_collections(username)
+            .limit(100)
+            .build()?
+            .fetch_all()
+            .await?;
+        Ok(CollectionStats::from_collections(&collections))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_collection_stats() {
+        let collection = |subject_type: u8, rate: u8, r#type: u8, tags: &str| {
+            format!(
+                r#"{{"subject_id":1,"subject_type":{subject_type},"rate":{rate},"type":{type},"comment":null,"tags":[{tags}],"ep_status":0,"vol_status":0,"updated_at":"2024-01-01T12:00:00+08:00","private":false}}"#
+            )
+        };
+        let body = format!(
+            r#"{{"total":3,"limit":100,"offset":0,"data":[{},{},{}]}}"#,
+            collection(2, 8, 2, r#""TV","2024""#),
+            collection(2, 0, 3, r#""TV""#),
+            collection(1, 6, 2, ""),
+        );
+        let server = MockServer::start(vec![MockResponse::new(200, body)]).await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let stats = client.collection_stats("sai").await.unwrap();
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.status(SubjectCollectionType::Collect), 2);
+        assert_eq!(stats.status(SubjectCollectionType::Dropped), 0);
+        assert_eq!(stats.subject_type(SubjectType::Anime), 2);
+        assert_eq!(stats.rated, 2);
+        assert_eq!(stats.mean_score, Some(7.0));
+        assert_eq!(stats.score_histogram[7], 1);
+        assert_eq!(stats.top_tags(1), vec![("TV", 2)]);
+        assert_eq!(
+            server.requests()[0].line,
+            "GET /v0/users/sai/collections?limit=100 HTTP/1.1"
+        );
+    }
+}
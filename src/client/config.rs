@@ -0,0 +1,376 @@
+//! # Configuration Snapshot (配置快照)
+//!
+//! [`ClientConfigSnapshot`] 是 [`Client`] 生效配置的可序列化快照，可以附在 bug 报告中，
+//! 或在另一台机器上通过 [`Client::from_snapshot`] 复现相同的配置。
+//!
+//! 启用 `config` feature 后，还可以通过 [`Client::from_config_path`] 从 TOML 配置文件构建 [`Client`]，
+//! 适合基于本 crate 的命令行工具读取用户的配置。
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Client, ClientBuilder, ClientBuilderError};
+
+/// Client configuration snapshot (客户端配置快照)
+///
+/// 默认不包含 token 等敏感信息，参见 [`Client::snapshot_config_with_secrets`]。
+///
+/// 自定义的 [`reqwest::Client`] 与 [`TokenProvider`](super::token::TokenProvider) 无法被序列化，不会包含在快照中。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClientConfigSnapshot {
+    /// Base URL of the API.
+    pub base_url: String,
+
+    /// Base URL of the OAuth API.
+    #[serde(default)]
+    pub oauth_base_url: String,
+
+    /// User agent.
+    pub user_agent: String,
+
+    /// Granted scopes declared on the client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<crate::types::Scopes>,
+
+    /// Total timeout of a request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<Duration>,
+
+    /// Connect timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<Duration>,
+
+    /// Read timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout: Option<Duration>,
+
+    /// Maximum size of JSON response bodies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_json_size: Option<u64>,
+
+    /// Maximum size of image response bodies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_image_size: Option<u64>,
+
+    /// Default privacy of collection writes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_private_collections: Option<bool>,
+
+    /// 是否禁用了环境变量中的代理
+    #[serde(default)]
+    pub no_proxy: bool,
+
+    /// Proxy URL, 可能包含认证信息，仅在 [`Client::snapshot_config_with_secrets`] 中包含
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// 是否设置了 token
+    pub has_token: bool,
+
+    /// Authorization token, 仅在 [`Client::snapshot_config_with_secrets`] 中包含
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// # Configuration snapshot methods for [`Client`].
+impl Client {
+    /// 获取当前生效配置的快照，不包含 token 等敏感信息
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use bgmtv::prelude::*;
+    /// let client = Client::builder()
+    ///     .user_agent("xxx/yyy/1.0")
+    ///     .token("auth_token")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let snapshot = client.snapshot_config();
+    /// assert_eq!(snapshot.user_agent, "xxx/yyy/1.0");
+    /// assert!(snapshot.has_token);
+    /// assert_eq!(snapshot.token, None);
+    /// ```
+    pub fn snapshot_config(&self) -> ClientConfigSnapshot {
+        ClientConfigSnapshot {
+            base_url: self.base_url().to_string(),
+            oauth_base_url: self
+                .oauth_base_url
+                .as_str()
+                .trim_end_matches('/')
+                .to_string(),
+            user_agent: self.user_agent().to_string(),
+            scopes: self.scopes,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            max_json_size: self.max_json_size,
+            max_image_size: self.max_image_size,
+            default_private_collections: self.default_private_collections,
+            no_proxy: self.no_proxy,
+            proxy: None,
+            has_token: self.token.is_some(),
+            token: None,
+        }
+    }
+
+    /// 获取当前生效配置的快照，包含当前的 token 与代理 URL
+    pub async fn snapshot_config_with_secrets(&self) -> ClientConfigSnapshot {
+        ClientConfigSnapshot {
+            token: self.token().await,
+            proxy: self.proxy.clone(),
+            ..self.snapshot_config()
+        }
+    }
+
+    /// 从配置快照构建 [`Client`]
+    pub fn from_snapshot(snapshot: &ClientConfigSnapshot) -> Result<Client, ClientBuilderError> {
+        let mut builder = ClientBuilder::default();
+        builder
+            .base_url(snapshot.base_url.clone())
+            .user_agent(snapshot.user_agent.clone());
+        if !snapshot.oauth_base_url.is_empty() {
+            builder.oauth_base_url(snapshot.oauth_base_url.clone());
+        }
+        if let Some(scopes) = snapshot.scopes {
+            builder.scopes(scopes);
+        }
+        if let Some(timeout) = snapshot.timeout {
+            builder.timeout(timeout);
+        }
+        if let Some(timeout) = snapshot.connect_timeout {
+            builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = snapshot.read_timeout {
+            builder.read_timeout(timeout);
+        }
+        if let Some(size) = snapshot.max_json_size {
+            builder.max_json_size(size);
+        }
+        if let Some(size) = snapshot.max_image_size {
+            builder.max_image_size(size);
+        }
+        if let Some(private) = snapshot.default_private_collections {
+            builder.default_private_collections(private);
+        }
+        if snapshot.no_proxy {
+            builder.no_proxy();
+        }
+        if let Some(proxy) = &snapshot.proxy {
+            builder.proxy(proxy.clone());
+        }
+        if let Some(token) = &snapshot.token {
+            builder.token(token.clone());
+        }
+        builder.build()
+    }
+}
+
+/// Config file (配置文件)
+///
+/// [`Client::from_config_path`] 读取的 TOML 配置，所有项都是可选的，时长均以秒为单位：
+///
+/// ```toml
+/// user_agent = "me/my-app/1.0"
+/// token = "auth_token"
+/// base_url = "https://api.bgm.tv"
+/// cache_dir = "/home/me/.cache/my-app"
+/// timeout = 30
+/// proxy = "http://127.0.0.1:7890"
+///
+/// [rate_limit]
+/// requests = 2
+/// per = 1
+/// ```
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct ConfigFile {
+    /// User agent.
+    pub user_agent: Option<String>,
+
+    /// Authorization token.
+    pub token: Option<String>,
+
+    /// Base URL of the API.
+    pub base_url: Option<String>,
+
+    /// Base URL of the OAuth API.
+    pub oauth_base_url: Option<String>,
+
+    /// 离线快照目录，设置后使用 [`FileSnapshotStore`](super::offline::FileSnapshotStore)
+    pub cache_dir: Option<std::path::PathBuf>,
+
+    /// 请求的总超时时间 (秒)
+    pub timeout: Option<u64>,
+
+    /// 建立连接的超时时间 (秒)
+    pub connect_timeout: Option<u64>,
+
+    /// Proxy URL.
+    pub proxy: Option<String>,
+
+    /// 限流配置
+    pub rate_limit: Option<ConfigRateLimit>,
+}
+
+/// [`ConfigFile`] 中的限流配置
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct ConfigRateLimit {
+    /// 每个周期内的请求数
+    pub requests: u32,
+
+    /// 周期 (秒)，默认为 1
+    #[serde(default = "default_rate_limit_per")]
+    pub per: u64,
+}
+
+#[cfg(feature = "config")]
+fn default_rate_limit_per() -> u64 {
+    1
+}
+
+#[cfg(feature = "config")]
+impl ConfigFile {
+    /// 解析 TOML 格式的配置
+    pub fn parse(content: &str) -> Result<Self, crate::error::ConfigError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// 根据配置设置 [`ClientBuilder`]
+    pub fn apply(&self, builder: &mut ClientBuilder) -> Result<(), crate::error::ConfigError> {
+        if let Some(user_agent) = &self.user_agent {
+            builder.user_agent(user_agent.clone());
+        }
+        if let Some(token) = &self.token {
+            builder.token(token.clone());
+        }
+        if let Some(base_url) = &self.base_url {
+            builder.base_url(base_url.clone());
+        }
+        if let Some(oauth_base_url) = &self.oauth_base_url {
+            builder.oauth_base_url(oauth_base_url.clone());
+        }
+        if let Some(dir) = &self.cache_dir {
+            builder.snapshot_store(super::offline::FileSnapshotStore::new(dir)?);
+        }
+        if let Some(timeout) = self.timeout {
+            builder.timeout(Duration::from_secs(timeout));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder.connect_timeout(Duration::from_secs(timeout));
+        }
+        if let Some(proxy) = &self.proxy {
+            builder.proxy(proxy.clone());
+        }
+        if let Some(limit) = self.rate_limit {
+            builder.rate_limit(super::scheduler::RateLimit::new(
+                limit.requests,
+                Duration::from_secs(limit.per),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// # Config file methods for [`Client`].
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+impl Client {
+    /// 从 TOML 配置文件构建 [`Client`]，参见 [`ConfigFile`]
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let client = Client::from_config_path("bgmtv.toml")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_config_path(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Client, crate::error::ConfigError> {
+        let content = std::fs::read_to_string(path)?;
+        let mut builder = ClientBuilder::default();
+        ConfigFile::parse(&content)?.apply(&mut builder)?;
+        Ok(builder.build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let client = Client::builder()
+            .base_url("http://localhost:8080".to_string())
+            .user_agent("test_user_agent")
+            .token("test_token")
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        let snapshot = client.snapshot_config();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(!json.contains("test_token"));
+
+        let restored = Client::from_snapshot(&serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(restored.base_url(), "http://localhost:8080");
+        assert_eq!(restored.user_agent(), "test_user_agent");
+        assert!(restored.token().await.is_none());
+        assert_eq!(
+            restored.snapshot_config().timeout,
+            Some(Duration::from_secs(30))
+        );
+
+        let snapshot = client.snapshot_config_with_secrets().await;
+        let restored = Client::from_snapshot(&snapshot).unwrap();
+        assert_eq!(restored.token().await.as_deref(), Some("test_token"));
+    }
+
+    #[cfg(feature = "config")]
+    #[tokio::test]
+    async fn test_from_config_path() {
+        let path = std::env::temp_dir().join(format!("bgmtv-config-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+                user_agent = "me/app/1.0"
+                token = "file_token"
+                base_url = "http://localhost:8080"
+                timeout = 30
+
+                [rate_limit]
+                requests = 2
+            "#,
+        )
+        .unwrap();
+
+        let client = Client::from_config_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(client.user_agent(), "me/app/1.0");
+        assert_eq!(client.base_url(), "http://localhost:8080");
+        assert_eq!(client.token().await.as_deref(), Some("file_token"));
+        assert_eq!(
+            client.snapshot_config().timeout,
+            Some(Duration::from_secs(30))
+        );
+
+        assert!(matches!(
+            ConfigFile::parse("unknown = 1"),
+            Err(crate::error::ConfigError::Toml(_))
+        ));
+        assert!(matches!(
+            Client::from_config_path("/nonexistent/bgmtv.toml"),
+            Err(crate::error::ConfigError::Io(_))
+        ));
+    }
+}
@@ -1,16 +1,23 @@
 //! # Episodes Resource (章节资源)
 
-use std::ops::Deref;
+use std::{
+    future::{Future, IntoFuture},
+    ops::Deref,
+};
 
 use derive_builder::Builder;
+use futures_core::Stream;
 
-use super::Client;
-use crate::{error::*, types::*};
+use super::{
+    pagination::{self, FetchAllOptions, Paginated},
+    Client, ExecutorFuture,
+};
+use crate::{error::*, sealed::Sealed, types::*};
 
 /// # 获取章节列表执行器
 ///
 /// 此结构用于构建请求参数并发送请求
-#[derive(Debug, Builder)]
+#[derive(Clone, Debug, Builder)]
 #[builder(pattern = "owned", setter(strip_option))]
 pub struct GetEpisodesExecutor<'a> {
     #[doc(hidden)]
@@ -30,6 +37,12 @@ pub struct GetEpisodesExecutor<'a> {
     /// 偏移量
     #[builder(default)]
     offset: Option<u64>,
+
+    /// 分页的超时时间
+    ///
+    /// 仅对 `pages()`、`items()` 与 `fetch_all()` 中的每一页生效，参见 [`pagination`](super::pagination) 模块。
+    #[builder(default)]
+    timeout_per_page: Option<std::time::Duration>,
 }
 
 impl Deref for GetEpisodesExecutor<'_> {
@@ -42,7 +55,7 @@ impl Deref for GetEpisodesExecutor<'_> {
 
 impl GetEpisodesExecutor<'_> {
     /// 返回一个 Builder 模式的 [`GetEpisodesExecutorBuilder`], 用于构建请求参数
-    pub(super) fn builder(client: &Client, subject_id: u64) -> GetEpisodesExecutorBuilder {
+    pub(super) fn builder(client: &Client, subject_id: u64) -> GetEpisodesExecutorBuilder<'_> {
         GetEpisodesExecutorBuilder::default()
             .subject_id(subject_id)
             .client(client)
@@ -52,23 +65,88 @@ impl GetEpisodesExecutor<'_> {
     ///
     /// 根据构建的请求参数发送请求，并返回搜索结果
     pub async fn send(&self) -> Result<PagedEpisode, GetEpisodesError> {
-        let url = format!("{}/v0/episodes", self.base_url());
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回原始的 JSON，用于访问 [`Episode`] 尚未包含的字段
+    pub async fn send_raw(&self) -> Result<serde_json::Value, GetEpisodesError> {
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回未解析的分页结果，参见 [`PagedRaw`]
+    pub async fn send_lazy(&self) -> Result<PagedRaw, GetEpisodesError> {
+        Ok(self.send_as().await?)
+    }
+
+    async fn send_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, DepsError> {
+        let url = self.api_url(&["v0", "episodes"])?;
 
         let req = self
             .client()
             .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
             .query(&[("subject_id", self.subject_id)])
             .query(&[("type", self.r#type)])
             .query(&[("limit", self.limit)])
             .query(&[("offset", self.offset)])
             .build()?;
 
-        let resp = self.client().execute(req).await?;
+        self.client.execute_json("GET /v0/episodes", req).await
+    }
+}
 
-        let episodes: PagedEpisode = resp.json().await?;
+impl<'a> GetEpisodesExecutor<'a> {
+    /// 逐页获取章节
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据
+    pub fn pages(&self) -> impl Stream<Item = Result<PagedEpisode, GetEpisodesError>> + Send + 'a {
+        pagination::pages(self.clone())
+    }
 
-        Ok(episodes)
+    /// 逐条获取章节
+    ///
+    /// 与 [`pages`](Self::pages) 相同，但返回每一条数据
+    pub fn items(&self) -> impl Stream<Item = Result<Episode, GetEpisodesError>> + Send + 'a {
+        pagination::items(self.clone())
+    }
+
+    /// 获取全部章节
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据，并返回所有数据
+    pub async fn fetch_all(&self) -> Result<Vec<Episode>, GetEpisodesError> {
+        self.fetch_all_with(FetchAllOptions::default()).await
+    }
+
+    /// 获取全部章节，可以限制数量与设置分页间的等待时间
+    ///
+    /// 参见 [`FetchAllOptions`]
+    pub async fn fetch_all_with(
+        &self,
+        options: FetchAllOptions,
+    ) -> Result<Vec<Episode>, GetEpisodesError> {
+        pagination::fetch_all(self.clone(), self.client.clock(), options).await
+    }
+}
+
+impl Sealed for GetEpisodesExecutor<'_> {}
+
+impl Paginated for GetEpisodesExecutor<'_> {
+    type Page = PagedEpisode;
+    type Error = GetEpisodesError;
+
+    fn set_offset(&mut self, offset: u64) {
+        self.offset = Some(offset);
+    }
+
+    fn timeout_per_page(&self) -> Option<std::time::Duration> {
+        self.timeout_per_page
+    }
+
+    fn clock(&self) -> &dyn crate::time::Clock {
+        self.client.clock()
+    }
+
+    fn fetch_page(&self) -> impl Future<Output = Result<PagedEpisode, GetEpisodesError>> + Send {
+        self.send()
     }
 }
 
@@ -80,3 +158,13 @@ impl GetEpisodesExecutorBuilder<'_> {
         self.build()?.send().await
     }
 }
+
+impl<'a> IntoFuture for GetEpisodesExecutorBuilder<'a> {
+    type Output = Result<PagedEpisode, GetEpisodesError>;
+    type IntoFuture = ExecutorFuture<'a, Self::Output>;
+
+    /// 等同于 [`send`](GetEpisodesExecutorBuilder::send)
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
@@ -0,0 +1,210 @@
+//! # Hooks (请求钩子)
+//!
+//! 通过 [`ClientBuilder::interceptor`](super::ClientBuilder::interceptor)、
+//! [`ClientBuilder::on_request`](super::ClientBuilder::on_request) 与
+//! [`ClientBuilder::on_response`](super::ClientBuilder::on_response) 注册的钩子会在每次发送 API 请求时被调用，
+//! 可以用于自定义日志、注入请求头与统计延迟。
+//!
+//! 请求被重试时，每次尝试都会调用钩子；命中缓存的请求不会调用钩子。
+//!
+//! ## Example
+//!
+//! ```
+//! # use bgmtv::prelude::*;
+//! let client = Client::builder()
+//!     .on_request(|endpoint, req| {
+//!         req.headers_mut().insert("x-trace-id", "42".parse().unwrap());
+//!     })
+//!     .on_response(|endpoint, res, elapsed| {
+//!         println!("{} -> {} in {:?}", endpoint, res.status(), elapsed);
+//!     })
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ## Image URL rewriting (图片 URL 改写)
+//!
+//! 通过 [`ClientBuilder::image_url_rewriter`](super::ClientBuilder::image_url_rewriter) 设置的钩子会改写
+//! [`IMAGE_HOST`] 下的图片 URL，例如将图片经由自己的 CDN 分发：
+//!
+//! - API 返回的数据中，值为图片 URL 的字符串 (例如 [`Images`](crate::types::Images) 中的各尺寸)
+//! - [`Client::download_image`](super::Client::download_image)、[`Client::save_image`](super::Client::save_image)
+//!   与图片预取等以 URL 为参数的方法所请求的 URL
+//!
+//! 钩子返回 `None` 时保留原 URL。`/v0/subjects/{subject_id}/image` 等图片 API 由服务器重定向到图片，不会被改写。
+//! 返回的数据在缓存中保存的是改写前的内容，每次读取时重新改写。
+//!
+//! ```
+//! # use bgmtv::prelude::*;
+//! let client = Client::builder()
+//!     .image_url_rewriter(|url| {
+//!         let mut url = url.clone();
+//!         url.set_host(Some("img.example.com")).ok()?;
+//!         Some(url)
+//!     })
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{borrow::Cow, fmt, sync::Arc, time::Duration};
+
+/// Interceptor (拦截器)
+///
+/// 所有方法都有默认的空实现，只需实现需要的方法。
+pub trait Interceptor: Send + Sync {
+    /// 请求发送前调用，可以修改请求
+    ///
+    /// `endpoint` 为 API 名称，例如 `GET /v0/subjects/{subject_id}`。
+    fn on_request(&self, endpoint: &'static str, req: &mut reqwest::Request) {
+        let _ = (endpoint, req);
+    }
+
+    /// 收到响应后调用，`elapsed` 为发送请求到收到响应头的耗时
+    fn on_response(&self, endpoint: &'static str, res: &reqwest::Response, elapsed: Duration) {
+        let _ = (endpoint, res, elapsed);
+    }
+}
+
+/// 由 [`ClientBuilder::on_request`](super::ClientBuilder::on_request) 注册的钩子
+pub(crate) struct OnRequest<F>(pub(crate) F);
+
+impl<F> Interceptor for OnRequest<F>
+where
+    F: Fn(&'static str, &mut reqwest::Request) + Send + Sync,
+{
+    fn on_request(&self, endpoint: &'static str, req: &mut reqwest::Request) {
+        (self.0)(endpoint, req)
+    }
+}
+
+/// 由 [`ClientBuilder::on_response`](super::ClientBuilder::on_response) 注册的钩子
+pub(crate) struct OnResponse<F>(pub(crate) F);
+
+impl<F> Interceptor for OnResponse<F>
+where
+    F: Fn(&'static str, &reqwest::Response, Duration) + Send + Sync,
+{
+    fn on_response(&self, endpoint: &'static str, res: &reqwest::Response, elapsed: Duration) {
+        (self.0)(endpoint, res, elapsed)
+    }
+}
+
+/// [`Interceptor`] trait objects held by [`Client`](super::Client), called in registration order
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(pub(crate) Vec<Arc<dyn Interceptor>>);
+
+impl Interceptors {
+    pub(crate) fn on_request(&self, endpoint: &'static str, req: &mut reqwest::Request) {
+        for interceptor in &self.0 {
+            interceptor.on_request(endpoint, req);
+        }
+    }
+
+    pub(crate) fn on_response(
+        &self,
+        endpoint: &'static str,
+        res: &reqwest::Response,
+        elapsed: Duration,
+    ) {
+        for interceptor in &self.0 {
+            interceptor.on_response(endpoint, res, elapsed);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptors({})", self.0.len())
+    }
+}
+
+/// 图片所在的域名，只有此域名下的 URL 会被图片 URL 改写钩子改写
+pub const IMAGE_HOST: &str = "lain.bgm.tv";
+
+/// 由 [`ClientBuilder::image_url_rewriter`](super::ClientBuilder::image_url_rewriter) 设置的钩子
+#[derive(Clone)]
+pub(crate) struct ImageUrlRewriter(pub(crate) Arc<RewriteFn>);
+
+type RewriteFn = dyn Fn(&url::Url) -> Option<url::Url> + Send + Sync;
+
+impl ImageUrlRewriter {
+    /// 改写 `url`，不是 [`IMAGE_HOST`] 下的 URL 或钩子返回 `None` 时返回 `None`
+    fn rewrite(&self, url: &str) -> Option<String> {
+        if !url.contains(IMAGE_HOST) {
+            return None;
+        }
+        let parsed = url::Url::parse(url).ok()?;
+        if parsed.host_str() != Some(IMAGE_HOST) {
+            return None;
+        }
+        (self.0)(&parsed).map(String::from)
+    }
+
+    /// 改写请求的图片 URL
+    pub(crate) fn rewrite_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        self.rewrite(url).map_or(Cow::Borrowed(url), Cow::Owned)
+    }
+
+    /// 改写 JSON 中所有值为图片 URL 的字符串
+    pub(crate) fn rewrite_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(url) => {
+                if let Some(rewritten) = self.rewrite(url) {
+                    *url = rewritten;
+                }
+            }
+            serde_json::Value::Array(values) => {
+                values.iter_mut().for_each(|value| self.rewrite_json(value))
+            }
+            serde_json::Value::Object(map) => {
+                map.values_mut().for_each(|value| self.rewrite_json(value))
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Debug for ImageUrlRewriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ImageUrlRewriter")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_url_rewriter() {
+        let rewriter = ImageUrlRewriter(Arc::new(|url: &url::Url| {
+            let mut url = url.clone();
+            url.set_host(Some("img.example.com")).ok()?;
+            Some(url)
+        }));
+
+        let mut value = serde_json::json!({
+            "images": {
+                "large": "https://lain.bgm.tv/pic/cover/l/f1/1b/3559_rrwkw.jpg",
+                "small": "",
+            },
+            "summary": "see https://lain.bgm.tv/pic/cover/l/f1/1b/3559_rrwkw.jpg",
+            "tags": [{"name": "https://bgm.tv/subject/3559"}],
+        });
+        rewriter.rewrite_json(&mut value);
+        assert_eq!(
+            value["images"]["large"],
+            "https://img.example.com/pic/cover/l/f1/1b/3559_rrwkw.jpg"
+        );
+        assert_eq!(value["images"]["small"], "");
+        assert_eq!(
+            value["summary"],
+            "see https://lain.bgm.tv/pic/cover/l/f1/1b/3559_rrwkw.jpg"
+        );
+        assert_eq!(value["tags"][0]["name"], "https://bgm.tv/subject/3559");
+
+        assert_eq!(
+            rewriter.rewrite_url("https://img.example.com/a.jpg"),
+            "https://img.example.com/a.jpg"
+        );
+    }
+}
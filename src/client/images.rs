@@ -0,0 +1,589 @@
+//! # Image Prefetching (图片预取)
+//!
+//! [`Client::prefetch_images`] 按指定尺寸批量下载条目封面等图片并写入 [`ImageCache`]，
+//! 已缓存的图片会被跳过，适合在首次同步片单后预热海报墙。
+//! [`Client::prefetch_images_to`] 则将图片写入 [`MediaSink`]，例如本地目录或对象存储。
+//!
+//! [`Client::download_subject_image`] 等方法将图片边下载边写入任意 [`AsyncWrite`]，不会在内存中缓冲整张图片；
+//! 启用 `fs` feature 后，[`Client::save_subject_image`] 等方法则将图片保存到文件，支持跳过未变化的图片与断点续传。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::images::{MemoryImageCache, PrefetchOptions};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//! let cache = MemoryImageCache::default();
+//!
+//! let subjects = vec![client.get_subject(3559).await?];
+//! let report = client
+//!     .prefetch_images(
+//!         subjects.iter().map(|s| &s.images),
+//!         ImageType::Common,
+//!         &cache,
+//!         PrefetchOptions::default().concurrency(8),
+//!     )
+//!     .await;
+//! println!("fetched {}, skipped {}", report.fetched, report.skipped);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::{
+    check_size, identity_encoding,
+    sink::{CacheSink, MediaKey, MediaSink},
+    Client,
+};
+use crate::{error::DepsError, types::*};
+
+#[cfg(feature = "fs")]
+mod save;
+
+#[cfg(feature = "fs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+pub use save::SaveOutcome;
+
+/// Image cache (图片缓存)
+///
+/// 预取的图片以 URL 为键写入缓存，可以为磁盘等存储实现此 trait。
+pub trait ImageCache: Send + Sync {
+    /// `url` 对应的图片是否已缓存
+    fn contains(&self, url: &str) -> bool;
+
+    /// 写入 `url` 对应的图片
+    fn insert(&self, url: &str, image: Bytes);
+}
+
+/// 内存中的图片缓存
+#[derive(Debug, Default)]
+pub struct MemoryImageCache {
+    images: Mutex<HashMap<String, Bytes>>,
+}
+
+impl MemoryImageCache {
+    /// 获取 `url` 对应的图片
+    pub fn get(&self, url: &str) -> Option<Bytes> {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(url)
+            .cloned()
+    }
+
+    /// 已缓存的图片数量
+    pub fn len(&self) -> usize {
+        self.images.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// 是否没有缓存任何图片
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ImageCache for MemoryImageCache {
+    fn contains(&self, url: &str) -> bool {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(url)
+    }
+
+    fn insert(&self, url: &str, image: Bytes) {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(url.to_string(), image);
+    }
+}
+
+/// [`Client::prefetch_images`] 的选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PrefetchOptions {
+    /// 同时进行的下载数量，默认为 4
+    pub concurrency: usize,
+}
+
+impl Default for PrefetchOptions {
+    fn default() -> Self {
+        Self { concurrency: 4 }
+    }
+}
+
+impl PrefetchOptions {
+    /// 设置同时进行的下载数量
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+/// [`Client::prefetch_images`] 的结果
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct PrefetchReport {
+    /// 下载并写入缓存的图片数量
+    pub fetched: usize,
+
+    /// 已缓存或没有对应尺寸而跳过的图片数量
+    pub skipped: usize,
+
+    /// 下载失败的图片 URL 与错误
+    pub failed: Vec<(String, DepsError)>,
+}
+
+/// Download progress (下载进度)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DownloadProgress {
+    /// 已写入的字节数
+    pub downloaded: u64,
+
+    /// 响应头 `Content-Length` 给出的总字节数
+    pub total: Option<u64>,
+}
+
+/// 下载进度回调，每写入一块数据调用一次
+pub type ProgressCallback<'a> = &'a mut (dyn FnMut(DownloadProgress) + Send);
+
+/// # Image prefetching methods for [`Client`].
+impl Client {
+    /// 预取图片
+    ///
+    /// 下载 `images` 中 `size` 尺寸的图片并写入 `cache`，同时进行的下载数量由 [`PrefetchOptions::concurrency`] 限制。
+    /// 重复的 URL 只会下载一次；单张图片下载失败不会中断其余的下载，失败的 URL 记录在 [`PrefetchReport::failed`] 中。
+    pub async fn prefetch_images<'a, I>(
+        &self,
+        images: impl IntoIterator<Item = &'a I>,
+        size: ImageType,
+        cache: &dyn ImageCache,
+        options: PrefetchOptions,
+    ) -> PrefetchReport
+    where
+        I: ImageUrls + 'a,
+    {
+        let key = |url: &str, size| MediaKey {
+            resource: url.to_string(),
+            size,
+        };
+        self.prefetch(images, size, &CacheSink(cache), options, key)
+            .await
+    }
+
+    /// 预取图片并写入 [`MediaSink`]
+    ///
+    /// 与 [`prefetch_images`](Self::prefetch_images) 相同，但图片以 [`MediaKey::from_url`] 为键写入 `sink`，
+    /// 已存在于 `sink` 中的图片会被跳过，写入失败的图片记录在 [`PrefetchReport::failed`] 中。
+    pub async fn prefetch_images_to<'a, I>(
+        &self,
+        images: impl IntoIterator<Item = &'a I>,
+        size: ImageType,
+        sink: &dyn MediaSink,
+        options: PrefetchOptions,
+    ) -> PrefetchReport
+    where
+        I: ImageUrls + 'a,
+    {
+        self.prefetch(images, size, sink, options, MediaKey::from_url)
+            .await
+    }
+
+    async fn prefetch<'a, I>(
+        &self,
+        images: impl IntoIterator<Item = &'a I>,
+        size: ImageType,
+        sink: &dyn MediaSink,
+        options: PrefetchOptions,
+        key: fn(&str, ImageType) -> MediaKey,
+    ) -> PrefetchReport
+    where
+        I: ImageUrls + 'a,
+    {
+        let mut report = PrefetchReport::default();
+        let mut seen = HashSet::new();
+        let mut urls = Vec::new();
+
+        for images in images {
+            match images.url(size) {
+                Some(url) if seen.insert(url) => urls.push(url),
+                _ => report.skipped += 1,
+            }
+        }
+
+        // 使用持有所有权的 URL，借用的 URL 会使 future 不满足 `Send`
+        let mut downloads = futures_util::stream::iter(urls.into_iter().map(str::to_string))
+            .map(|url| async move {
+                let key = key(&url, size);
+                // 无法确认是否已存在时重新下载
+                if sink.contains(&key).await.unwrap_or(false) {
+                    return (url, Ok(false));
+                }
+                let result = async {
+                    let image = self.fetch_image(&url).await?;
+                    sink.write(&key, image).await?;
+                    Ok(true)
+                };
+                let result = result.await;
+                (url, result)
+            })
+            .buffer_unordered(options.concurrency.max(1));
+
+        while let Some((url, result)) = downloads.next().await {
+            match result {
+                Ok(true) => report.fetched += 1,
+                Ok(false) => report.skipped += 1,
+                Err(err) => report.failed.push((url, err)),
+            }
+        }
+
+        report
+    }
+
+    /// 下载 `url` 对应的图片
+    async fn fetch_image(&self, url: &str) -> Result<Bytes, DepsError> {
+        let req = self.client.get(&*self.image_url(url)).build()?;
+        self.execute_bytes("GET {image_url}", req).await
+    }
+}
+
+/// # Image download methods for [`Client`].
+///
+/// 这些方法将图片流式写入 `writer` 并返回写入的字节数，可以传入 [`ProgressCallback`] 获取下载进度。
+impl Client {
+    /// # 下载条目图片 `GET /v0/subjects/{subject_id}/image`
+    ///
+    /// 与 [`get_subject_image`](Client::get_subject_image) 相同，但将图片写入 `writer`。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let mut file = tokio::fs::File::create("3559.jpg").await?;
+    /// let mut progress = |p: bgmtv::client::images::DownloadProgress| {
+    ///     println!("{} / {:?}", p.downloaded, p.total);
+    /// };
+    /// client
+    ///     .download_subject_image(3559, ImageType::Large, &mut file, Some(&mut progress))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_subject_image<W>(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        image_type: ImageType,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let subject_id: SubjectId = subject_id.into();
+
+        let req = self.image_request(
+            &["v0", "subjects", &subject_id.to_string(), "image"],
+            image_type,
+        )?;
+
+        self.execute_download("GET /v0/subjects/{subject_id}/image", req, writer, progress)
+            .await
+    }
+
+    /// # 下载角色图片 `GET /v0/characters/{character_id}/image`
+    ///
+    /// 与 [`get_character_image`](Client::get_character_image) 相同，但将图片写入 `writer`。
+    pub async fn download_character_image<W>(
+        &self,
+        character_id: impl Into<CharacterId>,
+        image_type: ImageType,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let character_id: CharacterId = character_id.into();
+
+        let req = self.image_request(
+            &["v0", "characters", &character_id.to_string(), "image"],
+            image_type,
+        )?;
+
+        self.execute_download(
+            "GET /v0/characters/{character_id}/image",
+            req,
+            writer,
+            progress,
+        )
+        .await
+    }
+
+    /// # 下载人物图片 `GET /v0/persons/{person_id}/image`
+    ///
+    /// 与 [`get_person_image`](Client::get_person_image) 相同，但将图片写入 `writer`。
+    pub async fn download_person_image<W>(
+        &self,
+        person_id: impl Into<PersonId>,
+        image_type: ImageType,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let person_id: PersonId = person_id.into();
+
+        let req = self.image_request(
+            &["v0", "persons", &person_id.to_string(), "image"],
+            image_type,
+        )?;
+
+        self.execute_download("GET /v0/persons/{person_id}/image", req, writer, progress)
+            .await
+    }
+
+    /// # 下载用户头像 `GET /v0/users/{username}/avatar`
+    ///
+    /// 与 [`get_user_avatar`](Client::get_user_avatar) 相同，但将图片写入 `writer`。
+    pub async fn download_user_avatar<W>(
+        &self,
+        username: &str,
+        image_type: ImageType,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let req = self.image_request(&["v0", "users", username, "avatar"], image_type)?;
+
+        self.execute_download("GET /v0/users/{username}/avatar", req, writer, progress)
+            .await
+    }
+
+    /// 下载 `url` 对应的图片，例如 [`Images`] 中的 URL
+    ///
+    /// 设置了 [`image_url_rewriter`](super::ClientBuilder::image_url_rewriter) 时请求改写后的 URL。
+    pub async fn download_image<W>(
+        &self,
+        url: &str,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let req = self.client.get(&*self.image_url(url)).build()?;
+        self.execute_download("GET {image_url}", req, writer, progress)
+            .await
+    }
+
+    /// 构建 `GET` 图片 API 的请求
+    fn image_request(
+        &self,
+        segments: &[&str],
+        image_type: ImageType,
+    ) -> Result<reqwest::Request, DepsError> {
+        let url = self.api_url(segments)?;
+        Ok(self
+            .client
+            .get(url)
+            .query(&[("type", image_type)])
+            .build()?)
+    }
+
+    /// 发送请求并将响应体逐块写入 `writer`
+    async fn execute_download<W>(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+        writer: &mut W,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        identity_encoding(&mut req);
+        let res = self.execute(endpoint, req).await?;
+        let res = self.check_status(res).await?;
+
+        self.write_body(res, writer, progress).await
+    }
+
+    /// 将响应体逐块写入 `writer`，返回写入的字节数
+    ///
+    /// 响应体超过 [`max_image_size`](Client::max_image_size) 时停止写入并返回 [`DepsError::ResponseTooLarge`]。
+    async fn write_body<W>(
+        &self,
+        mut res: reqwest::Response,
+        writer: &mut W,
+        mut progress: Option<ProgressCallback<'_>>,
+    ) -> Result<u64, DepsError>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let total = res.content_length();
+        let limit = self.max_image_size;
+        if let Some(limit) = limit {
+            check_size(total, limit)?;
+        }
+        let mut downloaded = 0;
+        while let Some(chunk) = res.chunk().await? {
+            self.stats.record_bytes(chunk.len() as u64);
+            if let Some(limit) = limit {
+                check_size(Some(downloaded + chunk.len() as u64), limit)?;
+            }
+            writer.write_all(&chunk).await?;
+            downloaded += chunk.len() as u64;
+            if let Some(progress) = progress.as_mut() {
+                progress(DownloadProgress { downloaded, total });
+            }
+        }
+        writer.flush().await?;
+
+        Ok(downloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    fn images(base_url: &str, name: &str) -> Images {
+        let url = format!("{}/pic/{}.jpg", base_url, name);
+        Images {
+            small: url.clone(),
+            grid: url.clone(),
+            large: url.clone(),
+            medium: url.clone(),
+            common: url,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_images() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "image"),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let cache = MemoryImageCache::default();
+        let cached = images(&server.base_url, "cached");
+        cache.insert(cached.url(ImageType::Common).unwrap(), Bytes::new());
+
+        let a = images(&server.base_url, "a");
+        let b = images(&server.base_url, "b");
+        let report = client
+            .prefetch_images(
+                [&cached, &a, &a, &b],
+                ImageType::Common,
+                &cache,
+                PrefetchOptions::default().concurrency(1),
+            )
+            .await;
+
+        assert_eq!(report.fetched, 1);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_download_image() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "image"),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let mut file = Vec::new();
+        let mut updates = Vec::new();
+        let mut progress = |p: DownloadProgress| updates.push(p);
+        let written = client
+            .download_subject_image(3559, ImageType::Large, &mut file, Some(&mut progress))
+            .await
+            .unwrap();
+        assert_eq!(written, 5);
+        assert_eq!(file, b"image");
+        assert_eq!(
+            updates.last(),
+            Some(&DownloadProgress {
+                downloaded: 5,
+                total: Some(5)
+            })
+        );
+        assert_eq!(
+            server.requests()[0].line,
+            "GET /v0/subjects/3559/image?type=large HTTP/1.1"
+        );
+
+        let mut file = Vec::new();
+        let err = client
+            .download_user_avatar("sai", ImageType::Small, &mut file, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DepsError::Api(_)));
+        assert!(file.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_image_url_rewriter() {
+        let image = "https://lain.bgm.tv/pic/cover/l/f1/1b/3559_rrwkw.jpg";
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                format!(
+                    r#"[{{"id":3559,"type":1,"staff":"主角","name":"","name_cn":"","image":"{}"}}]"#,
+                    image
+                ),
+            ),
+            MockResponse::new(200, "image"),
+        ])
+        .await;
+        let base_url = server.base_url.clone();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .image_url_rewriter(move |url| format!("{}/cdn{}", base_url, url.path()).parse().ok())
+            .build()
+            .unwrap();
+
+        let subjects = client.get_character_subjects(3498).await.unwrap();
+        let rewritten = format!("{}/cdn/pic/cover/l/f1/1b/3559_rrwkw.jpg", server.base_url);
+        assert_eq!(subjects[0].image.as_deref(), Some(rewritten.as_str()));
+
+        let mut file = Vec::new();
+        client.download_image(image, &mut file, None).await.unwrap();
+        assert_eq!(file, b"image");
+        assert_eq!(
+            server.requests()[1].line,
+            "GET /cdn/pic/cover/l/f1/1b/3559_rrwkw.jpg HTTP/1.1"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! # Image Saving (保存图片到文件)
+//!
+//! 启用 `fs` feature 后，[`Client::save_subject_image`] 等方法将图片保存到本地文件，
+//! 支持跳过未变化的图片与断点续传。
+
+use std::path::{Path, PathBuf};
+
+use reqwest::{header, StatusCode};
+use tokio::fs;
+
+use super::super::{identity_encoding, Client};
+use crate::{error::DepsError, types::*};
+
+/// [`Client::save_subject_image`] 等方法的结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SaveOutcome {
+    /// 完整下载了图片，值为写入的字节数
+    Downloaded(u64),
+
+    /// 续传了上次未完成的下载，值为本次写入的字节数
+    Resumed(u64),
+
+    /// 本地文件已是最新，没有下载
+    UpToDate,
+}
+
+/// # Image saving methods for [`Client`].
+///
+/// 这些方法将图片保存到 `path`：先写入 `{path}.part`，下载完成后重命名为 `path`，不会留下写了一半的文件。
+/// 下载中的响应的 `ETag` 保存在 `{path}.part.etag` 中，重命名完成后才写入 `{path}.etag`，
+/// 下载失败不会使 `path` 与新图片的 `ETag` 对应。下次保存时：
+///
+/// - `path` 已存在时通过 `If-None-Match` 询问图片是否变化，未变化时返回 [`SaveOutcome::UpToDate`]；
+///   服务器没有返回 `ETag` 时，`Content-Length` 与本地文件大小相同即视为未变化
+/// - `{path}.part` 存在时通过 `Range` 与 `If-Range` 续传，图片已变化时重新下载
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+impl Client {
+    /// # 保存条目图片 `GET /v0/subjects/{subject_id}/image`
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let outcome = client
+    ///     .save_subject_image(3559, ImageType::Large, "covers/3559.jpg")
+    ///     .await?;
+    /// println!("{:?}", outcome);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn save_subject_image(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        image_type: ImageType,
+        path: impl AsRef<Path>,
+    ) -> Result<SaveOutcome, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let req = self.image_request(
+            &["v0", "subjects", &subject_id.to_string(), "image"],
+            image_type,
+        )?;
+        self.execute_save("GET /v0/subjects/{subject_id}/image", req, path.as_ref())
+            .await
+    }
+
+    /// # 保存角色图片 `GET /v0/characters/{character_id}/image`
+    pub async fn save_character_image(
+        &self,
+        character_id: impl Into<CharacterId>,
+        image_type: ImageType,
+        path: impl AsRef<Path>,
+    ) -> Result<SaveOutcome, DepsError> {
+        let character_id: CharacterId = character_id.into();
+
+        let req = self.image_request(
+            &["v0", "characters", &character_id.to_string(), "image"],
+            image_type,
+        )?;
+        self.execute_save(
+            "GET /v0/characters/{character_id}/image",
+            req,
+            path.as_ref(),
+        )
+        .await
+    }
+
+    /// # 保存人物图片 `GET /v0/persons/{person_id}/image`
+    pub async fn save_person_image(
+        &self,
+        person_id: impl Into<PersonId>,
+        image_type: ImageType,
+        path: impl AsRef<Path>,
+    ) -> Result<SaveOutcome, DepsError> {
+        let person_id: PersonId = person_id.into();
+
+        let req = self.image_request(
+            &["v0", "persons", &person_id.to_string(), "image"],
+            image_type,
+        )?;
+        self.execute_save("GET /v0/persons/{person_id}/image", req, path.as_ref())
+            .await
+    }
+
+    /// 保存 `url` 对应的图片，例如 [`Images`] 中的 URL
+    ///
+    /// 设置了 [`image_url_rewriter`](super::ClientBuilder::image_url_rewriter) 时请求改写后的 URL。
+    pub async fn save_image(
+        &self,
+        url: &str,
+        path: impl AsRef<Path>,
+    ) -> Result<SaveOutcome, DepsError> {
+        let req = self.client.get(&*self.image_url(url)).build()?;
+        self.execute_save("GET {image_url}", req, path.as_ref())
+            .await
+    }
+
+    async fn execute_save(
+        &self,
+        endpoint: &'static str,
+        mut req: reqwest::Request,
+        path: &Path,
+    ) -> Result<SaveOutcome, DepsError> {
+        let part = sidecar(path, "part");
+        let etag_path = sidecar(path, "etag");
+        let part_etag_path = sidecar(path, "part.etag");
+
+        let etag = read_etag(&etag_path).await;
+        let part_etag = read_etag(&part_etag_path).await;
+        let existing = fs::metadata(path).await.ok().map(|meta| meta.len());
+        // 没有 ETag 时无法确认未完成的文件是否对应同一张图片，不续传
+        let mut offset = match part_etag {
+            Some(_) => fs::metadata(&part)
+                .await
+                .map(|meta| meta.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        identity_encoding(&mut req);
+        let fallback = req.try_clone();
+        let headers = req.headers_mut();
+        match (&part_etag, &etag) {
+            (Some(part_etag), _) if offset > 0 => {
+                headers.insert(header::RANGE, format!("bytes={}-", offset).parse()?);
+                headers.insert(header::IF_RANGE, part_etag.parse()?);
+            }
+            (_, Some(etag)) if existing.is_some() => {
+                headers.insert(header::IF_NONE_MATCH, etag.parse()?);
+            }
+            _ => {}
+        }
+
+        let mut res = self.execute(endpoint, req).await?;
+        if res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            if let Some(fallback) = fallback {
+                offset = 0;
+                res = self.execute(endpoint, fallback).await?;
+            }
+        }
+        if res.status() == StatusCode::NOT_MODIFIED {
+            return Ok(SaveOutcome::UpToDate);
+        }
+        let res = self.check_status(res).await?;
+
+        let resumed = offset > 0 && res.status() == StatusCode::PARTIAL_CONTENT;
+        let new_etag = res
+            .headers()
+            .get(header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToString::to_string);
+        if !resumed && existing.is_some() {
+            let unchanged = match (&new_etag, &etag) {
+                (Some(new), Some(old)) => new == old,
+                (Some(_), None) => false,
+                (None, _) => res.content_length() == existing,
+            };
+            if unchanged {
+                return Ok(SaveOutcome::UpToDate);
+            }
+        }
+
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).await?;
+        }
+        let (mut file, new_etag) = if resumed {
+            let file = fs::OpenOptions::new().append(true).open(&part).await?;
+            (file, new_etag.or(part_etag))
+        } else {
+            write_etag(&part_etag_path, new_etag.as_deref()).await?;
+            (fs::File::create(&part).await?, new_etag)
+        };
+
+        let written = self.write_body(res, &mut file, None).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&part, path).await?;
+        write_etag(&etag_path, new_etag.as_deref()).await?;
+        let _ = fs::remove_file(&part_etag_path).await;
+
+        Ok(if resumed {
+            SaveOutcome::Resumed(written)
+        } else {
+            SaveOutcome::Downloaded(written)
+        })
+    }
+}
+
+/// 读取 `ETag` 文件，不存在或为空时返回 `None`
+async fn read_etag(path: &Path) -> Option<String> {
+    let etag = fs::read_to_string(path).await.ok()?;
+    let etag = etag.trim();
+    (!etag.is_empty()).then(|| etag.to_string())
+}
+
+/// 写入 `ETag` 文件，`etag` 为 `None` 时删除文件
+async fn write_etag(path: &Path, etag: Option<&str>) -> Result<(), DepsError> {
+    match etag {
+        Some(etag) => fs::write(path, etag).await?,
+        None => {
+            let _ = fs::remove_file(path).await;
+        }
+    }
+    Ok(())
+}
+
+/// 在 `path` 后追加 `.{ext}`
+fn sidecar(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_save_image() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "image").header("ETag", "\"v1\""),
+            MockResponse::new(304, ""),
+            MockResponse::new(206, "ge").header("ETag", "\"v1\""),
+            MockResponse::new(200, "image"),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("bgmtv-save-{}", std::process::id()));
+        let path = dir.join("3559.jpg");
+        let save = || client.save_subject_image(3559, ImageType::Large, &path);
+
+        assert_eq!(save().await.unwrap(), SaveOutcome::Downloaded(5));
+        assert_eq!(std::fs::read(&path).unwrap(), b"image");
+        assert_eq!(
+            std::fs::read_to_string(sidecar(&path, "etag")).unwrap(),
+            "\"v1\""
+        );
+
+        assert_eq!(save().await.unwrap(), SaveOutcome::UpToDate);
+
+        std::fs::write(sidecar(&path, "part"), "ima").unwrap();
+        std::fs::write(sidecar(&path, "part.etag"), "\"v1\"").unwrap();
+        assert_eq!(save().await.unwrap(), SaveOutcome::Resumed(2));
+        assert_eq!(std::fs::read(&path).unwrap(), b"image");
+        assert!(!sidecar(&path, "part").exists());
+        assert!(!sidecar(&path, "part.etag").exists());
+
+        // 没有 ETag 时按 Content-Length 判断
+        std::fs::remove_file(sidecar(&path, "etag")).unwrap();
+        assert_eq!(save().await.unwrap(), SaveOutcome::UpToDate);
+
+        let requests = server.requests();
+        assert_eq!(requests[1].header("if-none-match"), Some("\"v1\""));
+        assert_eq!(requests[2].header("range"), Some("bytes=3-"));
+        assert_eq!(requests[2].header("if-range"), Some("\"v1\""));
+        assert_eq!(requests[3].header("if-none-match"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_image_failure_keeps_etag() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "img").header("ETag", "\"v1\""),
+            MockResponse::new(200, "image").header("ETag", "\"v2\""),
+            MockResponse::new(200, "img").header("ETag", "\"v3\""),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .max_image_size(4)
+            .build()
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("bgmtv-save-fail-{}", std::process::id()));
+        let path = dir.join("3559.jpg");
+        let save = || client.save_subject_image(3559, ImageType::Large, &path);
+
+        assert_eq!(save().await.unwrap(), SaveOutcome::Downloaded(3));
+        assert!(matches!(
+            save().await,
+            Err(DepsError::ResponseTooLarge { .. })
+        ));
+        assert_eq!(
+            std::fs::read_to_string(sidecar(&path, "etag")).unwrap(),
+            "\"v1\""
+        );
+
+        // 未完成的文件丢失后，仍然以原图片的 ETag 询问
+        std::fs::remove_file(sidecar(&path, "part")).unwrap();
+        assert_eq!(save().await.unwrap(), SaveOutcome::Downloaded(3));
+        assert_eq!(server.requests()[2].header("if-none-match"), Some("\"v1\""));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
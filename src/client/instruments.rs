@@ -0,0 +1,96 @@
+//! # Metrics (指标)
+//!
+//! 启用 `metrics` feature 后，每次发送请求都会通过 [`metrics`] crate 记录以下指标，
+//! 可以配合 `metrics-exporter-prometheus` 等 exporter 导出：
+//!
+//! - `bgmtv_requests_total`：请求数，标签为 `endpoint` 与 `status`
+//! - `bgmtv_request_errors_total`：失败的请求数，标签为 `endpoint` 与 `kind` (`network` 或 `status`)
+//! - `bgmtv_request_duration_seconds`：请求耗时的直方图，标签为 `endpoint`
+
+use std::time::Duration;
+
+use metrics::{counter, histogram};
+
+use crate::error::DepsError;
+
+/// 记录收到的响应
+pub(crate) fn response(endpoint: &'static str, res: &reqwest::Response, elapsed: Duration) {
+    let status = res.status();
+    counter!("bgmtv_requests_total", "endpoint" => endpoint, "status" => status.as_str().to_string())
+        .increment(1);
+    histogram!("bgmtv_request_duration_seconds", "endpoint" => endpoint)
+        .record(elapsed.as_secs_f64());
+    if status.is_client_error() || status.is_server_error() {
+        counter!("bgmtv_request_errors_total", "endpoint" => endpoint, "kind" => "status")
+            .increment(1);
+    }
+}
+
+/// 记录未收到响应的请求
+pub(crate) fn error(endpoint: &'static str, _err: &DepsError) {
+    counter!("bgmtv_requests_total", "endpoint" => endpoint, "status" => "error").increment(1);
+    counter!("bgmtv_request_errors_total", "endpoint" => endpoint, "kind" => "network")
+        .increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use crate::{
+        client::Client,
+        mock::{MockResponse, MockServer},
+    };
+
+    #[test]
+    fn test_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    let server = MockServer::start(vec![MockResponse::new(
+                        404,
+                        r#"{"title":"Not Found","description":""}"#,
+                    )])
+                    .await;
+                    let client = Client::builder()
+                        .base_url(server.base_url.clone())
+                        .build()
+                        .unwrap();
+                    assert!(client.get_subject(1).await.is_err());
+                })
+        });
+
+        let metrics = snapshotter.snapshot().into_vec();
+        let value = |name: &str| {
+            metrics
+                .iter()
+                .find(|(key, ..)| key.key().name() == name)
+                .map(|(key, _, _, value)| {
+                    let labels: Vec<_> = key.key().labels().cloned().collect();
+                    (labels, value)
+                })
+                .unwrap()
+        };
+
+        let (labels, count) = value("bgmtv_requests_total");
+        assert_eq!(count, &DebugValue::Counter(1));
+        assert!(labels
+            .iter()
+            .any(|l| l.key() == "status" && l.value() == "404"));
+
+        let (labels, count) = value("bgmtv_request_errors_total");
+        assert_eq!(count, &DebugValue::Counter(1));
+        assert!(labels
+            .iter()
+            .any(|l| l.key() == "kind" && l.value() == "status"));
+
+        let (_, durations) = value("bgmtv_request_duration_seconds");
+        assert!(matches!(durations, DebugValue::Histogram(v) if v.len() == 1));
+    }
+}
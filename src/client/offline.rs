@@ -0,0 +1,618 @@
+//! # Offline Snapshots (离线快照)
+//!
+//! 通过 [`ClientBuilder::snapshot_store`](super::ClientBuilder::snapshot_store) 设置 [`SnapshotStore`] 后，
+//! [`Client::with_snapshot`] 会把成功获取的条目、章节、收藏等数据持久化到本地；
+//! 离线或 API 出错时改为返回本地保存的数据，并通过 [`Snapshot`] 告知调用方数据的获取时间。
+//!
+//! [`Client::sync_library`] 基于快照存储实现了用户收藏的增量同步，只获取上次同步后修改过的收藏。
+//!
+//! 本模块提供了内存中的 [`MemorySnapshotStore`] 与基于文件系统的 [`FileSnapshotStore`]，
+//! 可以为 SQLite 等存储实现 [`SnapshotStore`]。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::offline::FileSnapshotStore;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder()
+//!     .snapshot_store(FileSnapshotStore::new("./bgmtv-snapshots")?)
+//!     .build()?;
+//!
+//! let subject = client.get_subject_snapshot(3559).await?;
+//! if subject.is_stored() {
+//!     println!("offline, data fetched {:?} ago", subject.age());
+//! }
+//! println!("{}", subject.name);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    future::Future,
+    io,
+    ops::Deref,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures_util::TryStreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::Client;
+use crate::{error::*, time::parse_rfc3339, types::*};
+
+/// 保存在 [`SnapshotStore`] 中的原始数据
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredSnapshot {
+    /// 序列化为 JSON 的数据
+    pub body: Vec<u8>,
+
+    /// 数据的获取时间
+    pub fetched_at: SystemTime,
+}
+
+/// Snapshot store (快照存储)
+///
+/// 以 [`Client::with_snapshot`] 的 `key` 为键保存数据。读写失败时应当静默忽略，不影响正常的请求。
+pub trait SnapshotStore: Send + Sync {
+    /// 读取 `key` 对应的快照
+    fn load(&self, key: &str) -> Option<StoredSnapshot>;
+
+    /// 保存 `key` 对应的快照
+    fn save(&self, key: &str, snapshot: StoredSnapshot);
+}
+
+/// 内存中的快照存储，进程退出后丢失，主要用于测试
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore {
+    snapshots: Mutex<HashMap<String, StoredSnapshot>>,
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn load(&self, key: &str) -> Option<StoredSnapshot> {
+        self.snapshots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn save(&self, key: &str, snapshot: StoredSnapshot) {
+        self.snapshots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), snapshot);
+    }
+}
+
+/// 基于文件系统的快照存储
+///
+/// 每个快照保存为目录下的一个文件：第一行为获取时间的 Unix 毫秒时间戳，其余为 JSON 数据。
+/// 文件名为 `key` 的十六进制编码。
+#[derive(Clone, Debug)]
+pub struct FileSnapshotStore {
+    dir: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// 使用 `dir` 目录保存快照，目录不存在时会被创建
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key.bytes().map(|b| format!("{:02x}", b)).collect();
+        self.dir.join(name)
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn load(&self, key: &str) -> Option<StoredSnapshot> {
+        let content = fs::read(self.path(key)).ok()?;
+        let newline = content.iter().position(|&b| b == b'\n')?;
+        let millis: u64 = std::str::from_utf8(&content[..newline])
+            .ok()?
+            .parse()
+            .ok()?;
+
+        Some(StoredSnapshot {
+            body: content[newline + 1..].to_vec(),
+            fetched_at: UNIX_EPOCH + Duration::from_millis(millis),
+        })
+    }
+
+    fn save(&self, key: &str, snapshot: StoredSnapshot) {
+        let millis = snapshot
+            .fetched_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut content = format!("{}\n", millis).into_bytes();
+        content.extend(snapshot.body);
+
+        // 先写入临时文件再重命名，避免读到写了一半的快照
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        let _ = fs::write(&tmp, content).and_then(|_| fs::rename(&tmp, &path));
+    }
+}
+
+impl<S: SnapshotStore + ?Sized> SnapshotStore for Arc<S> {
+    fn load(&self, key: &str) -> Option<StoredSnapshot> {
+        (**self).load(key)
+    }
+
+    fn save(&self, key: &str, snapshot: StoredSnapshot) {
+        (**self).save(key, snapshot)
+    }
+}
+
+/// 共享的 [`SnapshotStore`]
+#[derive(Clone)]
+pub(crate) struct SharedSnapshotStore(pub(crate) Arc<dyn SnapshotStore>);
+
+impl fmt::Debug for SharedSnapshotStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedSnapshotStore")
+    }
+}
+
+/// 数据的来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// 刚从 API 获取
+    Live,
+
+    /// API 请求失败，来自 [`SnapshotStore`]
+    Stored,
+}
+
+/// 带有获取时间的数据
+///
+/// 实现了 [`Deref`]，可以直接访问内部的数据。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot<T> {
+    /// 数据
+    pub value: T,
+
+    /// 数据的获取时间
+    pub fetched_at: SystemTime,
+
+    /// 数据的来源
+    pub source: SnapshotSource,
+}
+
+impl<T> Snapshot<T> {
+    /// 数据是否来自本地的 [`SnapshotStore`]，即可能已经过时
+    pub fn is_stored(&self) -> bool {
+        self.source == SnapshotSource::Stored
+    }
+
+    /// 数据获取至今经过的时间
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed().unwrap_or_default()
+    }
+
+    /// 取出内部的数据
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Snapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// [`Client::sync_library`] 的结果
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct LibrarySync {
+    /// 上次同步后新增或修改的收藏，按最后修改时间从新到旧排列
+    pub updated: Vec<UserSubjectCollection>,
+
+    /// 是否为首次同步，即获取了全部收藏
+    pub full: bool,
+}
+
+/// [`Client::sync_library`] 的同步位置
+#[derive(Debug, Serialize, Deserialize)]
+struct LibraryMarker {
+    /// 已同步的收藏中最新的修改时间
+    updated_at: String,
+
+    /// 修改时间为 `updated_at` 的已同步收藏
+    subject_ids: Vec<u64>,
+}
+
+impl LibraryMarker {
+    /// 解析保存的同步位置，兼容旧版本只保存了修改时间的格式
+    fn parse(body: &[u8]) -> Option<Self> {
+        serde_json::from_slice(body).ok().or_else(|| {
+            let updated_at = std::str::from_utf8(body).ok()?;
+            Some(Self {
+                updated_at: updated_at.to_owned(),
+                subject_ids: Vec::new(),
+            })
+        })
+    }
+}
+
+impl LibrarySync {
+    /// 发生变化的条目 ID
+    pub fn subject_ids(&self) -> Vec<u64> {
+        self.updated.iter().map(|c| c.subject_id).collect()
+    }
+}
+
+/// # Offline snapshot methods for [`Client`].
+impl Client {
+    /// 获取数据并保存快照，失败时返回本地保存的快照
+    ///
+    /// `fetch` 成功时将结果以 `key` 为键保存到 [`SnapshotStore`]；失败时如果存在 `key` 对应的快照，
+    /// 返回来源为 [`SnapshotSource::Stored`] 的快照，否则返回原本的错误。未设置 [`SnapshotStore`] 时只是简单地包装结果。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let episodes = client
+    ///     .with_snapshot("episodes/3559", client.get_episodes(3559).send())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_snapshot<T, E>(
+        &self,
+        key: &str,
+        fetch: impl Future<Output = Result<T, E>>,
+    ) -> Result<Snapshot<T>, E>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let result = fetch.await;
+        let Some(store) = &self.snapshot_store else {
+            return result.map(|value| Snapshot {
+                value,
+                fetched_at: SystemTime::now(),
+                source: SnapshotSource::Live,
+            });
+        };
+
+        match result {
+            Ok(value) => {
+                let fetched_at = SystemTime::now();
+                if let Ok(body) = serde_json::to_vec(&value) {
+                    store.0.save(key, StoredSnapshot { body, fetched_at });
+                }
+                Ok(Snapshot {
+                    value,
+                    fetched_at,
+                    source: SnapshotSource::Live,
+                })
+            }
+            Err(err) => store
+                .0
+                .load(key)
+                .and_then(|stored| {
+                    let value = serde_json::from_slice(&stored.body).ok()?;
+                    Some(Snapshot {
+                        value,
+                        fetched_at: stored.fetched_at,
+                        source: SnapshotSource::Stored,
+                    })
+                })
+                .ok_or(err),
+        }
+    }
+
+    /// 获取条目，失败时返回本地保存的快照，参见 [`with_snapshot`](Self::with_snapshot)
+    pub async fn get_subject_snapshot(
+        &self,
+        subject_id: impl Into<SubjectId>,
+    ) -> Result<Snapshot<Subject>, DepsError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        self.with_snapshot(
+            &format!("subjects/{}", subject_id),
+            self.get_subject(subject_id),
+        )
+        .await
+    }
+
+    /// 增量同步用户收藏
+    ///
+    /// 收藏列表按最后修改时间从新到旧排列，此方法逐页获取收藏，遇到早于上次同步时最新的 `updated_at`
+    /// 时停止，因此启动时通常只需要一次请求。与上次同步时最新的 `updated_at` 相同的收藏按条目 ID 去重。每条变化的收藏以 `collections/{username}/{subject_id}` 为键保存到
+    /// [`SnapshotStore`]，同步位置保存在 `library/{username}` 中。
+    ///
+    /// 未设置 [`SnapshotStore`] 时每次都会获取全部收藏。同步中途出错时不会更新同步位置，下次同步会重新获取。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// use bgmtv::client::offline::FileSnapshotStore;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::builder()
+    ///     .snapshot_store(FileSnapshotStore::new("./bgmtv-snapshots")?)
+    ///     .build()?;
+    ///
+    /// let sync = client.sync_library("sai").await?;
+    /// println!("{} collections changed", sync.updated.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sync_library(
+        &self,
+        username: &str,
+    ) -> Result<LibrarySync, GetUserCollectionsError> {
+        let marker_key = format!("library/{}", username);
+        let marker = self
+            .snapshot_store
+            .as_ref()
+            .and_then(|store| store.0.load(&marker_key))
+            .and_then(|stored| LibraryMarker::parse(&stored.body));
+        let since = marker
+            .as_ref()
+            .and_then(|marker| parse_rfc3339(&marker.updated_at));
+
+        let mut updated = Vec::new();
+        let mut items = std::pin::pin!(self
+            .get_user_collections(username)
+            .limit(50)
+            .build()?
+            .items());
+        while let Some(collection) = items.try_next().await? {
+            let changed_at = parse_rfc3339(&collection.updated_at);
+            match (since, changed_at) {
+                // 修改时间相同的收藏可能在上次同步之后才出现，只跳过上次已经同步过的
+                (Some(since), Some(at)) if at < since => break,
+                (Some(since), Some(at))
+                    if at == since
+                        && marker
+                            .as_ref()
+                            .is_some_and(|m| m.subject_ids.contains(&collection.subject_id)) =>
+                {
+                    continue
+                }
+                _ => updated.push(collection),
+            }
+        }
+
+        if let Some(store) = &self.snapshot_store {
+            let fetched_at = SystemTime::now();
+            for collection in &updated {
+                if let Ok(body) = serde_json::to_vec(collection) {
+                    let key = format!("collections/{}/{}", username, collection.subject_id);
+                    store.0.save(&key, StoredSnapshot { body, fetched_at });
+                }
+            }
+            if let Some(latest) = updated.first() {
+                let latest_at = parse_rfc3339(&latest.updated_at);
+                let mut subject_ids: Vec<u64> = updated
+                    .iter()
+                    .filter(|c| parse_rfc3339(&c.updated_at) == latest_at)
+                    .map(|c| c.subject_id)
+                    .collect();
+                if let Some(marker) = marker.filter(|_| latest_at.is_some() && latest_at == since) {
+                    subject_ids.extend(marker.subject_ids);
+                }
+                let marker = LibraryMarker {
+                    updated_at: latest.updated_at.clone(),
+                    subject_ids,
+                };
+                if let Ok(body) = serde_json::to_vec(&marker) {
+                    store
+                        .0
+                        .save(&marker_key, StoredSnapshot { body, fetched_at });
+                }
+            }
+        }
+
+        Ok(LibrarySync {
+            updated,
+            full: since.is_none(),
+        })
+    }
+
+    /// 获取章节，失败时返回本地保存的快照，参见 [`with_snapshot`](Self::with_snapshot)
+    pub async fn get_episode_snapshot(
+        &self,
+        episode_id: impl Into<EpisodeId>,
+    ) -> Result<Snapshot<Episode>, DepsError> {
+        let episode_id: EpisodeId = episode_id.into();
+
+        self.with_snapshot(
+            &format!("episodes/{}", episode_id),
+            self.get_episode(episode_id),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    fn episode() -> String {
+        let episode = Episode {
+            name: "ep".to_string(),
+            ..crate::mock::episode(1)
+        };
+        serde_json::to_string(&episode).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_with_snapshot() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, episode()),
+            MockResponse::new(503, "Service Unavailable"),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .snapshot_store(MemorySnapshotStore::default())
+            .build()
+            .unwrap();
+
+        let live = client.get_episode_snapshot(1).await.unwrap();
+        assert_eq!(live.source, SnapshotSource::Live);
+
+        let stored = client.get_episode_snapshot(1).await.unwrap();
+        assert!(stored.is_stored());
+        assert_eq!(stored.name, "ep");
+        assert_eq!(stored.fetched_at, live.fetched_at);
+
+        assert!(client.get_episode_snapshot(2).await.is_err());
+    }
+
+    fn collections(items: &[(u64, &str)]) -> String {
+        let data: Vec<_> = items
+            .iter()
+            .map(|(id, updated_at)| {
+                format!(
+                    r#"{{"subject_id":{},"subject_type":2,"rate":0,"type":2,"comment":null,"tags":[],"ep_status":0,"vol_status":0,"updated_at":"{}","private":false}}"#,
+                    id, updated_at
+                )
+            })
+            .collect();
+        format!(
+            r#"{{"total":{},"limit":50,"offset":0,"data":[{}]}}"#,
+            items.len(),
+            data.join(",")
+        )
+    }
+
+    #[tokio::test]
+    async fn test_sync_library() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                collections(&[
+                    (2, "2024-01-02T00:00:00+08:00"),
+                    (1, "2024-01-01T00:00:00+08:00"),
+                ]),
+            ),
+            MockResponse::new(
+                200,
+                collections(&[
+                    (3, "2024-01-03T00:00:00+08:00"),
+                    (2, "2024-01-02T00:00:00+08:00"),
+                    (1, "2024-01-01T00:00:00+08:00"),
+                ]),
+            ),
+        ])
+        .await;
+        let store = std::sync::Arc::new(MemorySnapshotStore::default());
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .snapshot_store(store.clone())
+            .build()
+            .unwrap();
+
+        let sync = client.sync_library("sai").await.unwrap();
+        assert!(sync.full);
+        assert_eq!(sync.subject_ids(), vec![2, 1]);
+        assert!(store.load("collections/sai/1").is_some());
+
+        let sync = client.sync_library("sai").await.unwrap();
+        assert!(!sync.full);
+        assert_eq!(sync.subject_ids(), vec![3]);
+        assert_eq!(
+            store.load("library/sai").unwrap().body,
+            br#"{"updated_at":"2024-01-03T00:00:00+08:00","subject_ids":[3]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_library_same_updated_at() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                collections(&[
+                    (2, "2024-01-02T00:00:00+08:00"),
+                    (1, "2024-01-02T00:00:00+08:00"),
+                ]),
+            ),
+            MockResponse::new(
+                200,
+                collections(&[
+                    (3, "2024-01-02T00:00:00+08:00"),
+                    (2, "2024-01-02T00:00:00+08:00"),
+                    (1, "2024-01-02T00:00:00+08:00"),
+                ]),
+            ),
+            MockResponse::new(
+                200,
+                collections(&[
+                    (3, "2024-01-02T00:00:00+08:00"),
+                    (2, "2024-01-02T00:00:00+08:00"),
+                    (1, "2024-01-02T00:00:00+08:00"),
+                ]),
+            ),
+        ])
+        .await;
+        let store = std::sync::Arc::new(MemorySnapshotStore::default());
+        // 旧版本只保存了修改时间
+        store.save(
+            "library/sai",
+            StoredSnapshot {
+                body: b"2024-01-01T00:00:00+08:00".to_vec(),
+                fetched_at: UNIX_EPOCH,
+            },
+        );
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .snapshot_store(store.clone())
+            .build()
+            .unwrap();
+
+        let sync = client.sync_library("sai").await.unwrap();
+        assert!(!sync.full);
+        assert_eq!(sync.subject_ids(), vec![2, 1]);
+
+        let sync = client.sync_library("sai").await.unwrap();
+        assert_eq!(sync.subject_ids(), vec![3]);
+        assert_eq!(
+            store.load("library/sai").unwrap().body,
+            br#"{"updated_at":"2024-01-02T00:00:00+08:00","subject_ids":[3,2,1]}"#
+        );
+
+        let sync = client.sync_library("sai").await.unwrap();
+        assert!(sync.updated.is_empty());
+    }
+
+    #[test]
+    fn test_file_snapshot_store() {
+        let dir = std::env::temp_dir().join(format!("bgmtv-snapshots-{}", std::process::id()));
+        let store = FileSnapshotStore::new(&dir).unwrap();
+        let snapshot = StoredSnapshot {
+            body: episode().into_bytes(),
+            fetched_at: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        };
+
+        assert_eq!(store.load("episodes/1"), None);
+        store.save("episodes/1", snapshot.clone());
+        assert_eq!(store.load("episodes/1"), Some(snapshot));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
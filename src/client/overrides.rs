@@ -0,0 +1,199 @@
+//! # Endpoint Overrides (按 API 类别覆盖配置)
+//!
+//! 图片下载与 JSON API 的延迟与失败模式差别很大，可以通过 [`ClientBuilder::endpoint_override`](super::ClientBuilder::endpoint_override)
+//! 为某一类 API ([`EndpointClass`]) 单独设置超时时间、重试策略与限流权重，未设置的项沿用 [`Client`](super::Client) 的全局配置。
+//!
+//! ## Example
+//!
+//! ```
+//! # use bgmtv::prelude::*;
+//! use std::time::Duration;
+//!
+//! use bgmtv::client::{
+//!     overrides::{EndpointClass, EndpointOverride},
+//!     retry::RetryPolicy,
+//!     scheduler::RateLimit,
+//! };
+//!
+//! let client = Client::builder()
+//!     .timeout(Duration::from_secs(10))
+//!     .rate_limit(RateLimit::new(4, Duration::from_secs(1)))
+//!     .endpoint_override(
+//!         EndpointClass::Images,
+//!         EndpointOverride::default()
+//!             .timeout(Duration::from_secs(60))
+//!             .rate_weight(2),
+//!     )
+//!     .endpoint_override(
+//!         EndpointClass::Writes,
+//!         EndpointOverride::default().retry(RetryPolicy::default().max_retries(0)),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{collections::HashMap, time::Duration};
+
+use super::retry::RetryPolicy;
+
+/// Endpoint class (API 类别)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EndpointClass {
+    /// 返回 JSON 的查询类 API，例如获取条目、章节
+    Read,
+
+    /// 搜索类 API，例如 `POST /v0/search/subjects`
+    Search,
+
+    /// 返回图片的 API，以及下载 [`Images`](crate::types::Images) 中的图片
+    Images,
+
+    /// 修改类 API，例如修改收藏
+    Writes,
+}
+
+impl EndpointClass {
+    /// 根据 API 名称 (例如 `GET /v0/subjects/{subject_id}`) 判断所属的类别
+    pub fn of(endpoint: &str) -> Self {
+        let (method, path) = endpoint.split_once(' ').unwrap_or(("GET", endpoint));
+        if path.starts_with("/v0/search/") {
+            Self::Search
+        } else if path == "{image_url}" || path.ends_with("/image") || path.ends_with("/avatar") {
+            Self::Images
+        } else if method != "GET" && !path.starts_with("/oauth/") {
+            Self::Writes
+        } else {
+            Self::Read
+        }
+    }
+}
+
+/// Endpoint override (一类 API 的覆盖配置)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EndpointOverride {
+    /// 请求的总超时时间，覆盖 [`ClientBuilder::timeout`](super::ClientBuilder::timeout)
+    pub timeout: Option<Duration>,
+
+    /// 重试策略，覆盖 [`ClientBuilder::retry`](super::ClientBuilder::retry)；
+    /// 设置为 `max_retries(0)` 可以为这类 API 关闭重试
+    pub retry: Option<RetryPolicy>,
+
+    /// 每个请求消耗的限流令牌数，默认为 1，为 0 时不受限流影响；超过 [`RateLimit::requests`](super::scheduler::RateLimit::requests) 时按其计算
+    pub rate_weight: Option<u32>,
+}
+
+impl EndpointOverride {
+    /// 设置请求的总超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置重试策略
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// 设置每个请求消耗的限流令牌数
+    pub fn rate_weight(mut self, rate_weight: u32) -> Self {
+        self.rate_weight = Some(rate_weight);
+        self
+    }
+}
+
+/// 各类 API 的覆盖配置
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EndpointOverrides(pub(crate) HashMap<EndpointClass, EndpointOverride>);
+
+impl EndpointOverrides {
+    /// `endpoint` 所属类别的覆盖配置
+    pub(crate) fn get(&self, endpoint: &str) -> EndpointOverride {
+        self.0
+            .get(&EndpointClass::of(endpoint))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::{scheduler::RateLimit, Client},
+        error::DepsError,
+        mock::{MockResponse, MockServer},
+        time::ManualClock,
+        types::ImageType,
+    };
+
+    #[test]
+    fn test_endpoint_class() {
+        use EndpointClass::*;
+
+        assert_eq!(EndpointClass::of("GET /v0/subjects/{subject_id}"), Read);
+        assert_eq!(EndpointClass::of("POST /v0/search/subjects"), Search);
+        assert_eq!(
+            EndpointClass::of("GET /v0/subjects/{subject_id}/image"),
+            Images
+        );
+        assert_eq!(EndpointClass::of("GET /v0/users/{username}/avatar"), Images);
+        assert_eq!(EndpointClass::of("GET {image_url}"), Images);
+        assert_eq!(
+            EndpointClass::of("PATCH /v0/users/-/collections/{subject_id}"),
+            Writes
+        );
+        assert_eq!(EndpointClass::of("POST /oauth/token_status"), Read);
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_override() {
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "").header("Retry-After", "1"),
+            MockResponse::new(429, "").header("Retry-After", "1"),
+            MockResponse::new(200, "image"),
+        ])
+        .await;
+        let clock = ManualClock::default();
+        let timeouts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = timeouts.clone();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock.clone())
+            .retry(RetryPolicy::default())
+            .rate_limit(RateLimit::new(1, Duration::from_secs(10)))
+            .endpoint_override(
+                EndpointClass::Read,
+                EndpointOverride::default().retry(RetryPolicy::default().max_retries(0)),
+            )
+            .endpoint_override(
+                EndpointClass::Images,
+                EndpointOverride::default()
+                    .timeout(Duration::from_secs(60))
+                    .rate_weight(0),
+            )
+            .on_request(move |_, req| recorded.lock().unwrap().push(req.timeout().copied()))
+            .build()
+            .unwrap();
+
+        let err = client.get_subject(3559).await.unwrap_err();
+        assert!(matches!(err, DepsError::RateLimited { .. }));
+
+        // 图片请求沿用全局的重试策略，且不消耗限流令牌
+        let image = client
+            .get_subject_image(3559, ImageType::Small)
+            .await
+            .unwrap();
+        assert_eq!(&image[..], b"image");
+        assert_eq!(clock.slept(), vec![Duration::from_secs(1)]);
+        assert_eq!(
+            *timeouts.lock().unwrap(),
+            vec![
+                None,
+                Some(Duration::from_secs(60)),
+                Some(Duration::from_secs(60))
+            ]
+        );
+    }
+}
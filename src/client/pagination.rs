@@ -0,0 +1,393 @@
+//! # Pagination (分页)
+//!
+//! 此模块为分页的执行器提供了 [`Stream`] 适配器，按需依次获取后续的分页，避免手写 offset 循环。
+//!
+//! 实现了 [`Paginated`] 的执行器都提供了 `pages()` 与 `items()` 方法：
+//!
+//! - `pages()` 返回逐页的 [`Stream`]
+//! - `items()` 返回逐条数据的 [`Stream`]
+//! - `fetch_all()` 获取全部数据并返回 [`Vec`]，可以通过 [`FetchAllOptions`] 限制数量与设置分页间的等待时间
+//!
+//! 执行器的 `timeout_per_page` 为上述方法中的每一页设置超时，超时时返回
+//! [`DepsError::Timeout`](crate::error::DepsError::Timeout)。超时或提前丢弃 [`Stream`] 都是取消安全的：
+//! 响应缓存只会在完整读取响应体后写入，不会留下写了一半的缓存项，之后可以从最后一页的偏移量重新开始。
+//!
+//! [`Page`] 与 [`Paginated`] 是密封的 trait，只能由本 crate 中的类型实现。
+
+use std::{future::Future, time::Duration};
+
+use futures_core::Stream;
+use futures_util::{
+    future::{self, Either},
+    stream, TryStreamExt,
+};
+
+use crate::{error::DepsError, sealed::Sealed, time::Clock, types::*};
+
+/// 分页结果
+pub trait Page: Sealed {
+    /// 分页数据的类型
+    type Item;
+
+    /// 数据总数
+    fn total(&self) -> u64;
+
+    /// 当前分页的偏移量
+    fn offset(&self) -> u64;
+
+    /// 当前分页的数据数量
+    fn len(&self) -> usize;
+
+    /// 当前分页是否为空
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 下一页的偏移量
+    fn next_offset(&self) -> u64 {
+        self.offset() + self.len() as u64
+    }
+
+    /// 是否还有下一页
+    fn has_next(&self) -> bool {
+        !self.is_empty() && self.next_offset() < self.total()
+    }
+
+    /// 取出当前分页的数据
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl<T> Sealed for Paged<T> {}
+
+impl<T> Page for Paged<T> {
+    type Item = T;
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+
+    fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.data
+    }
+}
+
+/// 分页的执行器
+pub trait Paginated: Sealed + Clone + Send + Sync {
+    /// 分页结果的类型
+    type Page: Page + Send;
+
+    /// 错误类型
+    type Error: Send + From<DepsError>;
+
+    /// 设置偏移量
+    #[doc(hidden)]
+    fn set_offset(&mut self, offset: u64);
+
+    /// 每一页的超时时间
+    #[doc(hidden)]
+    fn timeout_per_page(&self) -> Option<Duration>;
+
+    /// 用于计时的 [`Clock`]
+    #[doc(hidden)]
+    fn clock(&self) -> &dyn Clock;
+
+    /// 获取当前偏移量对应的分页
+    #[doc(hidden)]
+    fn fetch_page(&self) -> impl Future<Output = Result<Self::Page, Self::Error>> + Send;
+}
+
+/// 获取 `executor` 当前偏移量对应的分页，超过 [`timeout_per_page`](Paginated::timeout_per_page) 时返回
+/// [`DepsError::Timeout`]
+pub(crate) async fn fetch_page<E: Paginated>(executor: &E) -> Result<E::Page, E::Error> {
+    let Some(timeout) = executor.timeout_per_page() else {
+        return executor.fetch_page().await;
+    };
+
+    let fetch = std::pin::pin!(executor.fetch_page());
+    match future::select(fetch, executor.clock().sleep(timeout)).await {
+        Either::Left((page, _)) => page,
+        Either::Right(_) => Err(DepsError::Timeout { timeout }.into()),
+    }
+}
+
+/// 从 `executor` 当前的偏移量开始，逐页获取数据
+pub(crate) fn pages<E: Paginated>(
+    executor: E,
+) -> impl Stream<Item = Result<E::Page, E::Error>> + Send {
+    stream::try_unfold(Some(executor), |state| async move {
+        let Some(executor) = state else {
+            return Ok(None);
+        };
+
+        let page = fetch_page(&executor).await?;
+
+        let next_offset = page.next_offset();
+        let next = page.has_next().then(|| {
+            let mut executor = executor.clone();
+            executor.set_offset(next_offset);
+            executor
+        });
+
+        Ok(Some((page, next)))
+    })
+}
+
+/// 从 `executor` 当前的偏移量开始，逐条获取数据
+pub(crate) fn items<E: Paginated>(
+    executor: E,
+) -> impl Stream<Item = Result<<E::Page as Page>::Item, E::Error>> + Send
+where
+    <E::Page as Page>::Item: Send,
+{
+    pages(executor)
+        .map_ok(|page| stream::iter(page.into_items().into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// [`fetch_all`](crate::client::episodes::GetEpisodesExecutor::fetch_all) 的选项
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FetchAllOptions {
+    /// 最多获取的数据数量，默认不限制
+    pub max_items: Option<usize>,
+
+    /// 每两次分页请求之间的等待时间，默认不等待
+    pub delay: Option<Duration>,
+}
+
+impl FetchAllOptions {
+    /// 设置最多获取的数据数量
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// 设置每两次分页请求之间的等待时间
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// 分页参数，用于 [`get_subject_persons_page`](crate::client::Client::get_subject_persons_page) 等
+/// 尚未在服务端分页的 API
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PageOptions {
+    /// 返回数量，默认返回全部
+    pub limit: Option<u64>,
+
+    /// 偏移量，默认为 0
+    pub offset: Option<u64>,
+}
+
+impl PageOptions {
+    /// 设置返回数量
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 设置偏移量
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// 从 `executor` 当前的偏移量开始，获取全部数据
+pub(crate) async fn fetch_all<E: Paginated>(
+    mut executor: E,
+    clock: &dyn Clock,
+    options: FetchAllOptions,
+) -> Result<Vec<<E::Page as Page>::Item>, E::Error> {
+    let max_items = options.max_items.unwrap_or(usize::MAX);
+    let mut items = Vec::new();
+
+    while items.len() < max_items {
+        if !items.is_empty() {
+            if let Some(delay) = options.delay {
+                clock.sleep(delay).await;
+            }
+        }
+
+        let page = fetch_page(&executor).await?;
+
+        let next_offset = page.next_offset();
+        let has_next = page.has_next();
+        items.extend(page.into_items());
+        if !has_next {
+            break;
+        }
+        executor.set_offset(next_offset);
+    }
+
+    items.truncate(max_items);
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::TryStreamExt;
+
+    use super::FetchAllOptions;
+    use crate::{
+        client::{
+            transport::{HttpTransport, TransportFuture},
+            Client,
+        },
+        error::GetEpisodesError,
+        mock::{MockResponse, MockServer},
+        time::ManualClock,
+    };
+
+    fn episode(id: u64) -> String {
+        serde_json::to_string(&crate::mock::episode(id)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_items() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"total":3,"limit":2,"offset":0,"data":[{},{}]}}"#,
+                    episode(1),
+                    episode(2)
+                ),
+            ),
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"total":3,"limit":2,"offset":2,"data":[{}]}}"#,
+                    episode(3)
+                ),
+            ),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let episodes: Vec<_> = client
+            .get_episodes(1)
+            .limit(2)
+            .build()
+            .unwrap()
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            episodes.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].line.contains("offset=2"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all() {
+        let page = |offset: u64| {
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"total":5,"limit":2,"offset":{},"data":[{},{}]}}"#,
+                    offset,
+                    episode(offset + 1),
+                    episode(offset + 2)
+                ),
+            )
+        };
+        let server = MockServer::start(vec![page(0), page(2), page(4)]).await;
+
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        let episodes = client
+            .get_episodes(1)
+            .limit(2)
+            .build()
+            .unwrap()
+            .fetch_all_with(
+                FetchAllOptions::default()
+                    .max_items(3)
+                    .delay(Duration::from_secs(1)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            episodes.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(clock.slept(), vec![Duration::from_secs(1)]);
+    }
+
+    /// 第一次请求永不返回，之后立即返回同一页的传输层
+    struct Slow(std::sync::atomic::AtomicUsize, String);
+
+    impl HttpTransport for Slow {
+        fn execute(&self, _req: reqwest::Request) -> TransportFuture<'_> {
+            let first = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0;
+            let body = self.1.clone();
+            Box::pin(async move {
+                if first {
+                    std::future::pending::<()>().await;
+                }
+                let res = http::Response::builder().status(200).body(body).unwrap();
+                Ok(reqwest::Response::from(res))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_per_page() {
+        let body = format!(
+            r#"{{"total":1,"limit":2,"offset":0,"data":[{}]}}"#,
+            episode(1)
+        );
+        let clock = ManualClock::new();
+        let client = Client::builder()
+            .transport(Slow(Default::default(), body))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let executor = client
+            .get_episodes(1)
+            .timeout_per_page(Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        let err = executor.fetch_all().await.unwrap_err();
+        assert!(matches!(
+            err,
+            GetEpisodesError::Timeout { timeout } if timeout == Duration::from_millis(50)
+        ));
+
+        assert_eq!(clock.slept(), vec![Duration::from_millis(50)]);
+
+        let episodes: Vec<_> = executor.items().try_collect().await.unwrap();
+        assert_eq!(episodes[0].id, 1);
+    }
+}
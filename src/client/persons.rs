@@ -0,0 +1,247 @@
+//! # Persons Resource (人物资源)
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::Client;
+use crate::{error::*, types::*};
+
+/// Person works timeline (人物作品年表)
+///
+/// 由 [`Client::person_timeline`] 或 [`PersonTimeline::from_works`] 构建。
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct PersonTimeline {
+    /// 按年份升序排列，发布日期未知的作品在最后
+    pub years: Vec<TimelineYear>,
+}
+
+/// [`PersonTimeline`] 中的一年
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TimelineYear {
+    /// 年份，发布日期未知时为 `None`
+    pub year: Option<i32>,
+
+    /// 按职位名称排列的职位
+    pub roles: Vec<TimelineRole>,
+}
+
+/// [`TimelineYear`] 中的一个职位
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TimelineRole {
+    /// 职位，即 [`RelatedSubject::staff`]，例如 `导演`
+    pub role: String,
+
+    /// 按发布日期排列的作品
+    pub works: Vec<TimelineWork>,
+}
+
+/// 年表中的一部作品
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TimelineWork {
+    /// 相关条目
+    pub subject: RelatedSubject,
+
+    /// 条目的发布日期，例如 `2004-04-24`
+    pub date: Option<String>,
+}
+
+impl TimelineWork {
+    /// 创建一部作品
+    pub fn new(subject: RelatedSubject, date: Option<String>) -> Self {
+        Self { subject, date }
+    }
+
+    /// 发布年份
+    pub fn year(&self) -> Option<i32> {
+        self.date.as_deref()?.get(..4)?.parse().ok()
+    }
+}
+
+impl PersonTimeline {
+    /// 按年份与职位整理 `works`
+    pub fn from_works(works: impl IntoIterator<Item = TimelineWork>) -> Self {
+        let mut years: BTreeMap<Option<i32>, BTreeMap<String, Vec<TimelineWork>>> = BTreeMap::new();
+        for work in works {
+            years
+                .entry(work.year())
+                .or_default()
+                .entry(work.subject.staff.clone())
+                .or_default()
+                .push(work);
+        }
+
+        // `None` 在 `BTreeMap` 中排在最前，移到最后
+        let undated = years.remove(&None);
+        let years = years
+            .into_iter()
+            .chain(undated.map(|roles| (None, roles)))
+            .map(|(year, roles)| TimelineYear {
+                year,
+                roles: roles
+                    .into_iter()
+                    .map(|(role, mut works)| {
+                        works.sort_by(|a, b| (&a.date, a.subject.id).cmp(&(&b.date, b.subject.id)));
+                        TimelineRole { role, works }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { years }
+    }
+
+    /// 某一年的作品，`None` 表示发布日期未知的作品
+    pub fn year(&self, year: Option<i32>) -> Option<&TimelineYear> {
+        self.years.iter().find(|y| y.year == year)
+    }
+
+    /// 作品总数，同一条目担任多个职位时计算多次
+    pub fn len(&self) -> usize {
+        self.years
+            .iter()
+            .flat_map(|year| &year.roles)
+            .map(|role| role.works.len())
+            .sum()
+    }
+
+    /// 是否没有任何作品
+    pub fn is_empty(&self) -> bool {
+        self.years.is_empty()
+    }
+}
+
+/// # Aggregate person methods for [`Client`].
+impl Client {
+    /// 获取人物的作品年表
+    ///
+    /// 通过 [`get_person_subjects`](Client::get_person_subjects) 获取参与的条目，再通过
+    /// [`get_subjects_by_ids`](Client::get_subjects_by_ids) 获取各条目的发布日期，按年份与职位整理为 [`PersonTimeline`]。
+    /// 任意一个请求失败时返回其错误。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::new();
+    /// let timeline = client.person_timeline(1).await?;
+    /// for year in &timeline.years {
+    ///     for role in &year.roles {
+    ///         println!("{:?} {}: {} works", year.year, role.role, role.works.len());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn person_timeline(
+        &self,
+        person_id: impl Into<PersonId>,
+    ) -> Result<PersonTimeline, DepsError> {
+        let person_id: PersonId = person_id.into();
+
+        let subjects = self.get_person_subjects(person_id).await?;
+
+        let mut seen = HashSet::new();
+        let ids: Vec<_> = subjects
+            .iter()
+            .map(|subject| subject.id)
+            .filter(|id| seen.insert(*id))
+            .collect();
+        let dates: HashMap<_, _> = self
+            .get_subjects_by_ids(ids, 4)
+            .await
+            .into_result()?
+            .into_iter()
+            .map(|subject| (subject.id, subject.date.filter(|date| !date.is_empty())))
+            .collect();
+
+        Ok(PersonTimeline::from_works(subjects.into_iter().map(
+            |subject| {
+                let date = dates.get(&subject.id).cloned().flatten();
+                TimelineWork::new(subject, date)
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::transport::{HttpTransport, TransportFuture};
+
+    fn subject(id: u64, date: Option<&str>) -> String {
+        let subject = Subject {
+            id,
+            date: date.map(ToString::to_string),
+            ..crate::mock::subject()
+        };
+        serde_json::to_string(&subject).unwrap()
+    }
+
+    /// 按路径返回固定响应的传输层
+    struct Routes(Vec<(&'static str, String)>);
+
+    impl HttpTransport for Routes {
+        fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+            let body = self
+                .0
+                .iter()
+                .find(|(path, _)| *path == req.url().path())
+                .map(|(_, body)| body.clone())
+                .unwrap_or_default();
+            Box::pin(async move {
+                let res = http::Response::builder().status(200).body(body).unwrap();
+                Ok(reqwest::Response::from(res))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_person_timeline() {
+        let related = |id: u64, staff: &str| {
+            format!(
+                r#"{{"id":{},"type":2,"staff":"{}","name":"","name_cn":"","image":""}}"#,
+                id, staff
+            )
+        };
+        let subjects = format!(
+            "[{},{},{},{}]",
+            related(1, "导演"),
+            related(2, "脚本"),
+            related(2, "导演"),
+            related(3, "导演"),
+        );
+        let client = Client::builder()
+            .transport(Routes(vec![
+                ("/v0/persons/7/subjects", subjects),
+                ("/v0/subjects/1", subject(1, Some("2006-10-05"))),
+                ("/v0/subjects/2", subject(2, Some("2004-04-24"))),
+                ("/v0/subjects/3", subject(3, None)),
+            ]))
+            .build()
+            .unwrap();
+
+        let timeline = client.person_timeline(7).await.unwrap();
+        assert_eq!(timeline.len(), 4);
+        assert_eq!(
+            timeline.years.iter().map(|y| y.year).collect::<Vec<_>>(),
+            vec![Some(2004), Some(2006), None]
+        );
+
+        let year = timeline.year(Some(2004)).unwrap();
+        assert_eq!(
+            year.roles
+                .iter()
+                .map(|r| r.role.as_str())
+                .collect::<Vec<_>>(),
+            vec!["导演", "脚本"]
+        );
+        assert_eq!(year.roles[0].works[0].subject.id, 2);
+        assert_eq!(year.roles[0].works[0].date.as_deref(), Some("2004-04-24"));
+        assert_eq!(timeline.year(None).unwrap().roles[0].works[0].subject.id, 3);
+    }
+}
@@ -0,0 +1,355 @@
+//! # Recorder (录制与回放)
+//!
+//! [`Recorder`] 是一个 [`HttpTransport`]：录制模式下将真实的响应保存为 fixture 文件，
+//! 回放模式下从 fixture 文件读取响应而不访问网络，使集成测试不依赖于 API 的可用性。
+//!
+//! 每个请求保存为目录下的一个 JSON 文件，文件名由请求方法、路径与请求内容的哈希组成，
+//! 可以直接提交到版本库中。fixture 中 URL 的 `access_token` 等敏感参数与 `Set-Cookie` 等敏感响应头会被隐去，
+//! 文件名的哈希也不包含这些敏感内容。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::recorder::Recorder;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! // 第一次运行时使用 Recorder::record 录制，之后改为 Recorder::replay
+//! let client = Client::builder()
+//!     .transport(Recorder::replay_or_record("tests/fixtures"))
+//!     .build()?;
+//!
+//! let subject = client.get_subject(3559).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use reqwest::header::{HeaderName, AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, SET_COOKIE};
+
+use super::{
+    redact_form, redact_url,
+    transport::{HttpTransport, TransportFuture},
+};
+use crate::error::DepsError;
+
+/// 不会保存到 fixture 中的敏感响应头
+const SECRET_HEADERS: &[HeaderName] = &[SET_COOKIE, COOKIE, AUTHORIZATION, PROXY_AUTHORIZATION];
+
+/// Record mode (录制模式)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordMode {
+    /// 总是发送真实的请求，并保存响应
+    Record,
+
+    /// 只从 fixture 文件读取响应，不存在时返回 [`DepsError::Io`]
+    Replay,
+
+    /// 存在 fixture 文件时回放，否则发送真实的请求并保存响应
+    ReplayOrRecord,
+}
+
+/// 保存在 fixture 文件中的响应
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_hex: Option<String>,
+}
+
+/// Recorder (录制器)
+///
+/// 参见 [模块文档](self)。
+#[derive(Clone)]
+pub struct Recorder {
+    dir: PathBuf,
+    mode: RecordMode,
+    inner: Arc<dyn HttpTransport>,
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("dir", &self.dir)
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// 在 `dir` 目录中以 `mode` 模式录制或回放
+    ///
+    /// 录制时通过默认的 [`reqwest::Client`] 发送请求，可以通过 [`inner`](Self::inner) 替换。
+    pub fn new(dir: impl Into<PathBuf>, mode: RecordMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+            inner: Arc::new(reqwest::Client::new()),
+        }
+    }
+
+    /// 录制到 `dir` 目录，参见 [`RecordMode::Record`]
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, RecordMode::Record)
+    }
+
+    /// 从 `dir` 目录回放，参见 [`RecordMode::Replay`]
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, RecordMode::Replay)
+    }
+
+    /// 参见 [`RecordMode::ReplayOrRecord`]
+    pub fn replay_or_record(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, RecordMode::ReplayOrRecord)
+    }
+
+    /// 设置录制时实际发送请求的 [`HttpTransport`]
+    pub fn inner(mut self, inner: impl HttpTransport + 'static) -> Self {
+        self.inner = Arc::new(inner);
+        self
+    }
+
+    /// fixture 文件所在的目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, req: &reqwest::Request) -> PathBuf {
+        let url = redact_url(req.url());
+        let mut name = format!("{}{}", req.method(), url.path());
+        name = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        // FNV-1a，保证不同版本的 Rust 下文件名保持不变；敏感参数在计算哈希前已被隐去
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let body = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .unwrap_or_default();
+        let body = match redact_form(body) {
+            Some(redacted) => redacted.into_bytes(),
+            None => body.to_vec(),
+        };
+        for byte in url.as_str().bytes().chain(*b"\n").chain(body) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+
+        self.dir.join(format!("{}_{:016x}.json", name, hash))
+    }
+
+    async fn record_response(
+        &self,
+        path: PathBuf,
+        req: reqwest::Request,
+    ) -> Result<reqwest::Response, DepsError> {
+        let method = req.method().to_string();
+        let url = redact_url(req.url()).to_string();
+        let res = self.inner.execute(req).await?;
+
+        let status = res.status().as_u16();
+        let headers = res
+            .headers()
+            .iter()
+            .filter(|(name, _)| !SECRET_HEADERS.contains(name))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let bytes = res.bytes().await?;
+        let (body, body_hex) = match std::str::from_utf8(&bytes) {
+            Ok(body) => (Some(body.to_string()), None),
+            Err(_) => (
+                None,
+                Some(bytes.iter().map(|b| format!("{:02x}", b)).collect()),
+            ),
+        };
+        let fixture = Fixture {
+            method,
+            url,
+            status,
+            headers,
+            body,
+            body_hex,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, serde_json::to_vec_pretty(&fixture)?)?;
+        fixture.into_response()
+    }
+}
+
+impl Fixture {
+    fn load(path: &Path) -> Result<Self, DepsError> {
+        let content = fs::read(path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("cannot read fixture {}: {}", path.display(), err),
+            )
+        })?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    fn into_response(self) -> Result<reqwest::Response, DepsError> {
+        let body = match (self.body, self.body_hex) {
+            (Some(body), _) => body.into_bytes(),
+            (None, Some(hex)) => (0..hex.len())
+                .step_by(2)
+                .filter_map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+
+        let mut res = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            res = res.header(name, value);
+        }
+        let res = res
+            .body(body)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(reqwest::Response::from(res))
+    }
+}
+
+/// 读取 `dir` 中所有 2xx 响应的 fixture 文件，返回请求的 URL 与响应体
+#[cfg(feature = "schema-check")]
+pub(crate) fn read_fixtures(dir: &Path) -> Result<Vec<(String, Vec<u8>)>, DepsError> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<_, io::Error>>()?;
+    paths.sort();
+
+    let mut fixtures = Vec::new();
+    for path in paths {
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let fixture = Fixture::load(&path)?;
+        if let (200..=299, Some(body)) = (fixture.status, fixture.body) {
+            fixtures.push((fixture.url, body.into_bytes()));
+        }
+    }
+    Ok(fixtures)
+}
+
+impl HttpTransport for Recorder {
+    fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let path = self.path(&req);
+            match self.mode {
+                RecordMode::Replay => Fixture::load(&path)?.into_response(),
+                RecordMode::ReplayOrRecord if path.exists() => {
+                    Fixture::load(&path)?.into_response()
+                }
+                _ => self.record_response(path, req).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::Client,
+        mock::{MockResponse, MockServer},
+    };
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let dir = std::env::temp_dir().join(format!("bgmtv-recorder-{}", std::process::id()));
+        let user = r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, user),
+            MockResponse::new(200, vec![0xff, 0x00]),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .transport(Recorder::record(&dir))
+            .build()
+            .unwrap();
+        assert_eq!(client.get_user("sai").await.unwrap().nickname, "Sai");
+        let avatar = client
+            .get_user_avatar("sai", crate::types::ImageType::Small)
+            .await
+            .unwrap();
+        assert_eq!(avatar, vec![0xff, 0x00]);
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(
+            server.requests()[0].header("user-agent"),
+            Some(client.user_agent())
+        );
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .transport(Recorder::replay(&dir))
+            .build()
+            .unwrap();
+        assert_eq!(client.get_user("sai").await.unwrap().nickname, "Sai");
+        let avatar = client
+            .get_user_avatar("sai", crate::types::ImageType::Small)
+            .await
+            .unwrap();
+        assert_eq!(avatar, vec![0xff, 0x00]);
+        assert_eq!(server.requests().len(), 2);
+        assert!(matches!(
+            client.get_user("other").await,
+            Err(DepsError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_redacts_secrets() {
+        let dir =
+            std::env::temp_dir().join(format!("bgmtv-recorder-secret-{}", std::process::id()));
+        let status = r#"{"access_token":"","client_id":"app","user_id":1,"expires":0}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, status).header("set-cookie", "chii_sid=secret_cookie")
+        ])
+        .await;
+
+        let client = Client::builder()
+            .oauth_base_url(server.base_url.clone())
+            .token("secret_token")
+            .transport(Recorder::record(&dir))
+            .build()
+            .unwrap();
+        client.token_status().await.unwrap();
+
+        let url =
+            url::Url::parse(&format!("{}?access_token=secret_token", server.base_url)).unwrap();
+        let req = reqwest::Request::new(reqwest::Method::GET, url);
+        let path = Recorder::record(&dir).path(&req);
+        let mut redacted = req.url().clone();
+        redacted.set_query(Some("access_token=other"));
+        assert_eq!(
+            path,
+            Recorder::record(&dir).path(&reqwest::Request::new(reqwest::Method::GET, redacted))
+        );
+
+        for entry in fs::read_dir(&dir).unwrap() {
+            let content = fs::read_to_string(entry.unwrap().path()).unwrap();
+            assert!(!content.contains("secret"), "{}", content);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,91 @@
+//! # Request ID (请求 ID)
+//!
+//! 每次 API 调用都会生成一个 UUID v4 作为请求 ID，通过 `X-Request-Id` 请求头发送，重试时保持不变。
+//! 请求 ID 会记录在 `tracing` feature 的 `bgmtv.request` span 中，
+//! 并通过 [`DepsError::request_id`](crate::error::DepsError::request_id) 等方法从错误中获取，
+//! 便于将客户端日志与服务端的排查关联起来。
+//!
+//! 请求中已有 `X-Request-Id` 请求头 (例如通过 `ClientBuilder::default_headers` 设置) 时沿用其值。
+
+use std::{
+    hash::BuildHasher,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use reqwest::header::{HeaderName, HeaderValue};
+
+/// `X-Request-Id` 请求头
+pub(crate) const HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 保存在响应扩展中的请求 ID
+#[derive(Clone, Debug)]
+pub(crate) struct RequestId(pub(crate) String);
+
+/// 获取请求的 ID，请求中没有 `X-Request-Id` 请求头时生成一个并写入
+pub(crate) fn ensure(req: &mut reqwest::Request) -> String {
+    if let Some(id) = req.headers().get(HEADER).and_then(|v| v.to_str().ok()) {
+        return id.to_string();
+    }
+
+    let id = generate();
+    req.headers_mut().insert(
+        HEADER,
+        HeaderValue::from_str(&id).expect("UUID is a valid header value"),
+    );
+    id
+}
+
+/// 生成一个 UUID v4
+///
+/// 请求 ID 只用于关联日志，不需要密码学安全的随机数，使用标准库的随机哈希种子即可。
+fn generate() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let state = std::collections::hash_map::RandomState::new();
+    let high = state.hash_one((count, 0u8)).to_be_bytes();
+    let low = state.hash_one((count, 1u8)).to_be_bytes();
+
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&high);
+    bytes[8..].copy_from_slice(&low);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let id = generate();
+        assert_eq!(id.len(), 36);
+        assert_eq!(
+            id.split('-').map(str::len).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert_eq!(&id[14..15], "4");
+        assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(id, generate());
+    }
+
+    #[test]
+    fn test_ensure() {
+        let url = "https://api.bgm.tv/v0/me".parse().unwrap();
+        let mut req = reqwest::Request::new(reqwest::Method::GET, url);
+        let id = ensure(&mut req);
+        assert_eq!(req.headers()[HEADER], id.as_str());
+        assert_eq!(ensure(&mut req), id);
+    }
+}
@@ -0,0 +1,351 @@
+//! # Retry (重试)
+//!
+//! API 返回 `429 Too Many Requests` 时，[`Client`](super::Client) 会读取 `Retry-After` 响应头：
+//!
+//! - 通过 [`ClientBuilder::retry`](super::ClientBuilder::retry) 设置了 [`RetryPolicy`] 时，等待相应的时长后重试
+//! - 否则返回 [`DepsError::RateLimited`](crate::error::DepsError::RateLimited)，其中包含需要等待的时长
+//!
+//! 设置了 [`RetryPolicy`] 时，网络错误 (连接失败、超时等) 也会在等待 [`RetryPolicy::default_delay`] 后重试。
+//! 重试会区分请求是否幂等：
+//!
+//! - 查询类 API (`GET`，以及 `POST /v0/search/subjects` 等只读的 `POST`) 可以安全地重试
+//! - 修改收藏等 `POST` / `PATCH` 请求重复发送可能导致重复修改，默认只在连接失败 (请求尚未发出) 时重试，
+//!   请求一旦发出 (包括收到 `429`) 就不再重试，可以通过 [`RetryPolicy::writes`] 调整
+//!
+//! 等待通过 [`Clock`](crate::time::Clock) 进行，可以在测试中注入 [`ManualClock`](crate::time::ManualClock)。
+//!
+//! `Retry-After` 为 HTTP 日期时，等待时长按 [`Clock::system_time`](crate::time::Clock::system_time) 计算。
+//!
+//! 每次请求的各次尝试会被记录为 [`RetryHistory`]：重试用尽时包含在
+//! [`DepsError::RateLimited`](crate::error::DepsError::RateLimited) 或
+//! [`DepsError::Network`](crate::error::DepsError::Network) 中，
+//! 成功时可以通过 [`Client::with_retry_history`] 获取，以便观察限流的规律。
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method,
+};
+
+use super::{overrides::EndpointClass, Client};
+use crate::error::DepsError;
+
+/// Retry policy (重试策略)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+    /// 最大重试次数
+    pub max_retries: u32,
+
+    /// 响应中没有 `Retry-After` 时的等待时长
+    pub default_delay: Duration,
+
+    /// 最长等待时长，`Retry-After` 超过此时长时不再重试，直接返回错误
+    pub max_delay: Duration,
+
+    /// 是否在网络错误 (连接失败、超时等) 时重试，默认为 `true`
+    pub network_errors: bool,
+
+    /// 非幂等的修改类请求的重试方式，默认为 [`WriteRetry::ConnectErrors`]
+    pub writes: WriteRetry,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            default_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            network_errors: true,
+            writes: WriteRetry::default(),
+        }
+    }
+}
+
+/// Retry of non-idempotent writes (非幂等请求的重试方式)
+///
+/// 适用于修改收藏等 `POST` / `PATCH` 请求，参见 [模块文档](self)。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriteRetry {
+    /// 从不重试
+    Never,
+
+    /// 只在连接失败 (请求尚未发出) 时重试
+    #[default]
+    ConnectErrors,
+
+    /// 与幂等请求相同，在限流与任意网络错误时重试
+    Always,
+}
+
+impl RetryPolicy {
+    /// 设置最大重试次数
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 设置响应中没有 `Retry-After` 时的等待时长
+    pub fn default_delay(mut self, default_delay: Duration) -> Self {
+        self.default_delay = default_delay;
+        self
+    }
+
+    /// 设置最长等待时长
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// 设置是否在网络错误时重试
+    pub fn network_errors(mut self, network_errors: bool) -> Self {
+        self.network_errors = network_errors;
+        self
+    }
+
+    /// 设置非幂等的修改类请求的重试方式
+    pub fn writes(mut self, writes: WriteRetry) -> Self {
+        self.writes = writes;
+        self
+    }
+
+    /// 第 `attempt` 次 (从 0 开始) 请求被限流后，重试前需要等待的时长，`None` 表示不再重试
+    ///
+    /// 非幂等的请求此时已经发出，只有 [`WriteRetry::Always`] 时才会重试。
+    pub(crate) fn rate_limit_delay(
+        &self,
+        attempt: u32,
+        retry_after: Option<Duration>,
+        idempotent: bool,
+    ) -> Option<Duration> {
+        if !idempotent && self.writes != WriteRetry::Always {
+            return None;
+        }
+        let delay = retry_after.unwrap_or(self.default_delay);
+        (attempt < self.max_retries && delay <= self.max_delay).then_some(delay)
+    }
+
+    /// 第 `attempt` 次 (从 0 开始) 请求因 `err` 失败后，重试前需要等待的时长，`None` 表示不再重试
+    pub(crate) fn error_delay(
+        &self,
+        attempt: u32,
+        err: &DepsError,
+        idempotent: bool,
+    ) -> Option<Duration> {
+        let DepsError::Reqwest(err) = err else {
+            return None;
+        };
+        let retryable = match (idempotent, self.writes) {
+            (true, _) | (false, WriteRetry::Always) => {
+                err.is_connect() || err.is_timeout() || err.is_request()
+            }
+            (false, WriteRetry::ConnectErrors) => err.is_connect(),
+            (false, WriteRetry::Never) => false,
+        };
+        (self.network_errors && retryable && attempt < self.max_retries)
+            .then_some(self.default_delay)
+    }
+}
+
+/// `endpoint` 的请求是否幂等，即重复发送是否安全
+///
+/// `POST /v0/search/subjects` 等只读的 `POST` 视为幂等，只有修改类 API 的 `POST` / `PATCH` 不是。
+pub(crate) fn is_idempotent(endpoint: &str, method: &Method) -> bool {
+    !matches!(*method, Method::POST | Method::PATCH)
+        || EndpointClass::of(endpoint) != EndpointClass::Writes
+}
+
+/// Retry attempt (一次请求尝试)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RetryAttempt {
+    /// 响应状态码，请求失败 (例如网络错误) 时为 `None`
+    pub status: Option<u16>,
+
+    /// 此次尝试后、重试前等待的时长，最后一次尝试为 `None`
+    pub delay: Option<Duration>,
+}
+
+/// Retry history (重试记录)
+///
+/// 一次请求的全部尝试，按发送顺序排列。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetryHistory {
+    attempts: Vec<RetryAttempt>,
+}
+
+impl RetryHistory {
+    /// 全部尝试
+    pub fn attempts(&self) -> &[RetryAttempt] {
+        &self.attempts
+    }
+
+    /// 尝试次数
+    pub fn len(&self) -> usize {
+        self.attempts.len()
+    }
+
+    /// 是否没有任何尝试
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    /// 重试次数，即尝试次数减一
+    pub fn retries(&self) -> usize {
+        self.len().saturating_sub(1)
+    }
+
+    /// 重试前等待的总时长
+    pub fn total_delay(&self) -> Duration {
+        self.attempts.iter().filter_map(|a| a.delay).sum()
+    }
+
+    pub(crate) fn push(&mut self, status: Option<u16>, delay: Option<Duration>) {
+        self.attempts.push(RetryAttempt { status, delay });
+    }
+}
+
+thread_local! {
+    static TRACKED: RefCell<Option<Vec<RetryHistory>>> = const { RefCell::new(None) };
+}
+
+/// 如果当前 future 处于 [`Client::with_retry_history`] 中，记录一次请求的 `history`
+pub(crate) fn track(history: &RetryHistory) {
+    TRACKED.with(|tracked| {
+        if let Some(histories) = tracked.borrow_mut().as_mut() {
+            histories.push(history.clone());
+        }
+    });
+}
+
+/// 记录重试的 future，由 [`Client::with_retry_history`] 返回
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RetryTracked<F> {
+    inner: Pin<Box<F>>,
+    histories: Option<Vec<RetryHistory>>,
+}
+
+impl<F: Future> Future for RetryTracked<F> {
+    type Output = (F::Output, Vec<RetryHistory>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        struct Restore<'a>(&'a mut Option<Vec<RetryHistory>>, Option<Vec<RetryHistory>>);
+
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                *self.0 = TRACKED.with(|tracked| tracked.replace(self.1.take()));
+            }
+        }
+
+        let this = &mut *self;
+        let outer = TRACKED.with(|tracked| tracked.replace(this.histories.take()));
+        let restore = Restore(&mut this.histories, outer);
+        let poll = this.inner.as_mut().poll(cx);
+        drop(restore);
+
+        poll.map(|output| (output, this.histories.take().unwrap_or_default()))
+    }
+}
+
+/// # Retry methods for [`Client`].
+impl Client {
+    /// 执行 `fut` 并记录其中每个请求的 [`RetryHistory`]
+    ///
+    /// 返回 `fut` 的结果以及按完成顺序排列的重试记录，没有发生重试的请求也会被记录 (只有一次尝试)。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// # let client = Client::new();
+    /// let (subject, histories) = client.with_retry_history(client.get_subject(3559)).await;
+    /// for history in histories.iter().filter(|h| h.retries() > 0) {
+    ///     println!("retried {} times, waited {:?}", history.retries(), history.total_delay());
+    /// }
+    /// let subject = subject?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_retry_history<F: Future>(&self, fut: F) -> RetryTracked<F> {
+        RetryTracked {
+            inner: Box::pin(fut),
+            histories: Some(Vec::new()),
+        }
+    }
+}
+
+/// 解析 `Retry-After` 响应头，支持秒数与 HTTP 日期两种格式，HTTP 日期按 `now` 计算等待时长
+pub(crate) fn retry_after(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_after() {
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2015 07:27:00 GMT").unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, now), None);
+
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(60)));
+        assert_eq!(
+            retry_after(&headers, now + Duration::from_secs(120)),
+            Some(Duration::ZERO)
+        );
+
+        let policy = RetryPolicy::default().max_retries(1);
+        assert_eq!(
+            policy.rate_limit_delay(0, None, true),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(policy.rate_limit_delay(1, None, true), None);
+        assert_eq!(
+            policy.rate_limit_delay(0, Some(Duration::from_secs(3600)), true),
+            None
+        );
+        assert_eq!(policy.rate_limit_delay(0, None, false), None);
+        assert_eq!(
+            policy
+                .writes(WriteRetry::Always)
+                .rate_limit_delay(0, None, false),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        assert!(is_idempotent("GET /v0/subjects/{subject_id}", &Method::GET));
+        assert!(is_idempotent("POST /v0/search/subjects", &Method::POST));
+        assert!(is_idempotent("POST /oauth/token_status", &Method::POST));
+        assert!(!is_idempotent(
+            "PATCH /v0/users/-/collections/{subject_id}",
+            &Method::PATCH
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+//! # Scheduler (调度器)
+//!
+//! 通过 [`ClientBuilder::rate_limit`](super::ClientBuilder::rate_limit) 设置 [`RateLimit`] 后，
+//! [`Client`] 发出的所有请求都会经过同一个限流器。
+//!
+//! [`Scheduler`] 为请求区分优先级：限流器同时有交互请求 ([`Priority::Interactive`]) 与后台请求
+//! ([`Priority::Background`]) 在等待时，总是先放行交互请求，使后台的批量抓取不会拖慢面向用户的请求。
+//! 未经 [`Scheduler`] 发出的请求视为交互请求。
+//!
+//! 优先级只作用于 [`Scheduler`] 返回的 future 内部直接发出的请求；在其中通过 `tokio::spawn` 等方式启动的新任务
+//! 不会继承优先级，需要再次包装。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::scheduler::RateLimit;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder()
+//!     .rate_limit(RateLimit::new(2, Duration::from_secs(1)))
+//!     .build()?;
+//! let scheduler = client.scheduler();
+//!
+//! let crawl = scheduler.background(async {
+//!     for id in 1..100 {
+//!         client.get_subject(id).await?;
+//!     }
+//!     Ok::<_, DepsError>(())
+//! });
+//! let lookup = scheduler.interactive(client.get_subject(3559));
+//!
+//! let (_, subject) = tokio::join!(crawl, lookup);
+//! println!("{}", subject?.name);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use super::Client;
+use crate::time::Clock;
+
+/// Rate limit (限流配置)
+///
+/// 每 `per` 最多发出 `requests` 个请求，允许短时间内突发 `requests` 个请求。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RateLimit {
+    /// 每个周期内的请求数
+    pub requests: u32,
+
+    /// 周期
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// 每 `per` 最多发出 `requests` 个请求
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self {
+            requests: requests.max(1),
+            per,
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+/// Request priority (请求优先级)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// 面向用户的请求，优先放行
+    #[default]
+    Interactive,
+
+    /// 后台请求，只在没有交互请求等待时放行
+    Background,
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<Priority>> = const { Cell::new(None) };
+}
+
+/// 当前 future 的优先级，不在 [`Scheduler`] 中时为 [`Priority::Interactive`]
+pub(crate) fn current_priority() -> Priority {
+    CURRENT.with(Cell::get).unwrap_or_default()
+}
+
+/// 带有优先级的 future，由 [`Scheduler`] 返回
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Prioritized<F> {
+    inner: Pin<Box<F>>,
+    priority: Priority,
+}
+
+impl<F: Future> Future for Prioritized<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        struct Restore(Option<Priority>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.priority))));
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+/// Scheduler (调度器)
+///
+/// 通过 [`Client::scheduler`] 获取，参见 [模块文档](self)。
+#[derive(Clone, Copy, Debug)]
+pub struct Scheduler<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Scheduler<'a> {
+    /// 调度器所使用的 [`Client`]
+    pub fn client(&self) -> &'a Client {
+        self.client
+    }
+
+    /// 以 `priority` 优先级执行 `job`
+    pub fn run<F: Future>(&self, priority: Priority, job: F) -> Prioritized<F> {
+        Prioritized {
+            inner: Box::pin(job),
+            priority,
+        }
+    }
+
+    /// 以 [`Priority::Interactive`] 优先级执行 `job`
+    pub fn interactive<F: Future>(&self, job: F) -> Prioritized<F> {
+        self.run(Priority::Interactive, job)
+    }
+
+    /// 以 [`Priority::Background`] 优先级执行 `job`
+    pub fn background<F: Future>(&self, job: F) -> Prioritized<F> {
+        self.run(Priority::Background, job)
+    }
+}
+
+/// 令牌桶限流器
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<Bucket>,
+    interactive_waiting: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(Bucket {
+                tokens: f64::from(limit.requests),
+                updated: None,
+            }),
+            interactive_waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// 尝试取出 `weight` 个令牌，失败时返回需要等待的时长
+    fn try_acquire(&self, now: Instant, weight: f64) -> Result<(), Duration> {
+        let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let capacity = f64::from(self.limit.requests);
+        let interval = self.limit.interval().as_secs_f64();
+
+        if let Some(updated) = bucket.updated {
+            let elapsed = now.saturating_duration_since(updated).as_secs_f64();
+            if interval > 0.0 {
+                bucket.tokens = (bucket.tokens + elapsed / interval).min(capacity);
+            } else {
+                bucket.tokens = capacity;
+            }
+        }
+        bucket.updated = Some(now);
+
+        if bucket.tokens >= weight {
+            bucket.tokens -= weight;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((weight - bucket.tokens) * interval))
+        }
+    }
+
+    /// 等待直到可以发出一个 `priority` 优先级、消耗 `weight` 个令牌的请求
+    ///
+    /// `weight` 为 0 时直接返回，超过桶的容量时按容量计算。
+    pub(crate) async fn acquire(&self, priority: Priority, clock: &dyn Clock, weight: u32) {
+        if weight == 0 {
+            return;
+        }
+        let weight = f64::from(weight.min(self.limit.requests));
+
+        struct Waiting<'a>(&'a AtomicUsize);
+
+        impl Drop for Waiting<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let _waiting = (priority == Priority::Interactive).then(|| {
+            self.interactive_waiting.fetch_add(1, Ordering::SeqCst);
+            Waiting(&self.interactive_waiting)
+        });
+
+        loop {
+            // 后台请求在有交互请求等待时让出令牌
+            let yield_to_interactive = priority == Priority::Background
+                && self.interactive_waiting.load(Ordering::SeqCst) > 0;
+
+            let wait = if yield_to_interactive {
+                self.limit.interval().max(Duration::from_millis(1))
+            } else {
+                match self.try_acquire(clock.now(), weight) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            clock.sleep(wait).await;
+        }
+    }
+}
+
+/// # Scheduling methods for [`Client`].
+impl Client {
+    /// 获取为请求区分优先级的 [`Scheduler`]
+    ///
+    /// 未设置 [`RateLimit`] 时请求不会等待，优先级没有效果。
+    pub fn scheduler(&self) -> Scheduler<'_> {
+        Scheduler { client: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::time::{ManualClock, TokioClock};
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(RateLimit::new(2, Duration::from_secs(1)));
+
+        for _ in 0..4 {
+            limiter.acquire(Priority::Interactive, &clock, 1).await;
+        }
+        // 前两个请求为突发，之后每 500ms 一个
+        assert_eq!(
+            clock.slept(),
+            vec![Duration::from_millis(500), Duration::from_millis(500)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_priority() {
+        let limiter = RateLimiter::new(RateLimit::new(1, Duration::from_millis(20)));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        limiter.acquire(Priority::Interactive, &TokioClock, 1).await;
+
+        let job = |priority, name| {
+            let limiter = &limiter;
+            let order = order.clone();
+            async move {
+                limiter.acquire(priority, &TokioClock, 1).await;
+                order.lock().unwrap().push(name);
+            }
+        };
+
+        futures_util::join!(
+            job(Priority::Background, "b1"),
+            job(Priority::Background, "b2"),
+            job(Priority::Interactive, "i1"),
+        );
+        let order = order.lock().unwrap();
+        assert_eq!(order.len(), 3);
+        assert_eq!(order[0], "i1");
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_priority() {
+        let client = Client::new();
+        let scheduler = client.scheduler();
+
+        assert_eq!(current_priority(), Priority::Interactive);
+        scheduler
+            .background(async {
+                assert_eq!(current_priority(), Priority::Background);
+                tokio::task::yield_now().await;
+                assert_eq!(current_priority(), Priority::Background);
+            })
+            .await;
+        assert_eq!(current_priority(), Priority::Interactive);
+    }
+}
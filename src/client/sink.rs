@@ -0,0 +1,227 @@
+//! # Media Sink (媒体存储)
+//!
+//! [`MediaSink`] 是下载的图片等媒体文件的异步存储，[`Client::prefetch_images_to`](Client::prefetch_images_to)
+//! 将图片直接写入其中，便于归档工具将数据写入本地目录或对象存储。
+//!
+//! - [`FsMediaSink`]：写入本地目录，需要启用 `fs` feature (默认启用)
+//! - `s3::S3MediaSink`：写入 S3 兼容的对象存储，需要启用 `s3` feature
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::{images::PrefetchOptions, sink::FsMediaSink};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//! let sink = FsMediaSink::new("./archive/images");
+//!
+//! let subjects = vec![client.get_subject(3559).await?];
+//! client
+//!     .prefetch_images_to(
+//!         subjects.iter().map(|s| &s.images),
+//!         ImageType::Large,
+//!         &sink,
+//!         PrefetchOptions::default(),
+//!     )
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+use std::{future::Future, pin::Pin};
+
+use bytes::Bytes;
+#[cfg(feature = "fs")]
+use tokio::fs;
+
+#[cfg(doc)]
+use super::Client;
+use crate::{error::DepsError, types::ImageType};
+
+#[cfg(feature = "s3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "s3")))]
+pub mod s3;
+
+/// Future returned by [`MediaSink`] methods
+pub type SinkFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, DepsError>> + Send + 'a>>;
+
+/// Media key (媒体文件的键)
+///
+/// 由资源路径与尺寸组成，例如 `pic/cover/l/f1/1b/3559_rrwkw.jpg` 与 [`ImageType::Large`]。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MediaKey {
+    /// 资源路径，不以 `/` 开头
+    pub resource: String,
+
+    /// 尺寸
+    pub size: ImageType,
+}
+
+impl MediaKey {
+    /// 创建键，`resource` 开头的 `/` 会被去掉
+    pub fn new(resource: impl Into<String>, size: ImageType) -> Self {
+        let resource = resource.into();
+        Self {
+            resource: resource.trim_start_matches('/').to_string(),
+            size,
+        }
+    }
+
+    /// 以图片 URL 的路径作为资源路径，URL 无法解析时使用整个字符串
+    pub fn from_url(url: &str, size: ImageType) -> Self {
+        match url::Url::parse(url) {
+            Ok(parsed) => Self::new(parsed.path(), size),
+            Err(_) => Self::new(url, size),
+        }
+    }
+
+    /// 存储中的对象名，例如 `large/pic/cover/l/f1/1b/3559_rrwkw.jpg`
+    pub fn object_name(&self) -> String {
+        let size = match self.size {
+            ImageType::Small => "small",
+            ImageType::Common => "common",
+            ImageType::Medium => "medium",
+            ImageType::Large => "large",
+            ImageType::Grid => "grid",
+        };
+        format!("{}/{}", size, self.resource)
+    }
+}
+
+/// Media sink (媒体存储)
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// use bgmtv::client::sink::{MediaKey, MediaSink, SinkFuture};
+///
+/// struct Discard;
+///
+/// impl MediaSink for Discard {
+///     fn contains<'a>(&'a self, _key: &'a MediaKey) -> SinkFuture<'a, bool> {
+///         Box::pin(async { Ok(false) })
+///     }
+///
+///     fn write<'a>(&'a self, _key: &'a MediaKey, _data: bytes::Bytes) -> SinkFuture<'a, ()> {
+///         Box::pin(async { Ok(()) })
+///     }
+/// }
+/// ```
+pub trait MediaSink: Send + Sync {
+    /// `key` 对应的文件是否已存在
+    fn contains<'a>(&'a self, key: &'a MediaKey) -> SinkFuture<'a, bool>;
+
+    /// 写入 `key` 对应的文件，已存在时覆盖
+    fn write<'a>(&'a self, key: &'a MediaKey, data: Bytes) -> SinkFuture<'a, ()>;
+}
+
+/// 写入本地目录的 [`MediaSink`]
+///
+/// 文件保存在 `root` 下的 [`MediaKey::object_name`] 路径，所需的目录会被自动创建。
+/// 文件操作通过 [`tokio::fs`] 进行，不会阻塞异步运行时。
+#[cfg(feature = "fs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+#[derive(Clone, Debug)]
+pub struct FsMediaSink {
+    root: PathBuf,
+}
+
+#[cfg(feature = "fs")]
+impl FsMediaSink {
+    /// 使用 `root` 目录保存文件
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `key` 对应的文件路径，资源路径中的 `..` 等会被忽略
+    pub fn path(&self, key: &MediaKey) -> PathBuf {
+        let mut path = self.root.clone();
+        path.extend(
+            key.object_name()
+                .split('/')
+                .filter(|part| !part.is_empty() && *part != "." && *part != ".."),
+        );
+        path
+    }
+}
+
+#[cfg(feature = "fs")]
+impl MediaSink for FsMediaSink {
+    fn contains<'a>(&'a self, key: &'a MediaKey) -> SinkFuture<'a, bool> {
+        Box::pin(async move {
+            Ok(fs::metadata(self.path(key))
+                .await
+                .is_ok_and(|meta| meta.is_file()))
+        })
+    }
+
+    fn write<'a>(&'a self, key: &'a MediaKey, data: Bytes) -> SinkFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+
+            // 先写入临时文件再重命名，避免留下写了一半的文件
+            let mut tmp = path.clone().into_os_string();
+            tmp.push(".tmp");
+            fs::write(&tmp, &data).await?;
+            fs::rename(&tmp, &path).await?;
+            Ok(())
+        })
+    }
+}
+
+/// 将 [`ImageCache`](super::images::ImageCache) 作为 [`MediaSink`] 使用，[`MediaKey::resource`] 为完整的图片 URL
+pub(crate) struct CacheSink<'a>(pub(crate) &'a dyn super::images::ImageCache);
+
+impl MediaSink for CacheSink<'_> {
+    fn contains<'a>(&'a self, key: &'a MediaKey) -> SinkFuture<'a, bool> {
+        Box::pin(async move { Ok(self.0.contains(&key.resource)) })
+    }
+
+    fn write<'a>(&'a self, key: &'a MediaKey, data: Bytes) -> SinkFuture<'a, ()> {
+        Box::pin(async move {
+            self.0.insert(&key.resource, data);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "fs")]
+    #[tokio::test]
+    async fn test_fs_media_sink() {
+        let root = std::env::temp_dir().join(format!("bgmtv-sink-{}", std::process::id()));
+        let sink = FsMediaSink::new(&root);
+
+        let key = MediaKey::from_url(
+            "https://lain.bgm.tv/pic/cover/l/../3559.jpg",
+            ImageType::Large,
+        );
+        assert_eq!(key.object_name(), "large/pic/cover/3559.jpg");
+        assert!(!sink.contains(&key).await.unwrap());
+
+        sink.write(&key, Bytes::from_static(b"image"))
+            .await
+            .unwrap();
+        assert!(sink.contains(&key).await.unwrap());
+        assert_eq!(
+            std::fs::read(root.join("large/pic/cover/3559.jpg")).unwrap(),
+            b"image"
+        );
+
+        let key = MediaKey::new("../../escape.jpg", ImageType::Small);
+        assert_eq!(sink.path(&key), root.join("small").join("escape.jpg"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
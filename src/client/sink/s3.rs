@@ -0,0 +1,437 @@
+//! # S3 Media Sink (S3 兼容的对象存储)
+//!
+//! [`S3MediaSink`] 将媒体文件写入 S3 兼容的对象存储 (AWS S3、MinIO、Cloudflare R2 等)，
+//! 请求使用 AWS Signature Version 4 签名。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::client::{images::PrefetchOptions, sink::s3::S3MediaSink};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//! let sink = S3MediaSink::new(
+//!     "http://127.0.0.1:9000",
+//!     "bangumi",
+//!     "us-east-1",
+//!     "access-key",
+//!     "secret-key",
+//! )?
+//! .prefix("archive");
+//!
+//! let subjects = vec![client.get_subject(3559).await?];
+//! client
+//!     .prefetch_images_to(
+//!         subjects.iter().map(|s| &s.images),
+//!         ImageType::Large,
+//!         &sink,
+//!         PrefetchOptions::default(),
+//!     )
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use reqwest::{Method, StatusCode};
+
+use super::{MediaKey, MediaSink, SinkFuture};
+use crate::{error::DepsError, time::civil_from_days};
+
+/// 写入 S3 兼容的对象存储的 [`MediaSink`]
+///
+/// 对象名为 `prefix` 与 [`MediaKey::object_name`] 的组合，默认使用 path-style 的 URL (`{endpoint}/{bucket}/{object}`)。
+#[derive(Clone)]
+pub struct S3MediaSink {
+    endpoint: url::Url,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    prefix: String,
+    path_style: bool,
+    client: reqwest::Client,
+}
+
+impl std::fmt::Debug for S3MediaSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3MediaSink")
+            .field("endpoint", &self.endpoint.as_str())
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("access_key", &self.access_key)
+            .field("prefix", &self.prefix)
+            .field("path_style", &self.path_style)
+            .finish_non_exhaustive()
+    }
+}
+
+impl S3MediaSink {
+    /// 创建写入 `endpoint` 上 `bucket` 的存储
+    pub fn new(
+        endpoint: &str,
+        bucket: impl Into<String>,
+        region: impl Into<String>,
+        access_key: impl Into<String>,
+        secret_key: impl Into<String>,
+    ) -> Result<Self, DepsError> {
+        Ok(Self {
+            endpoint: url::Url::parse(endpoint)?,
+            bucket: bucket.into(),
+            region: region.into(),
+            access_key: access_key.into(),
+            secret_key: secret_key.into(),
+            prefix: String::new(),
+            path_style: true,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// 设置对象名的前缀，例如 `archive` 会将对象写入 `archive/large/pic/...`
+    pub fn prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.prefix = prefix.as_ref().trim_matches('/').to_string();
+        self
+    }
+
+    /// 是否使用 path-style 的 URL，为 `false` 时使用 `{bucket}.{host}` 形式的 virtual-hosted URL
+    pub fn path_style(mut self, path_style: bool) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    /// 设置发送请求使用的 [`reqwest::Client`]
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// `key` 对应的对象名
+    pub fn object_name(&self, key: &MediaKey) -> String {
+        if self.prefix.is_empty() {
+            key.object_name()
+        } else {
+            format!("{}/{}", self.prefix, key.object_name())
+        }
+    }
+
+    /// 对象的 URL 与签名使用的 canonical URI
+    fn object_url(&self, key: &MediaKey) -> Result<(url::Url, String), DepsError> {
+        let mut url = self.endpoint.clone();
+        let mut path = self.endpoint.path().trim_end_matches('/').to_string();
+        if self.path_style {
+            path.push('/');
+            path.push_str(&uri_encode(&self.bucket));
+        } else {
+            let host = url.host_str().unwrap_or_default();
+            url.set_host(Some(&format!("{}.{}", self.bucket, host)))?;
+        }
+        for segment in self.object_name(key).split('/') {
+            path.push('/');
+            path.push_str(&uri_encode(segment));
+        }
+        url.set_path(&path);
+        Ok((url, path))
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &MediaKey,
+        body: Bytes,
+    ) -> Result<reqwest::Response, DepsError> {
+        let (url, canonical_uri) = self.object_url(key)?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let amz_date = amz_date(SystemTime::now());
+        let payload_hash = hex(&sha256(&body));
+
+        let canonical_request = format!(
+            "{method}\n{canonical_uri}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{SIGNED_HEADERS}\n{payload_hash}"
+        );
+        let signature = signature(
+            &self.secret_key,
+            &amz_date,
+            &self.region,
+            "s3",
+            &canonical_request,
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}/{}/s3/aws4_request, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.access_key,
+            &amz_date[..8],
+            self.region,
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+        Ok(request.send().await?)
+    }
+}
+
+impl MediaSink for S3MediaSink {
+    fn contains<'a>(&'a self, key: &'a MediaKey) -> SinkFuture<'a, bool> {
+        Box::pin(async move {
+            let response = self.send(Method::HEAD, key, Bytes::new()).await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(false);
+            }
+            response.error_for_status()?;
+            Ok(true)
+        })
+    }
+
+    fn write<'a>(&'a self, key: &'a MediaKey, data: Bytes) -> SinkFuture<'a, ()> {
+        Box::pin(async move {
+            self.send(Method::PUT, key, data)
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// SigV4 的时间戳，例如 `20150830T123600Z`
+fn amz_date(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let secs = secs.rem_euclid(86400);
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// 计算 SigV4 签名，`amz_date` 为 [`amz_date`] 格式的时间戳
+fn signature(
+    secret_key: &str,
+    amz_date: &str,
+    region: &str,
+    service: &str,
+    canonical_request: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{date}/{region}/{service}/aws4_request\n{}",
+        hex(&sha256(canonical_request.as_bytes()))
+    );
+
+    let key = hmac_sha256(format!("AWS4{secret_key}").as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    let key = hmac_sha256(&key, b"aws4_request");
+    hex(&hmac_sha256(&key, string_to_sign.as_bytes()))
+}
+
+/// 按 SigV4 的规则编码 URI 的一段，只保留 `A-Za-z0-9-._~`
+fn uri_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+    inner.extend_from_slice(data);
+    let mut outer: Vec<u8> = block.iter().map(|b| b ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256，仅用于计算请求签名
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (bytes, h) in digest.chunks_exact_mut(4).zip(h) {
+        bytes.copy_from_slice(&h.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{MockResponse, MockServer},
+        types::ImageType,
+    };
+
+    #[test]
+    fn test_sigv4() {
+        assert_eq!(
+            hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        // AWS S3 文档中 GET Object 的签名示例
+        let empty = hex(&sha256(b""));
+        let canonical_request = format!(
+            "GET\n/test.txt\n\nhost:examplebucket.s3.amazonaws.com\nrange:bytes=0-9\nx-amz-content-sha256:{empty}\nx-amz-date:20130524T000000Z\n\nhost;range;x-amz-content-sha256;x-amz-date\n{empty}"
+        );
+        assert_eq!(
+            signature(
+                "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY",
+                "20130524T000000Z",
+                "us-east-1",
+                "s3",
+                &canonical_request
+            ),
+            "f0e8bdb87c964420e857bd35b5d6ed310bd44f0170aba48dd91039c6036bdb41"
+        );
+
+        assert_eq!(
+            amz_date(UNIX_EPOCH + std::time::Duration::from_secs(1_440_938_160)),
+            "20150830T123600Z"
+        );
+        assert_eq!(uri_encode("a b+c~.jpg"), "a%20b%2Bc~.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_s3_media_sink() {
+        let server = MockServer::start(vec![
+            MockResponse::new(404, ""),
+            MockResponse::new(200, ""),
+            MockResponse::new(200, ""),
+        ])
+        .await;
+        let sink = S3MediaSink::new(&server.base_url, "bucket", "us-east-1", "AKID", "SECRET")
+            .unwrap()
+            .prefix("/archive/");
+        let key = MediaKey::new("pic/cover/l/3559.jpg", ImageType::Large);
+
+        assert!(!sink.contains(&key).await.unwrap());
+        sink.write(&key, Bytes::from_static(b"image"))
+            .await
+            .unwrap();
+        assert!(sink.contains(&key).await.unwrap());
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[1].line,
+            "PUT /bucket/archive/large/pic/cover/l/3559.jpg HTTP/1.1"
+        );
+        assert_eq!(requests[1].body, b"image");
+        assert_eq!(
+            requests[1].header("x-amz-content-sha256").unwrap(),
+            hex(&sha256(b"image"))
+        );
+        let authorization = requests[1].header("authorization").unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains("/us-east-1/s3/aws4_request"));
+        assert!(requests[0].line.starts_with("HEAD "));
+    }
+}
@@ -0,0 +1,100 @@
+//! # Request Statistics (请求统计)
+//!
+//! 每个 [`Client`](super::Client) 都会统计自身发出的请求，可以通过 [`Client::stats`](super::Client::stats) 获取快照，
+//! 便于嵌入的应用向用户展示 API 用量。
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// 请求统计计数器
+///
+/// 由 [`Client`](super::Client) 内部持有并在每次请求时更新。
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounter {
+    total_requests: AtomicU64,
+    bytes_downloaded: AtomicU64,
+    cache_hits: AtomicU64,
+    by_endpoint: Mutex<BTreeMap<&'static str, u64>>,
+}
+
+impl StatsCounter {
+    /// 记录一次发往 `endpoint` 的请求
+    pub(crate) fn record_request(&self, endpoint: &'static str) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        *self
+            .by_endpoint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(endpoint)
+            .or_default() += 1;
+    }
+
+    /// 记录下载的响应体字节数
+    pub(crate) fn record_bytes(&self, bytes: u64) {
+        self.bytes_downloaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// 记录一次缓存命中
+    #[cfg_attr(not(feature = "cache"), allow(dead_code))]
+    pub(crate) fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 获取当前统计的快照
+    pub(crate) fn snapshot(&self) -> RequestStats {
+        RequestStats {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            bytes_downloaded: self.bytes_downloaded.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            by_endpoint: self
+                .by_endpoint
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|(endpoint, count)| (endpoint.to_string(), *count))
+                .collect(),
+        }
+    }
+}
+
+/// Request statistics snapshot (请求统计快照)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestStats {
+    /// 发出的请求总数
+    pub total_requests: u64,
+
+    /// 下载的响应体总字节数
+    pub bytes_downloaded: u64,
+
+    /// 缓存命中次数
+    pub cache_hits: u64,
+
+    /// 按 API 统计的请求数，键为类似 `GET /v0/subjects/{subject_id}` 的 API 名称
+    pub by_endpoint: BTreeMap<String, u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_counter() {
+        let counter = StatsCounter::default();
+        counter.record_request("GET /v0/subjects/{subject_id}");
+        counter.record_request("GET /v0/subjects/{subject_id}");
+        counter.record_request("GET /v0/episodes");
+        counter.record_bytes(42);
+
+        let stats = counter.snapshot();
+        assert_eq!(stats.total_requests, 3);
+        assert_eq!(stats.bytes_downloaded, 42);
+        assert_eq!(stats.by_endpoint["GET /v0/subjects/{subject_id}"], 2);
+        assert_eq!(stats.by_endpoint["GET /v0/episodes"], 1);
+    }
+}
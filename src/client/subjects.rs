@@ -1,16 +1,27 @@
 //! # Subjects Resource (条目资源)
 
-use std::ops::Deref;
+use std::{
+    future::{Future, IntoFuture},
+    ops::Deref,
+};
 
 use derive_builder::Builder;
+use futures_core::Stream;
+use futures_util::TryFutureExt;
 
-use super::Client;
-use crate::{error::*, types::*};
+use super::{
+    pagination::{self, FetchAllOptions, Paginated},
+    Client, ExecutorFuture,
+};
+use crate::{error::*, sealed::Sealed, types::*};
+
+/// 按标签浏览时同时获取条目详情的请求数量
+const TAG_FETCH_CONCURRENCY: usize = 4;
 
 /// # 条目搜索执行器
 ///
 /// 此结构用于构建请求参数并发送请求
-#[derive(Debug, Builder)]
+#[derive(Clone, Debug, Builder)]
 #[builder(pattern = "owned", setter(strip_option))]
 pub struct SearchSubjectsExecutor<'a> {
     #[doc(hidden)]
@@ -33,6 +44,12 @@ pub struct SearchSubjectsExecutor<'a> {
 
     /// 过滤条件
     filter: SearchSubjectsFilter,
+
+    /// 分页的超时时间
+    ///
+    /// 仅对 `pages()`、`items()` 与 `fetch_all()` 中的每一页生效，参见 [`pagination`](super::pagination) 模块。
+    #[builder(default)]
+    timeout_per_page: Option<std::time::Duration>,
 }
 
 impl Deref for SearchSubjectsExecutor<'_> {
@@ -45,7 +62,7 @@ impl Deref for SearchSubjectsExecutor<'_> {
 
 impl SearchSubjectsExecutor<'_> {
     /// 返回一个 Builder 模式的 [`SearchSubjectsExecutorBuilder`], 用于构建请求参数并发送请求
-    pub(super) fn builder(client: &Client) -> SearchSubjectsExecutorBuilder {
+    pub(super) fn builder(client: &Client) -> SearchSubjectsExecutorBuilder<'_> {
         SearchSubjectsExecutorBuilder::default().client(client)
     }
 
@@ -53,29 +70,240 @@ impl SearchSubjectsExecutor<'_> {
     ///
     /// 根据构建的请求参数发送请求，并返回搜索结果
     pub async fn send(&self) -> Result<SearchSubjects, SearchSubjectsError> {
-        let url = format!("{}/v0/search/subjects", self.client.base_url);
+        let body = SearchSubjectsBody {
+            keyword: self.keyword.clone(),
+            sort: self.sort,
+            filter: self.filter.clone(),
+        };
 
-        let req = self
-            .client()
-            .post(url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .query(&[("limit", &self.limit)])
-            .query(&[("offset", &self.offset)])
-            .json(&SearchSubjectsBody {
-                keyword: self.keyword.clone(),
-                sort: self.sort,
-                filter: self.filter.clone(),
-            })
-            .build()?;
+        let subjects = search_subjects(self.client, &body, self.limit, self.offset).await?;
 
-        let res = self.client.client.execute(req).await?.error_for_status()?;
+        Ok(subjects)
+    }
 
-        let subjects: SearchSubjects = res.json().await?;
+    /// 发送请求并返回未解析的分页结果，参见 [`PagedRaw`]
+    pub async fn send_lazy(&self) -> Result<PagedRaw, SearchSubjectsError> {
+        let body = SearchSubjectsBody {
+            keyword: self.keyword.clone(),
+            sort: self.sort,
+            filter: self.filter.clone(),
+        };
+
+        let subjects = search_subjects(self.client, &body, self.limit, self.offset).await?;
 
         Ok(subjects)
     }
 }
 
+impl SearchSubjectsItem {
+    /// 获取搜索结果对应的完整条目
+    ///
+    /// 搜索结果缺少信息框、收藏数等字段，此方法通过 [`get_subject`](Client::get_subject) 获取完整的 [`Subject`]。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::new();
+    /// let hits = client
+    ///     .search_subjects()
+    ///     .keyword("CLANNAD")
+    ///     .sort(SortType::Match)
+    ///     .filter(SearchSubjectsFilter::default())
+    ///     .send()
+    ///     .await?;
+    /// let subject = hits.data[0].fetch_full(&client).await?;
+    /// println!("{:?}", subject.infobox);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_full(&self, client: &Client) -> Result<Subject, DepsError> {
+        client.get_subject(self.id).await
+    }
+}
+
+/// Subject with its relations (条目及其关联信息)
+///
+/// 由 [`Client::get_subject_full`] 并发获取。
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SubjectFull {
+    /// 条目
+    pub subject: Subject,
+
+    /// 相关人物
+    pub persons: Vec<RelatedPerson>,
+
+    /// 相关角色
+    pub characters: Vec<RelatedCharacter>,
+
+    /// 相关条目
+    pub relations: Vec<SubjectRelation>,
+
+    /// 全部章节，仅在设置了 [`SubjectFullOptions::episodes`] 时获取
+    pub episodes: Option<Vec<Episode>>,
+}
+
+/// [`Client::get_subject_full`] 的选项
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SubjectFullOptions {
+    /// 是否同时获取全部章节，默认不获取
+    pub episodes: bool,
+}
+
+impl SubjectFullOptions {
+    /// 设置是否同时获取全部章节
+    pub fn episodes(mut self, episodes: bool) -> Self {
+        self.episodes = episodes;
+        self
+    }
+}
+
+/// # Aggregate subject methods for [`Client`].
+impl Client {
+    /// 获取条目及其关联信息
+    ///
+    /// 并发请求 [`get_subject`](Client::get_subject)、[`get_subject_persons`](Client::get_subject_persons)、
+    /// [`get_subject_characters`](Client::get_subject_characters)、[`get_subject_subjects`](Client::get_subject_subjects)，
+    /// 以及可选的 [`get_episodes`](Client::get_episodes)，任意一个请求失败时返回其错误。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// use bgmtv::client::subjects::SubjectFullOptions;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let client = Client::new();
+    /// let full = client
+    ///     .get_subject_full(3559, SubjectFullOptions::default().episodes(true))
+    ///     .await?;
+    /// println!("{} characters, {:?} episodes", full.characters.len(), full.episodes.map(|e| e.len()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_subject_full(
+        &self,
+        subject_id: impl Into<SubjectId>,
+        options: SubjectFullOptions,
+    ) -> Result<SubjectFull, GetEpisodesError> {
+        let subject_id: SubjectId = subject_id.into();
+
+        let episodes = async {
+            if !options.episodes {
+                return Ok(None);
+            }
+            let episodes = self.get_episodes(subject_id).build()?.fetch_all().await?;
+            Ok::<_, GetEpisodesError>(Some(episodes))
+        };
+
+        let (subject, persons, characters, relations, episodes) = futures_util::try_join!(
+            self.get_subject(subject_id).err_into::<GetEpisodesError>(),
+            self.get_subject_persons(subject_id)
+                .err_into::<GetEpisodesError>(),
+            self.get_subject_characters(subject_id)
+                .err_into::<GetEpisodesError>(),
+            self.get_subject_subjects(subject_id)
+                .err_into::<GetEpisodesError>(),
+            episodes,
+        )?;
+
+        Ok(SubjectFull {
+            subject,
+            persons,
+            characters,
+            relations,
+            episodes,
+        })
+    }
+}
+
+/// 发送条目搜索请求 `POST /v0/search/subjects`
+async fn search_subjects<T: serde::de::DeserializeOwned>(
+    client: &Client,
+    body: &SearchSubjectsBody,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> Result<T, DepsError> {
+    let url = client.api_url(&["v0", "search", "subjects"])?;
+
+    let req = client
+        .client()
+        .post(url)
+        .query(&[("limit", &limit)])
+        .query(&[("offset", &offset)])
+        .json(body)
+        .build()?;
+
+    client.execute_json("POST /v0/search/subjects", req).await
+}
+
+impl<'a> SearchSubjectsExecutor<'a> {
+    /// 逐页获取搜索结果
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据
+    pub fn pages(
+        &self,
+    ) -> impl Stream<Item = Result<SearchSubjects, SearchSubjectsError>> + Send + 'a {
+        pagination::pages(self.clone())
+    }
+
+    /// 逐条获取搜索结果
+    ///
+    /// 与 [`pages`](Self::pages) 相同，但返回每一条数据
+    pub fn items(
+        &self,
+    ) -> impl Stream<Item = Result<SearchSubjectsItem, SearchSubjectsError>> + Send + 'a {
+        pagination::items(self.clone())
+    }
+
+    /// 获取全部搜索结果
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据，并返回所有数据
+    pub async fn fetch_all(&self) -> Result<Vec<SearchSubjectsItem>, SearchSubjectsError> {
+        self.fetch_all_with(FetchAllOptions::default()).await
+    }
+
+    /// 获取全部搜索结果，可以限制数量与设置分页间的等待时间
+    ///
+    /// 参见 [`FetchAllOptions`]
+    pub async fn fetch_all_with(
+        &self,
+        options: FetchAllOptions,
+    ) -> Result<Vec<SearchSubjectsItem>, SearchSubjectsError> {
+        pagination::fetch_all(self.clone(), self.client.clock(), options).await
+    }
+}
+
+impl Sealed for SearchSubjectsExecutor<'_> {}
+
+impl Paginated for SearchSubjectsExecutor<'_> {
+    type Page = SearchSubjects;
+    type Error = SearchSubjectsError;
+
+    fn set_offset(&mut self, offset: u64) {
+        self.offset = Some(offset);
+    }
+
+    fn timeout_per_page(&self) -> Option<std::time::Duration> {
+        self.timeout_per_page
+    }
+
+    fn clock(&self) -> &dyn crate::time::Clock {
+        self.client.clock()
+    }
+
+    fn fetch_page(
+        &self,
+    ) -> impl Future<Output = Result<SearchSubjects, SearchSubjectsError>> + Send {
+        self.send()
+    }
+}
+
 impl SearchSubjectsExecutorBuilder<'_> {
     /// 发送请求
     ///
@@ -85,11 +313,25 @@ impl SearchSubjectsExecutorBuilder<'_> {
     }
 }
 
+impl<'a> IntoFuture for SearchSubjectsExecutorBuilder<'a> {
+    type Output = Result<SearchSubjects, SearchSubjectsError>;
+    type IntoFuture = ExecutorFuture<'a, Self::Output>;
+
+    /// 等同于 [`send`](SearchSubjectsExecutorBuilder::send)
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
 /// # 浏览条目执行器
 ///
 /// 此结构用于构建请求参数并发送请求
-#[derive(Debug, Builder)]
-#[builder(pattern = "owned", setter(strip_option))]
+#[derive(Clone, Debug, Builder)]
+#[builder(
+    pattern = "owned",
+    setter(strip_option),
+    build_fn(validate = "Self::validate")
+)]
 pub struct GetSubjectsExecutor<'a> {
     #[doc(hidden)]
     client: &'a Client,
@@ -113,15 +355,25 @@ pub struct GetSubjectsExecutor<'a> {
     #[builder(default, setter(into))]
     platform: Option<String>,
 
-    /// 排序方式，可选值为 `date`, `rank`
+    /// 排序方式
+    ///
+    /// 参见 [`BrowseSort`](crate::types::BrowseSort)
+    #[builder(default)]
+    sort: Option<BrowseSort>,
+
+    /// 标签
+    ///
+    /// 浏览条目 API 暂不支持按标签过滤，设置此项后会改为通过条目搜索 API 查询带有该标签的条目，
+    /// 再逐个获取条目详情，参见 [`send`](GetSubjectsExecutor::send)。
+    /// 此时 `sort` 仅支持 [`BrowseSort::Rank`]，设置其他排序方式时构建会返回错误。
     #[builder(default, setter(into))]
-    sort: Option<String>,
+    tag: Option<String>,
 
     /// 年份
     #[builder(default)]
     year: Option<u64>,
 
-    /// 月份
+    /// 月份，取值为 1 至 12，超出范围时构建会返回错误
     #[builder(default)]
     month: Option<u64>,
 
@@ -132,6 +384,12 @@ pub struct GetSubjectsExecutor<'a> {
     /// 分页参数，偏移量
     #[builder(default)]
     offset: Option<u64>,
+
+    /// 分页的超时时间
+    ///
+    /// 仅对 `pages()`、`items()` 与 `fetch_all()` 中的每一页生效，参见 [`pagination`](super::pagination) 模块。
+    #[builder(default)]
+    timeout_per_page: Option<std::time::Duration>,
 }
 
 impl Deref for GetSubjectsExecutor<'_> {
@@ -144,20 +402,71 @@ impl Deref for GetSubjectsExecutor<'_> {
 
 impl<'a> GetSubjectsExecutor<'a> {
     /// 返回一个 Builder 模式的 [`GetSubjectsExecutorBuilder`], 用于构建请求参数并发送请求
-    pub(super) fn builder(client: &'a Client) -> GetSubjectsExecutorBuilder {
+    pub(super) fn builder(client: &'a Client) -> GetSubjectsExecutorBuilder<'a> {
         GetSubjectsExecutorBuilder::default().client(client)
     }
 
     /// 发送请求
     ///
     /// 根据构建的请求参数发送请求，并返回搜索结果
+    ///
+    /// 设置了 [`tag`](GetSubjectsExecutorBuilder::tag) 时，会通过条目搜索 API 以条目类型、标签与年月作为过滤条件查询，
+    /// 再通过 [`get_subjects_by_ids`](Client::get_subjects_by_ids) 以最多 4 个同时进行的请求获取每个条目的详情。
+    /// 此时 `cat`、`series` 与 `platform` 不会生效，`sort` 仅支持 `rank`。
     pub async fn send(&self) -> Result<PagedSubject, GetSubjectsError> {
-        let url = format!("{}/v0/subjects", self.client.base_url);
+        if let Some(tag) = &self.tag {
+            return Ok(self.send_by_tag(tag).await?);
+        }
+
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回原始的 JSON，用于访问 [`Subject`] 尚未包含的字段
+    ///
+    /// 总是直接请求浏览条目 API，[`tag`](GetSubjectsExecutorBuilder::tag) 不会生效。
+    pub async fn send_raw(&self) -> Result<serde_json::Value, GetSubjectsError> {
+        Ok(self.send_as().await?)
+    }
+
+    /// 发送请求并返回未解析的分页结果，参见 [`PagedRaw`]
+    ///
+    /// 与 [`send_raw`](Self::send_raw) 相同，[`tag`](GetSubjectsExecutorBuilder::tag) 不会生效。
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bgmtv::prelude::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// #[derive(serde::Deserialize)]
+    /// struct Slim {
+    ///     id: u64,
+    ///     name: String,
+    /// }
+    ///
+    /// let client = Client::new();
+    /// let page = client
+    ///     .get_subjects()
+    ///     .r#type(SubjectType::Anime)
+    ///     .build()?
+    ///     .send_lazy()
+    ///     .await?;
+    /// for subject in page.decode::<Slim>()?.data {
+    ///     println!("{} {}", subject.id, subject.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_lazy(&self) -> Result<PagedRaw, GetSubjectsError> {
+        Ok(self.send_as().await?)
+    }
+
+    async fn send_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, DepsError> {
+        let url = self.client.api_url(&["v0", "subjects"])?;
 
         let req = self
             .client()
             .get(url)
-            .header(reqwest::header::ACCEPT, "application/json")
             .query(&[("type", &self.r#type)])
             .query(&[("cat", &self.cat)])
             .query(&[("series", &self.series)])
@@ -169,11 +478,125 @@ impl<'a> GetSubjectsExecutor<'a> {
             .query(&[("offset", &self.offset)])
             .build()?;
 
-        let res = self.client.client.execute(req).await?.error_for_status()?;
+        self.client.execute_json("GET /v0/subjects", req).await
+    }
 
-        let subjects: PagedSubject = res.json().await?;
+    /// 通过条目搜索 API 浏览带有 `tag` 标签的条目
+    async fn send_by_tag(&self, tag: &str) -> Result<PagedSubject, DepsError> {
+        let mut filter = SearchSubjectsFilter {
+            r#type: vec![self.r#type],
+            tag: vec![tag.to_string()],
+            ..Default::default()
+        };
+        if let Some(year) = self.year {
+            let (start, end) = match self.month {
+                Some(12) => (format!("{}-12-01", year), format!("{}-01-01", year + 1)),
+                Some(month) => (
+                    format!("{}-{:02}-01", year, month),
+                    format!("{}-{:02}-01", year, month + 1),
+                ),
+                None => (format!("{}-01-01", year), format!("{}-01-01", year + 1)),
+            };
+            filter.air_date = vec![format!(">={}", start), format!("<{}", end)];
+        }
+
+        let body = SearchSubjectsBody {
+            keyword: String::new(),
+            sort: match self.sort {
+                Some(BrowseSort::Rank) => SortType::Rank,
+                _ => SortType::Match,
+            },
+            filter,
+        };
+
+        let hits: SearchSubjects =
+            search_subjects(self.client, &body, self.limit, self.offset).await?;
+
+        let ids: Vec<u64> = hits.data.iter().map(|hit| hit.id).collect();
+        let data = self
+            .client
+            .get_subjects_by_ids(ids, TAG_FETCH_CONCURRENCY)
+            .await
+            .into_result()?;
+
+        Ok(PagedSubject {
+            total: hits.total,
+            limit: hits.limit,
+            offset: hits.offset,
+            data,
+        })
+    }
+}
 
-        Ok(subjects)
+impl GetSubjectsExecutorBuilder<'_> {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(month)) = self.month {
+            if !(1..=12).contains(&month) {
+                return Err(format!("month must be between 1 and 12, got {}", month));
+            }
+        }
+        if let (Some(Some(_)), Some(Some(sort))) = (&self.tag, &self.sort) {
+            if *sort != BrowseSort::Rank {
+                return Err(format!("sort {:?} is not supported with tag", sort));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> GetSubjectsExecutor<'a> {
+    /// 逐页获取条目
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据
+    pub fn pages(&self) -> impl Stream<Item = Result<PagedSubject, GetSubjectsError>> + Send + 'a {
+        pagination::pages(self.clone())
+    }
+
+    /// 逐条获取条目
+    ///
+    /// 与 [`pages`](Self::pages) 相同，但返回每一条数据
+    pub fn items(&self) -> impl Stream<Item = Result<Subject, GetSubjectsError>> + Send + 'a {
+        pagination::items(self.clone())
+    }
+
+    /// 获取全部条目
+    ///
+    /// 从当前的偏移量开始，依次请求后续的分页，直到没有更多数据，并返回所有数据
+    pub async fn fetch_all(&self) -> Result<Vec<Subject>, GetSubjectsError> {
+        self.fetch_all_with(FetchAllOptions::default()).await
+    }
+
+    /// 获取全部条目，可以限制数量与设置分页间的等待时间
+    ///
+    /// 参见 [`FetchAllOptions`]
+    pub async fn fetch_all_with(
+        &self,
+        options: FetchAllOptions,
+    ) -> Result<Vec<Subject>, GetSubjectsError> {
+        pagination::fetch_all(self.clone(), self.client.clock(), options).await
+    }
+}
+
+impl Sealed for GetSubjectsExecutor<'_> {}
+
+impl Paginated for GetSubjectsExecutor<'_> {
+    type Page = PagedSubject;
+    type Error = GetSubjectsError;
+
+    fn set_offset(&mut self, offset: u64) {
+        self.offset = Some(offset);
+    }
+
+    fn timeout_per_page(&self) -> Option<std::time::Duration> {
+        self.timeout_per_page
+    }
+
+    fn clock(&self) -> &dyn crate::time::Clock {
+        self.client.clock()
+    }
+
+    fn fetch_page(&self) -> impl Future<Output = Result<PagedSubject, GetSubjectsError>> + Send {
+        self.send()
     }
 }
 
@@ -185,3 +608,208 @@ impl GetSubjectsExecutorBuilder<'_> {
         self.build()?.send().await
     }
 }
+
+impl<'a> IntoFuture for GetSubjectsExecutorBuilder<'a> {
+    type Output = Result<PagedSubject, GetSubjectsError>;
+    type IntoFuture = ExecutorFuture<'a, Self::Output>;
+
+    /// 等同于 [`send`](GetSubjectsExecutorBuilder::send)
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubjectFullOptions;
+    use crate::{
+        client::{
+            transport::{HttpTransport, TransportFuture},
+            Client,
+        },
+        mock::{MockResponse, MockServer},
+        types::SubjectType,
+    };
+
+    fn subject(id: u64) -> String {
+        let subject = crate::types::Subject {
+            id,
+            ..crate::mock::subject()
+        };
+        serde_json::to_string(&subject).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_subjects_by_tag() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                r#"{"total":1,"limit":10,"offset":0,"data":[{"id":7,"type":2,"date":"","image":"","summary":"","name":"","name_cn":"","tags":[],"score":0,"rank":0}]}"#,
+            ),
+            MockResponse::new(200, subject(7)),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let subjects = client
+            .get_subjects()
+            .r#type(SubjectType::Anime)
+            .tag("原创")
+            .year(2024)
+            .month(4)
+            .await
+            .unwrap();
+
+        assert_eq!(subjects.total, 1);
+        assert_eq!(subjects.data[0].id, 7);
+
+        let requests = server.requests();
+        assert!(requests[0].line.starts_with("POST /v0/search/subjects"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["filter"]["tag"][0], "原创");
+        assert_eq!(body["filter"]["air_date"][0], ">=2024-04-01");
+        assert_eq!(body["filter"]["air_date"][1], "<2024-05-01");
+        assert!(requests[1].line.starts_with("GET /v0/subjects/7"));
+    }
+
+    #[test]
+    fn test_get_subjects_validation() {
+        let client = Client::new();
+        for month in [0, 13] {
+            let err = client
+                .get_subjects()
+                .r#type(SubjectType::Anime)
+                .year(2024)
+                .month(month)
+                .build()
+                .unwrap_err();
+            assert!(err.to_string().contains("month"));
+        }
+        assert!(client
+            .get_subjects()
+            .r#type(SubjectType::Anime)
+            .month(12)
+            .build()
+            .is_ok());
+
+        let err = client
+            .browse_by_tag("原创", SubjectType::Anime)
+            .sort(crate::types::BrowseSort::Date)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("Date"));
+        assert!(client
+            .get_subjects()
+            .r#type(SubjectType::Anime)
+            .sort(crate::types::BrowseSort::Date)
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_browse_by_tag() {
+        let hits = |offset: u64, id: u64| {
+            format!(
+                r#"{{"total":2,"limit":1,"offset":{},"data":[{{"id":{},"type":2,"date":"","image":"","summary":"","name":"","name_cn":"","tags":[],"score":0,"rank":0}}]}}"#,
+                offset, id
+            )
+        };
+        let server = MockServer::start(vec![
+            MockResponse::new(200, hits(0, 7)),
+            MockResponse::new(200, subject(7)),
+            MockResponse::new(200, hits(1, 8)),
+            MockResponse::new(200, subject(8)),
+        ])
+        .await;
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let subjects = client
+            .browse_by_tag("原创", SubjectType::Anime)
+            .limit(1)
+            .build()
+            .unwrap()
+            .fetch_all()
+            .await
+            .unwrap();
+        assert_eq!(
+            subjects.iter().map(|s| s.id).collect::<Vec<_>>(),
+            vec![7, 8]
+        );
+
+        let requests = server.requests();
+        assert!(requests[2]
+            .line
+            .starts_with("POST /v0/search/subjects?limit=1&offset=1"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["keyword"], "");
+        assert_eq!(body["filter"]["type"][0], 2);
+        assert_eq!(body["filter"]["tag"][0], "原创");
+    }
+
+    /// 按路径返回固定响应的传输层，用于并发请求的测试
+    struct Routes(Vec<(&'static str, String)>);
+
+    impl HttpTransport for Routes {
+        fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+            let body = self
+                .0
+                .iter()
+                .find(|(path, _)| *path == req.url().path())
+                .map(|(_, body)| body.clone());
+            Box::pin(async move {
+                let res = match body {
+                    Some(body) => http::Response::builder().status(200).body(body),
+                    None => http::Response::builder()
+                        .status(404)
+                        .body(r#"{"title":"Not Found","description":""}"#.to_string()),
+                };
+                Ok(reqwest::Response::from(res.unwrap()))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_subject_full() {
+        let episodes = format!(
+            r#"{{"total":1,"limit":100,"offset":0,"data":[{}]}}"#,
+            serde_json::to_string(&crate::mock::episode(1)).unwrap()
+        );
+        let client = Client::builder()
+            .transport(Routes(vec![
+                ("/v0/subjects/7", subject(7)),
+                ("/v0/subjects/7/persons", "[]".to_string()),
+                ("/v0/subjects/7/characters", "[]".to_string()),
+                ("/v0/subjects/7/subjects", "[]".to_string()),
+                ("/v0/episodes", episodes),
+            ]))
+            .build()
+            .unwrap();
+
+        let full = client
+            .get_subject_full(7, SubjectFullOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(full.subject.id, 7);
+        assert!(full.persons.is_empty());
+        assert_eq!(full.episodes, None);
+
+        let full = client
+            .get_subject_full(7, SubjectFullOptions::default().episodes(true))
+            .await
+            .unwrap();
+        assert_eq!(full.episodes.unwrap()[0].id, 1);
+
+        assert!(client
+            .get_subject_full(8, SubjectFullOptions::default())
+            .await
+            .is_err());
+    }
+}
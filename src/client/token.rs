@@ -0,0 +1,108 @@
+//! # Token Provider (授权 Token 提供者)
+//!
+//! 此模块定义了 [`TokenProvider`] trait，[`Client`](super::Client) 在每次请求时通过它获取 token，
+//! 因此 token 可以被轮换、从 keyring 中读取或由外部刷新，而无需重新构建 [`Client`](super::Client)。
+//! 也可以通过 [`Client::set_token`](super::Client::set_token) 在运行中直接替换 token，连接池会被保留，响应缓存会被清空。
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+/// Future returned by [`TokenProvider::token`]
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>>;
+
+/// # Token Provider
+///
+/// 在每次请求发送前被调用，返回 `None` 表示此次请求不携带 token。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// # use bgmtv::client::token::TokenFuture;
+/// struct EnvToken;
+///
+/// impl TokenProvider for EnvToken {
+///     fn token(&self) -> TokenFuture<'_> {
+///         Box::pin(async { std::env::var("BGMTV_TOKEN").ok() })
+///     }
+/// }
+///
+/// let client = Client::builder()
+///     .token_provider(EnvToken)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait TokenProvider: Send + Sync {
+    /// 获取当前的 token
+    fn token(&self) -> TokenFuture<'_>;
+}
+
+/// 固定的 token，由 [`ClientBuilder::token`](super::ClientBuilder::token) 使用
+#[derive(Clone)]
+pub struct StaticToken(pub String);
+
+impl TokenProvider for StaticToken {
+    fn token(&self) -> TokenFuture<'_> {
+        let token = self.0.clone();
+        Box::pin(async move { Some(token) })
+    }
+}
+
+impl fmt::Debug for StaticToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StaticToken").field(&"***").finish()
+    }
+}
+
+/// [`TokenProvider`] trait object held by [`Client`](super::Client)
+#[derive(Clone)]
+pub(crate) struct SharedTokenProvider(pub(crate) Arc<dyn TokenProvider>);
+
+impl fmt::Debug for SharedTokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TokenProvider")
+    }
+}
+
+/// [`Client`](super::Client) 中可在运行时替换的 [`TokenProvider`]
+#[derive(Default)]
+pub(crate) struct TokenSlot(RwLock<Option<SharedTokenProvider>>);
+
+impl TokenSlot {
+    pub(crate) fn new(provider: Option<SharedTokenProvider>) -> Self {
+        Self(RwLock::new(provider))
+    }
+
+    /// 当前的 provider，返回的是 [`Arc`] 的克隆，调用期间不会持有锁
+    pub(crate) fn get(&self) -> Option<SharedTokenProvider> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 替换 provider，已经在发送中的请求不受影响
+    pub(crate) fn set(&self, provider: Option<SharedTokenProvider>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = provider;
+    }
+
+    pub(crate) fn is_some(&self) -> bool {
+        self.get().is_some()
+    }
+}
+
+impl Clone for TokenSlot {
+    fn clone(&self) -> Self {
+        Self::new(self.get())
+    }
+}
+
+impl fmt::Debug for TokenSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(provider) => provider.fmt(f),
+            None => f.write_str("None"),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! # Tracing (链路追踪)
+//!
+//! 启用 `tracing` feature 后，每次 API 调用都会产生一个 `bgmtv.request` span，
+//! 包含 API 名称、请求路径（含资源 ID）、请求 ID、状态码与耗时；请求头中的 `Authorization` 与 URL 中的 `access_token` 等敏感参数会被隐去。
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use tracing::{field::Empty, Span};
+
+/// 为一次 API 调用创建 span
+pub(crate) fn span(endpoint: &'static str, req: &reqwest::Request, request_id: &str) -> Span {
+    tracing::info_span!(
+        "bgmtv.request",
+        endpoint,
+        method = %req.method(),
+        path = req.url().path(),
+        request_id,
+        status = Empty,
+        latency_ms = Empty,
+        attempts = Empty,
+    )
+}
+
+/// 记录一次发送的请求，`Authorization` 请求头与敏感查询参数会被隐去
+pub(crate) fn request(req: &reqwest::Request, attempt: u32) {
+    tracing::debug!(
+        url = %super::redact_url(req.url()),
+        headers = ?redact(req.headers()),
+        attempt,
+        "sending request"
+    );
+}
+
+/// 记录收到的响应，并更新当前 span 的状态码与耗时
+pub(crate) fn response(res: &reqwest::Response, elapsed: Duration, attempt: u32) {
+    let span = Span::current();
+    span.record("status", res.status().as_u16());
+    span.record("latency_ms", elapsed.as_millis() as u64);
+    span.record("attempts", attempt + 1);
+    tracing::debug!(
+        status = res.status().as_u16(),
+        ?elapsed,
+        "received response"
+    );
+}
+
+/// 复制请求头并隐去 `Authorization`
+fn redact(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    if let Some(value) = headers.get_mut(AUTHORIZATION) {
+        *value = HeaderValue::from_static("[redacted]");
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers.insert("accept", HeaderValue::from_static("application/json"));
+
+        let redacted = redact(&headers);
+        assert_eq!(redacted[AUTHORIZATION], "[redacted]");
+        assert_eq!(redacted["accept"], "application/json");
+        assert!(!format!("{:?}", redacted).contains("secret"));
+    }
+}
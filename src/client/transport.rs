@@ -0,0 +1,69 @@
+//! # HTTP Transport (HTTP 传输层)
+//!
+//! 此模块定义了 [`HttpTransport`] trait，[`Client`](super::Client) 通过它发送所有请求。
+//! 默认使用内部的 [`reqwest::Client`]，通过 [`ClientBuilder::transport`](super::ClientBuilder::transport)
+//! 可以替换为基于 hyper、ureq 等其他 HTTP 库的实现，或是测试中返回固定响应的替身。
+//!
+//! 请求与响应仍使用 [`reqwest::Request`] 与 [`reqwest::Response`] 表示：其他实现可以读取请求的
+//! method、URL、请求头与请求体，并通过 `reqwest::Response::from(http::Response)` 构造响应。
+
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+use crate::error::DepsError;
+
+/// Future returned by [`HttpTransport::execute`]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<reqwest::Response, DepsError>> + Send + 'a>>;
+
+/// # HTTP Transport
+///
+/// 发送一个已经构建好的请求，token、默认请求头、限流与重试等逻辑都在 [`Client`](super::Client) 中处理。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// use bgmtv::client::transport::{HttpTransport, TransportFuture};
+///
+/// struct Offline;
+///
+/// impl HttpTransport for Offline {
+///     fn execute(&self, _req: reqwest::Request) -> TransportFuture<'_> {
+///         Box::pin(async {
+///             let res = http::Response::builder().status(503).body("").unwrap();
+///             Ok(reqwest::Response::from(res))
+///         })
+///     }
+/// }
+///
+/// let client = Client::builder().transport(Offline).build().unwrap();
+/// ```
+pub trait HttpTransport: Send + Sync {
+    /// 发送请求
+    fn execute(&self, req: reqwest::Request) -> TransportFuture<'_>;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(reqwest::Client::execute(self, req).await?) })
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl HttpTransport for reqwest_middleware::ClientWithMiddleware {
+    fn execute(&self, req: reqwest::Request) -> TransportFuture<'_> {
+        Box::pin(
+            async move { Ok(reqwest_middleware::ClientWithMiddleware::execute(self, req).await?) },
+        )
+    }
+}
+
+/// [`HttpTransport`] trait object held by [`Client`](super::Client)
+#[derive(Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn HttpTransport>);
+
+impl fmt::Debug for SharedTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HttpTransport")
+    }
+}
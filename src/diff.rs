@@ -0,0 +1,341 @@
+//! # Diff (数据比较)
+//!
+//! 此模块提供了比较同一条目两次获取结果的工具，供缓存刷新等流程判断哪些字段发生了变化。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::diff::{diff_subjects, SubjectChange};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let cached = client.get_subject(3559).await?;
+//! let fresh = client.get_subject(3559).await?;
+//!
+//! for change in diff_subjects(&cached, &fresh) {
+//!     if let SubjectChange::Eps { old, new } = change {
+//!         println!("eps: {} -> {}", old, new);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`trend`] 则比较两次获取的评分与收藏数据，计算新增的收藏、排名变化与分数漂移，适合定期抓取数据的统计面板。
+
+use crate::types::*;
+
+/// Subject change (条目字段变化)
+#[derive(Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum SubjectChange {
+    /// 名称发生变化
+    Name { old: String, new: String },
+
+    /// 中文名发生变化
+    NameCn { old: String, new: String },
+
+    /// 信息框中的条目发生变化
+    Infobox(InfoboxChange),
+
+    /// 评分发生变化
+    Rating {
+        old: SubjectRating,
+        new: SubjectRating,
+    },
+
+    /// 话数发生变化
+    Eps { old: u64, new: u64 },
+
+    /// 总话数发生变化
+    TotalEpisodes { old: u64, new: u64 },
+}
+
+/// Infobox change (信息框变化)
+#[derive(Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum InfoboxChange {
+    /// 新增了 `key`
+    Added { key: String, value: InfoboxValue },
+
+    /// 删除了 `key`
+    Removed { key: String, value: InfoboxValue },
+
+    /// `key` 的值发生变化
+    Changed {
+        key: String,
+        old: InfoboxValue,
+        new: InfoboxValue,
+    },
+}
+
+/// 比较同一条目的两次获取结果 `old` 与 `new`，返回发生变化的字段
+///
+/// 信息框按 `key` 匹配，重复的 `key` 按出现顺序依次匹配。
+pub fn diff_subjects(old: &Subject, new: &Subject) -> Vec<SubjectChange> {
+    let mut changes = Vec::new();
+
+    if old.name != new.name {
+        changes.push(SubjectChange::Name {
+            old: old.name.clone(),
+            new: new.name.clone(),
+        });
+    }
+
+    if old.name_cn != new.name_cn {
+        changes.push(SubjectChange::NameCn {
+            old: old.name_cn.clone(),
+            new: new.name_cn.clone(),
+        });
+    }
+
+    changes.extend(
+        diff_infobox(&old.infobox, &new.infobox)
+            .into_iter()
+            .map(SubjectChange::Infobox),
+    );
+
+    if old.rating != new.rating {
+        changes.push(SubjectChange::Rating {
+            old: old.rating.clone(),
+            new: new.rating.clone(),
+        });
+    }
+
+    if old.eps != new.eps {
+        changes.push(SubjectChange::Eps {
+            old: old.eps,
+            new: new.eps,
+        });
+    }
+
+    if old.total_episodes != new.total_episodes {
+        changes.push(SubjectChange::TotalEpisodes {
+            old: old.total_episodes,
+            new: new.total_episodes,
+        });
+    }
+
+    changes
+}
+
+fn diff_infobox(old: &[Infobox], new: &[Infobox]) -> Vec<InfoboxChange> {
+    let mut changes = Vec::new();
+    let mut matched = vec![false; new.len()];
+
+    for entry in old {
+        let found = new
+            .iter()
+            .enumerate()
+            .position(|(i, n)| !matched[i] && n.key == entry.key);
+
+        match found {
+            Some(i) => {
+                matched[i] = true;
+                if new[i].value != entry.value {
+                    changes.push(InfoboxChange::Changed {
+                        key: entry.key.clone(),
+                        old: entry.value.clone(),
+                        new: new[i].value.clone(),
+                    });
+                }
+            }
+            None => changes.push(InfoboxChange::Removed {
+                key: entry.key.clone(),
+                value: entry.value.clone(),
+            }),
+        }
+    }
+
+    for (entry, _) in new.iter().zip(matched).filter(|(_, m)| !m) {
+        changes.push(InfoboxChange::Added {
+            key: entry.key.clone(),
+            value: entry.value.clone(),
+        });
+    }
+
+    changes
+}
+
+/// Trend delta (条目趋势变化)
+///
+/// 由 [`trend`] 计算，所有差值均为 `new - old`。
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct TrendDelta {
+    /// 评分变化
+    pub rating: RatingDelta,
+
+    /// 收藏变化
+    pub collection: CollectionDelta,
+}
+
+impl TrendDelta {
+    /// 新增的收藏人数，参见 [`CollectionDelta::total`]
+    pub fn new_collectors(&self) -> i64 {
+        self.collection.total()
+    }
+}
+
+/// Rating delta (评分变化)
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct RatingDelta {
+    /// 排名变化，正数表示排名上升；任意一方没有排名 (`rank` 为 0) 时为 `None`
+    pub rank: Option<i64>,
+
+    /// 新增的评分人数
+    pub total: i64,
+
+    /// 分数变化
+    pub score: f64,
+
+    /// 定点表示的分数变化，参见 [`score_to_x10`]
+    pub score_x10: i32,
+}
+
+/// Collection delta (收藏变化)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CollectionDelta {
+    /// 想看
+    pub wish: i64,
+
+    /// 看过
+    pub collect: i64,
+
+    /// 在看
+    pub doing: i64,
+
+    /// 搁置
+    pub on_hold: i64,
+
+    /// 抛弃
+    pub dropped: i64,
+}
+
+impl CollectionDelta {
+    /// 各收藏类型的变化之和
+    pub fn total(&self) -> i64 {
+        self.wish + self.collect + self.doing + self.on_hold + self.dropped
+    }
+}
+
+fn delta<T: TryInto<i64>>(old: T, new: T) -> i64 {
+    let to_i64 = |v: T| v.try_into().unwrap_or(i64::MAX);
+    to_i64(new) - to_i64(old)
+}
+
+/// 比较同一条目的两次获取结果 `old` 与 `new`，计算评分与收藏的变化
+pub fn trend(old: &Subject, new: &Subject) -> TrendDelta {
+    TrendDelta {
+        rating: rating_trend(&old.rating, &new.rating),
+        collection: collection_trend(&old.collection, &new.collection),
+    }
+}
+
+/// 比较两次获取的评分
+pub fn rating_trend(old: &SubjectRating, new: &SubjectRating) -> RatingDelta {
+    RatingDelta {
+        rank: (old.rank != 0 && new.rank != 0).then(|| delta(new.rank, old.rank)),
+        total: delta(old.total, new.total),
+        score: new.score - old.score,
+        score_x10: i32::from(new.score_x10()) - i32::from(old.score_x10()),
+    }
+}
+
+/// 比较两次获取的收藏数据
+pub fn collection_trend(old: &SubjectCollection, new: &SubjectCollection) -> CollectionDelta {
+    CollectionDelta {
+        wish: delta(old.wish, new.wish),
+        collect: delta(old.collect, new.collect),
+        doing: delta(old.doing, new.doing),
+        on_hold: delta(old.on_hold, new.on_hold),
+        dropped: delta(old.dropped, new.dropped),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject() -> Subject {
+        let mut subject = crate::mock::subject();
+        subject.infobox = vec![
+            Infobox {
+                key: "话数".to_string(),
+                value: InfoboxValue::Single("12".to_string()),
+            },
+            Infobox {
+                key: "别名".to_string(),
+                value: InfoboxValue::List(vec![InfoboxValueItem::V { v: "a".to_string() }]),
+            },
+        ];
+        subject
+    }
+
+    #[test]
+    fn test_diff_subjects() {
+        let old = subject();
+        assert!(diff_subjects(&old, &old).is_empty());
+
+        let mut new = subject();
+        new.eps = 13;
+        new.infobox[0].value = InfoboxValue::Single("13".to_string());
+        new.infobox.remove(1);
+        new.infobox.push(Infobox {
+            key: "放送星期".to_string(),
+            value: InfoboxValue::Single("星期日".to_string()),
+        });
+
+        assert_eq!(
+            diff_subjects(&old, &new),
+            vec![
+                SubjectChange::Infobox(InfoboxChange::Changed {
+                    key: "话数".to_string(),
+                    old: InfoboxValue::Single("12".to_string()),
+                    new: InfoboxValue::Single("13".to_string()),
+                }),
+                SubjectChange::Infobox(InfoboxChange::Removed {
+                    key: "别名".to_string(),
+                    value: InfoboxValue::List(vec![InfoboxValueItem::V { v: "a".to_string() }]),
+                }),
+                SubjectChange::Infobox(InfoboxChange::Added {
+                    key: "放送星期".to_string(),
+                    value: InfoboxValue::Single("星期日".to_string()),
+                }),
+                SubjectChange::Eps { old: 12, new: 13 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trend() {
+        let old = subject();
+        assert_eq!(trend(&old, &old), TrendDelta::default());
+
+        let mut new = subject();
+        new.rating.rank = 100;
+        new.rating.total = 20;
+        new.rating.score = 7.5;
+        new.collection.wish = 3;
+        new.collection.collect = 10;
+        let delta = trend(&old, &new);
+        assert_eq!(delta.rating.rank, None);
+        assert_eq!(delta.rating.total, 20);
+        assert_eq!(delta.rating.score_x10, 75);
+        assert_eq!(delta.new_collectors(), 13);
+
+        let mut newer = new.clone();
+        newer.rating.rank = 90;
+        newer.collection.dropped = 1;
+        newer.collection.wish = 2;
+        let delta = trend(&new, &newer);
+        assert_eq!(delta.rating.rank, Some(10));
+        assert_eq!(delta.collection.wish, -1);
+        assert_eq!(delta.new_collectors(), 0);
+    }
+}
@@ -0,0 +1,222 @@
+//! # Display (终端显示)
+//!
+//! 此模块提供了将 [`Subject`]、[`Episode`] 与 [`CharacterDetail`] 渲染为对齐的终端文本的格式化器，
+//! 支持纯文本与带 ANSI 颜色的输出，需要启用 `display` feature。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::display::PrettyPrint;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(3559).await?;
+//! println!("{}", subject.pretty_colored());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 此外 [`card`] 模块提供了 Markdown 与 HTML 格式的条目卡片，[`embed`] 模块提供了供聊天机器人使用的嵌入消息结构。
+
+use std::fmt;
+
+use crate::types::*;
+
+pub mod card;
+pub mod embed;
+
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// 可以渲染为终端文本的类型
+pub trait PrettyPrint {
+    /// 标题行
+    fn title(&self) -> String;
+
+    /// 字段列表，值为空的字段不会显示
+    fn fields(&self) -> Vec<(&'static str, String)>;
+
+    /// 渲染为纯文本
+    fn pretty(&self) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            color: false,
+        }
+    }
+
+    /// 渲染为带 ANSI 颜色的文本
+    fn pretty_colored(&self) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            color: true,
+        }
+    }
+}
+
+/// 实现了 [`fmt::Display`] 的格式化器，参见 [`PrettyPrint`]
+#[derive(Clone, Copy, Debug)]
+pub struct Pretty<'a, T: ?Sized> {
+    value: &'a T,
+    color: bool,
+}
+
+impl<T: PrettyPrint + ?Sized> fmt::Display for Pretty<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paint = |style: &str, text: &str| {
+            if self.color {
+                format!("{}{}{}", style, text, RESET)
+            } else {
+                text.to_string()
+            }
+        };
+
+        write!(f, "{}", paint(BOLD, &self.value.title()))?;
+
+        let fields: Vec<_> = self
+            .value
+            .fields()
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+
+        for (label, value) in fields {
+            let label = format!("{:width$}", label, width = width);
+            let value = if label.trim_end() == "Rating" {
+                paint(YELLOW, &value)
+            } else {
+                value
+            };
+            write!(f, "\n  {}  {}", paint(CYAN, &label), value)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn title(name: &str, name_cn: &str) -> String {
+    if name_cn.is_empty() || name_cn == name {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, name_cn)
+    }
+}
+
+impl PrettyPrint for Subject {
+    fn title(&self) -> String {
+        title(&self.name, &self.name_cn)
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let rating = if self.rating.total == 0 {
+            String::new()
+        } else {
+            format!(
+                "{:.1} ({} votes, #{})",
+                self.rating.score, self.rating.total, self.rating.rank
+            )
+        };
+        let tags = self
+            .tags
+            .iter()
+            .take(5)
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        vec![
+            ("ID", self.id.to_string()),
+            ("Type", format!("{:?}", self.r#type)),
+            ("Date", self.date.clone().unwrap_or_default()),
+            ("Platform", self.platform.clone()),
+            ("Rating", rating),
+            ("Eps", self.total_episodes.to_string()),
+            ("Tags", tags),
+        ]
+    }
+}
+
+impl PrettyPrint for Episode {
+    fn title(&self) -> String {
+        format!("ep.{} {}", self.sort, title(&self.name, &self.name_cn))
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ID", self.id.to_string()),
+            ("Type", format!("{:?}", self.r#type)),
+            ("Airdate", self.airdate.clone()),
+            ("Duration", self.duration.clone()),
+            ("Comments", self.comment.to_string()),
+        ]
+    }
+}
+
+impl PrettyPrint for CharacterDetail {
+    fn title(&self) -> String {
+        self.name.clone()
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let birthday = match (self.birth_year, self.birth_month, self.birth_day) {
+            (Some(y), Some(m), Some(d)) => format!("{}-{:02}-{:02}", y, m, d),
+            (None, Some(m), Some(d)) => format!("{:02}-{:02}", m, d),
+            (Some(y), _, _) => y.to_string(),
+            _ => String::new(),
+        };
+
+        vec![
+            ("ID", self.id.to_string()),
+            ("Type", format!("{:?}", self.r#type)),
+            ("Gender", self.gender.clone().unwrap_or_default()),
+            ("Birthday", birthday),
+            (
+                "Blood",
+                self.blood_type
+                    .map(|b| format!("{:?}", b))
+                    .unwrap_or_default(),
+            ),
+            ("Collects", self.stat.collects.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode() -> Episode {
+        Episode {
+            name: "ep name".to_string(),
+            name_cn: "中文名".to_string(),
+            sort: 3,
+            ep: Some(3),
+            airdate: "2024-04-01".to_string(),
+            comment: 10,
+            ..crate::mock::episode(1)
+        }
+    }
+
+    #[test]
+    fn test_pretty() {
+        let episode = episode();
+
+        assert_eq!(
+            episode.pretty().to_string(),
+            "ep.3 ep name (中文名)\n  ID        1\n  Type      MainStory\n  Airdate   2024-04-01\n  Comments  10"
+        );
+
+        let colored = episode.pretty_colored().to_string();
+        assert!(colored.starts_with("\x1b[1mep.3"));
+        assert!(colored.contains("\x1b[36mID      \x1b[0m"));
+    }
+}
@@ -0,0 +1,316 @@
+//! # Subject Card (条目卡片)
+//!
+//! 将 [`Subject`] 渲染为 Markdown 或 HTML 卡片，适用于在 Telegram、Discord、QQ 等聊天机器人中发送条目预览。
+//!
+//! 生成的 HTML 只使用 `<b>`、`<i>`、`<a>` 与 `<blockquote>` 标签，可以直接用于 Telegram 的 HTML 消息格式。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::display::card::{render_markdown, CardOptions};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(3559).await?;
+//! let card = render_markdown(&subject, &CardOptions::default().max_tags(3));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::*;
+
+/// 条目页面的 URL 前缀
+const SUBJECT_URL: &str = "https://bgm.tv/subject/";
+
+/// Card options (卡片选项)
+///
+/// 控制卡片中显示的字段，默认显示所有字段。
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CardOptions {
+    /// 封面图片的尺寸偏好，`None` 表示不显示封面
+    pub cover: Option<ImagePreference>,
+
+    /// 是否显示评分与排名
+    pub score: bool,
+
+    /// 最多显示的标签数量，`0` 表示不显示标签
+    pub max_tags: usize,
+
+    /// 简介摘要的最大字符数，`0` 表示不显示简介
+    pub summary_chars: usize,
+
+    /// 是否在标题上附加条目页面的链接
+    pub link: bool,
+}
+
+impl Default for CardOptions {
+    fn default() -> Self {
+        Self {
+            cover: Some(ImagePreference::default()),
+            score: true,
+            max_tags: 5,
+            summary_chars: 120,
+            link: true,
+        }
+    }
+}
+
+impl CardOptions {
+    /// 设置封面图片的尺寸偏好，`None` 表示不显示封面
+    pub fn cover(mut self, cover: Option<ImagePreference>) -> Self {
+        self.cover = cover;
+        self
+    }
+
+    /// 设置是否显示评分与排名
+    pub fn score(mut self, score: bool) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// 设置最多显示的标签数量
+    pub fn max_tags(mut self, max_tags: usize) -> Self {
+        self.max_tags = max_tags;
+        self
+    }
+
+    /// 设置简介摘要的最大字符数
+    pub fn summary_chars(mut self, summary_chars: usize) -> Self {
+        self.summary_chars = summary_chars;
+        self
+    }
+
+    /// 设置是否在标题上附加条目页面的链接
+    pub fn link(mut self, link: bool) -> Self {
+        self.link = link;
+        self
+    }
+}
+
+/// 卡片中与输出格式无关的内容
+struct Card {
+    title: String,
+    url: Option<String>,
+    cover: Option<String>,
+    score: Option<String>,
+    tags: Vec<String>,
+    summary: Option<String>,
+}
+
+impl Card {
+    fn new(subject: &Subject, options: &CardOptions) -> Self {
+        let title = if subject.name_cn.is_empty() || subject.name_cn == subject.name {
+            subject.name.clone()
+        } else {
+            format!("{} / {}", subject.name_cn, subject.name)
+        };
+
+        let cover = options
+            .cover
+            .as_ref()
+            .and_then(|preference| preference.pick(&subject.images))
+            .map(str::to_string);
+
+        let score = (options.score && subject.rating.total > 0).then(|| {
+            if subject.rating.rank > 0 {
+                format!("{:.1} (#{})", subject.rating.score, subject.rating.rank)
+            } else {
+                format!("{:.1}", subject.rating.score)
+            }
+        });
+
+        Self {
+            title,
+            url: options
+                .link
+                .then(|| format!("{}{}", SUBJECT_URL, subject.id)),
+            cover,
+            score,
+            tags: subject
+                .tags
+                .iter()
+                .take(options.max_tags)
+                .map(|tag| tag.name.clone())
+                .collect(),
+            summary: excerpt(&subject.summary, options.summary_chars),
+        }
+    }
+}
+
+/// 截取简介的前 `max_chars` 个字符，截断时以 `…` 结尾
+pub(super) fn excerpt(summary: &str, max_chars: usize) -> Option<String> {
+    let summary = summary.trim_matches(|c: char| c.is_whitespace());
+    if max_chars == 0 || summary.is_empty() {
+        return None;
+    }
+
+    let mut chars = summary.chars();
+    let mut excerpt: String = chars.by_ref().take(max_chars).collect();
+    if chars.next().is_some() {
+        excerpt = excerpt.trim_end().to_string();
+        excerpt.push('…');
+    }
+    Some(excerpt)
+}
+
+/// 将条目渲染为 Markdown 卡片
+pub fn render_markdown(subject: &Subject, options: &CardOptions) -> String {
+    let card = Card::new(subject, options);
+    let mut lines = Vec::new();
+
+    let title = escape_markdown(&card.title);
+    lines.push(match &card.url {
+        Some(url) => format!("**[{}]({})**", title, url),
+        None => format!("**{}**", title),
+    });
+    if let Some(score) = &card.score {
+        lines.push(format!("Score: {}", escape_markdown(score)));
+    }
+    if !card.tags.is_empty() {
+        let tags: Vec<_> = card
+            .tags
+            .iter()
+            .map(|tag| format!("`{}`", tag.replace('`', "'")))
+            .collect();
+        lines.push(format!("Tags: {}", tags.join(" ")));
+    }
+    if let Some(summary) = &card.summary {
+        for line in summary.lines().filter(|l| !l.trim().is_empty()) {
+            lines.push(format!("> {}", escape_markdown(line.trim())));
+        }
+    }
+    if let Some(cover) = &card.cover {
+        lines.push(format!("[Cover]({})", cover));
+    }
+
+    lines.join("\n")
+}
+
+/// 将条目渲染为 HTML 卡片
+pub fn render_html(subject: &Subject, options: &CardOptions) -> String {
+    let card = Card::new(subject, options);
+    let mut lines = Vec::new();
+
+    let title = escape_html(&card.title);
+    lines.push(match &card.url {
+        Some(url) => format!("<b><a href=\"{}\">{}</a></b>", escape_html(url), title),
+        None => format!("<b>{}</b>", title),
+    });
+    if let Some(score) = &card.score {
+        lines.push(format!("Score: {}", escape_html(score)));
+    }
+    if !card.tags.is_empty() {
+        let tags: Vec<_> = card
+            .tags
+            .iter()
+            .map(|tag| format!("<i>{}</i>", escape_html(tag)))
+            .collect();
+        lines.push(format!("Tags: {}", tags.join(" ")));
+    }
+    if let Some(summary) = &card.summary {
+        let summary: Vec<_> = summary
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(escape_html)
+            .collect();
+        lines.push(format!("<blockquote>{}</blockquote>", summary.join("\n")));
+    }
+    if let Some(cover) = &card.cover {
+        lines.push(format!("<a href=\"{}\">Cover</a>", escape_html(cover)));
+    }
+
+    lines.join("\n")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '[' | ']' | '(' | ')' | '~' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject() -> Subject {
+        let mut subject = crate::mock::subject();
+        subject.id = 3559;
+        subject.r#type = SubjectType::Book;
+        subject.name = "とある魔術の禁書目録".to_string();
+        subject.name_cn = "魔法禁书目录".to_string();
+        subject.summary =
+            "　　故事开始于<学园都市>中。\r\n这是个人口里八成都是学生的都市。".to_string();
+        subject.platform = "小说".to_string();
+        subject.images.large = "l.jpg".to_string();
+        subject.rating.rank = 1824;
+        subject.rating.total = 1032;
+        subject.rating.score = 7.6;
+        subject.tags = vec![
+            SubjectTag {
+                name: "轻小说".to_string(),
+                count: 281,
+            },
+            SubjectTag {
+                name: "科幻".to_string(),
+                count: 16,
+            },
+        ];
+        subject
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let options = CardOptions::default().max_tags(1).summary_chars(10);
+
+        assert_eq!(
+            render_markdown(&subject(), &options),
+            "**[魔法禁书目录 / とある魔術の禁書目録](https://bgm.tv/subject/3559)**\n\
+             Score: 7.6 \\(\\#1824\\)\n\
+             Tags: `轻小说`\n\
+             > 故事开始于<学园都市…\n\
+             [Cover](l.jpg)"
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let options = CardOptions::default()
+            .cover(None)
+            .score(false)
+            .max_tags(0)
+            .link(false);
+
+        assert_eq!(
+            render_html(&subject(), &options),
+            "<b>魔法禁书目录 / とある魔術の禁書目録</b>\n\
+             <blockquote>故事开始于&lt;学园都市&gt;中。\n这是个人口里八成都是学生的都市。</blockquote>"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! # Embed (嵌入消息)
+//!
+//! 与具体框架无关的嵌入消息结构 [`Embed`]，可以由 [`Subject`] 与 [`Episode`] 转换得到，
+//! 再由 Discord、Telegram 等机器人框架映射为各自的原生类型，统一 bgm.tv 数据在聊天中的展示方式。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::display::embed::Embed;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(3559).await?;
+//! let embed = Embed::from(&subject);
+//!
+//! println!("{}", embed.title);
+//! for field in &embed.fields {
+//!     println!("{}: {}", field.name, field.value);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::Serialize;
+
+use super::card::excerpt;
+use crate::types::*;
+
+/// 描述的最大字符数，满足 Telegram 图片说明 (1024) 与 Discord 描述 (4096) 的限制
+const DESCRIPTION_CHARS: usize = 200;
+
+/// Embed (嵌入消息)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Embed {
+    /// 标题
+    pub title: String,
+
+    /// 标题链接
+    pub url: Option<String>,
+
+    /// 描述
+    pub description: String,
+
+    /// 图片 URL
+    pub image_url: Option<String>,
+
+    /// 字段
+    pub fields: Vec<EmbedField>,
+
+    /// 页脚
+    pub footer: Option<String>,
+}
+
+/// Embed field (嵌入消息字段)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct EmbedField {
+    /// 名称
+    pub name: String,
+
+    /// 值
+    pub value: String,
+
+    /// 是否与相邻字段显示在同一行
+    pub inline: bool,
+}
+
+impl EmbedField {
+    fn inline(name: &str, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            inline: true,
+        }
+    }
+}
+
+fn title(name: &str, name_cn: &str) -> String {
+    if name_cn.is_empty() || name_cn == name {
+        name.to_string()
+    } else {
+        format!("{} / {}", name_cn, name)
+    }
+}
+
+impl From<&Subject> for Embed {
+    fn from(subject: &Subject) -> Self {
+        let mut fields = Vec::new();
+        if subject.rating.total > 0 {
+            fields.push(EmbedField::inline(
+                "Score",
+                format!("{:.1}", subject.rating.score),
+            ));
+        }
+        if subject.rating.rank > 0 {
+            fields.push(EmbedField::inline(
+                "Rank",
+                format!("#{}", subject.rating.rank),
+            ));
+        }
+        if let Some(date) = subject.date.as_deref().filter(|d| !d.is_empty()) {
+            fields.push(EmbedField::inline("Date", date));
+        }
+        if subject.total_episodes > 0 {
+            fields.push(EmbedField::inline("Eps", subject.total_episodes));
+        }
+        if !subject.tags.is_empty() {
+            let tags: Vec<_> = subject
+                .tags
+                .iter()
+                .take(5)
+                .map(|t| t.name.as_str())
+                .collect();
+            fields.push(EmbedField {
+                name: "Tags".to_string(),
+                value: tags.join(", "),
+                inline: false,
+            });
+        }
+
+        Self {
+            title: title(&subject.name, &subject.name_cn),
+            url: Some(format!("https://bgm.tv/subject/{}", subject.id)),
+            description: excerpt(&subject.summary, DESCRIPTION_CHARS).unwrap_or_default(),
+            image_url: ImagePreference::default()
+                .pick(&subject.images)
+                .map(str::to_string),
+            fields,
+            footer: Some(format!("bgm.tv · {:?}", subject.r#type)),
+        }
+    }
+}
+
+impl From<&Episode> for Embed {
+    fn from(episode: &Episode) -> Self {
+        let mut fields = Vec::new();
+        if !episode.airdate.is_empty() {
+            fields.push(EmbedField::inline("Airdate", &episode.airdate));
+        }
+        if !episode.duration.is_empty() {
+            fields.push(EmbedField::inline("Duration", &episode.duration));
+        }
+        fields.push(EmbedField::inline("Comments", episode.comment));
+
+        Self {
+            title: format!(
+                "ep.{} {}",
+                episode.sort,
+                title(&episode.name, &episode.name_cn)
+            ),
+            url: Some(format!("https://bgm.tv/ep/{}", episode.id)),
+            description: excerpt(&episode.desc, DESCRIPTION_CHARS).unwrap_or_default(),
+            image_url: None,
+            fields,
+            footer: Some(format!("bgm.tv · {:?}", episode.r#type)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_from_episode() {
+        let episode = Episode {
+            id: 1731,
+            r#type: EpisodeType::MainStory,
+            name: "学園都市".to_string(),
+            name_cn: "学园都市".to_string(),
+            sort: 1,
+            ep: Some(1),
+            airdate: "2008-10-04".to_string(),
+            comment: 3,
+            duration: String::new(),
+            desc: String::new(),
+            disc: 0,
+            duration_seconds: None,
+        };
+
+        let embed = Embed::from(&episode);
+        assert_eq!(embed.title, "ep.1 学园都市 / 学園都市");
+        assert_eq!(embed.url.as_deref(), Some("https://bgm.tv/ep/1731"));
+        assert_eq!(
+            embed.fields,
+            vec![
+                EmbedField::inline("Airdate", "2008-10-04"),
+                EmbedField::inline("Comments", 3),
+            ]
+        );
+        assert_eq!(embed.footer.as_deref(), Some("bgm.tv · MainStory"));
+    }
+}
@@ -0,0 +1,517 @@
+//! # Endpoints (API 地址)
+//!
+//! 此模块为每个 API 构建规范的请求方法、URL 与查询参数，与 [`Client`](crate::client::Client) 发送的请求一致，
+//! 供使用其他 HTTP 库的代码、curl 命令生成器或签名 URL 代理复用本 crate 的路由，而不依赖其 HTTP 实现。
+//!
+//! 所有函数都接收 API 的 base URL，例如 [`DEFAULT_BASE_URL`]，不会发送任何请求。
+//!
+//! ## Example
+//!
+//! ```
+//! use bgmtv::endpoints::{self, DEFAULT_BASE_URL};
+//! # use bgmtv::prelude::*;
+//! use bgmtv::api::GetEpisodesQuery;
+//!
+//! let endpoint = endpoints::get_subject(DEFAULT_BASE_URL, 3559).unwrap();
+//! assert_eq!(endpoint.to_string(), "GET https://api.bgm.tv/v0/subjects/3559");
+//!
+//! let endpoint = endpoints::get_episodes(
+//!     DEFAULT_BASE_URL,
+//!     &GetEpisodesQuery::new(3559).r#type(EpisodeType::MainStory).limit(10),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     endpoint.url.as_str(),
+//!     "https://api.bgm.tv/v0/episodes?subject_id=3559&type=0&limit=10"
+//! );
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use url::Url;
+
+use crate::{
+    api::{GetEpisodesQuery, GetSubjectsQuery, GetUserCollectionsQuery, SearchSubjectsQuery},
+    client::pagination::PageOptions,
+    types::*,
+};
+
+/// Default base URL of the API.
+pub const DEFAULT_BASE_URL: &str = "https://api.bgm.tv";
+
+/// Default base URL of the OAuth API.
+pub const DEFAULT_OAUTH_BASE_URL: &str = "https://bgm.tv";
+
+/// Endpoint (API 请求)
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Endpoint {
+    /// 请求方法，例如 `GET`
+    pub method: &'static str,
+
+    /// 路径模板，与指标和日志中的 `endpoint` 标签相同，例如 `/v0/subjects/{subject_id}`
+    pub path: &'static str,
+
+    /// 包含查询参数的 URL
+    pub url: Url,
+
+    /// JSON 请求体
+    pub body: Option<serde_json::Value>,
+
+    /// `application/x-www-form-urlencoded` 表单请求体，用于需要发送 token 等敏感参数的 OAuth API
+    pub form: Option<Vec<(&'static str, String)>>,
+}
+
+impl Endpoint {
+    fn new(method: &'static str, path: &'static str, url: Url) -> Self {
+        Self {
+            method,
+            path,
+            url,
+            body: None,
+            form: None,
+        }
+    }
+
+    /// 追加一个查询参数，`None` 不会被追加
+    fn query(mut self, key: &str, value: Option<impl Serialize>) -> Self {
+        let value = value.and_then(|value| match serde_json::to_value(value).ok()? {
+            serde_json::Value::String(s) => Some(s),
+            serde_json::Value::Null => None,
+            value => Some(value.to_string()),
+        });
+        if let Some(value) = value {
+            self.url.query_pairs_mut().append_pair(key, &value);
+        }
+        self
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)
+    }
+}
+
+/// 在 `base` 的路径后依次追加百分号编码后的 `segments`
+pub(crate) fn join(base: &Url, segments: &[&str]) -> Result<Url, url::ParseError> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|_| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+fn get(base_url: &str, path: &'static str, segments: &[&str]) -> Result<Endpoint, url::ParseError> {
+    Ok(Endpoint::new(
+        "GET",
+        path,
+        join(&base_url.parse()?, segments)?,
+    ))
+}
+
+/// `POST /v0/search/subjects`，参见 [`Client::search_subjects`](crate::client::Client::search_subjects)
+pub fn search_subjects(
+    base_url: &str,
+    query: &SearchSubjectsQuery,
+) -> Result<Endpoint, url::ParseError> {
+    let url = join(&base_url.parse()?, &["v0", "search", "subjects"])?;
+    let endpoint = Endpoint {
+        body: Some(serde_json::json!({
+            "keyword": query.keyword,
+            "filter": query.filter,
+            "sort": query.sort,
+        })),
+        ..Endpoint::new("POST", "/v0/search/subjects", url)
+    };
+    Ok(endpoint
+        .query("limit", query.limit)
+        .query("offset", query.offset))
+}
+
+/// `GET /v0/subjects`，参见 [`Client::get_subjects`](crate::client::Client::get_subjects)
+///
+/// 浏览条目 API 不支持按标签过滤，`query.tag` 会被忽略。
+pub fn get_subjects(base_url: &str, query: &GetSubjectsQuery) -> Result<Endpoint, url::ParseError> {
+    Ok(get(base_url, "/v0/subjects", &["v0", "subjects"])?
+        .query("type", Some(query.r#type))
+        .query("cat", query.cat.as_ref())
+        .query("series", query.series)
+        .query("platform", query.platform.as_ref())
+        .query("sort", query.sort.as_ref())
+        .query("year", query.year)
+        .query("month", query.month)
+        .query("limit", query.limit)
+        .query("offset", query.offset))
+}
+
+/// `GET /v0/subjects/{subject_id}`，参见 [`Client::get_subject`](crate::client::Client::get_subject)
+pub fn get_subject(base_url: &str, subject_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/subjects/{subject_id}",
+        &["v0", "subjects", &subject_id.to_string()],
+    )
+}
+
+/// `GET /v0/subjects/{subject_id}/image`，参见 [`Client::get_subject_image`](crate::client::Client::get_subject_image)
+pub fn get_subject_image(
+    base_url: &str,
+    subject_id: u64,
+    image_type: ImageType,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/subjects/{subject_id}/image",
+        &["v0", "subjects", &subject_id.to_string(), "image"],
+    )?
+    .query("type", Some(image_type)))
+}
+
+/// `GET /v0/subjects/{subject_id}/persons`，参见 [`Client::get_subject_persons_page`](crate::client::Client::get_subject_persons_page)
+pub fn get_subject_persons(
+    base_url: &str,
+    subject_id: u64,
+    options: PageOptions,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/subjects/{subject_id}/persons",
+        &["v0", "subjects", &subject_id.to_string(), "persons"],
+    )?
+    .query("limit", options.limit)
+    .query("offset", options.offset))
+}
+
+/// `GET /v0/subjects/{subject_id}/characters`，参见 [`Client::get_subject_characters_page`](crate::client::Client::get_subject_characters_page)
+pub fn get_subject_characters(
+    base_url: &str,
+    subject_id: u64,
+    options: PageOptions,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/subjects/{subject_id}/characters",
+        &["v0", "subjects", &subject_id.to_string(), "characters"],
+    )?
+    .query("limit", options.limit)
+    .query("offset", options.offset))
+}
+
+/// `GET /v0/subjects/{subject_id}/subjects`，参见 [`Client::get_subject_subjects`](crate::client::Client::get_subject_subjects)
+pub fn get_subject_subjects(base_url: &str, subject_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/subjects/{subject_id}/subjects",
+        &["v0", "subjects", &subject_id.to_string(), "subjects"],
+    )
+}
+
+/// `GET /v0/episodes`，参见 [`Client::get_episodes`](crate::client::Client::get_episodes)
+pub fn get_episodes(base_url: &str, query: &GetEpisodesQuery) -> Result<Endpoint, url::ParseError> {
+    Ok(get(base_url, "/v0/episodes", &["v0", "episodes"])?
+        .query("subject_id", Some(query.subject_id))
+        .query("type", query.r#type)
+        .query("limit", query.limit)
+        .query("offset", query.offset))
+}
+
+/// `GET /v0/episodes/{episode_id}`，参见 [`Client::get_episode`](crate::client::Client::get_episode)
+pub fn get_episode(base_url: &str, episode_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/episodes/{episode_id}",
+        &["v0", "episodes", &episode_id.to_string()],
+    )
+}
+
+/// `GET /v0/characters/{character_id}`，参见 [`Client::get_character`](crate::client::Client::get_character)
+pub fn get_character(base_url: &str, character_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/characters/{character_id}",
+        &["v0", "characters", &character_id.to_string()],
+    )
+}
+
+/// `GET /v0/characters/{character_id}/image`，参见 [`Client::get_character_image`](crate::client::Client::get_character_image)
+pub fn get_character_image(
+    base_url: &str,
+    character_id: u64,
+    image_type: ImageType,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/characters/{character_id}/image",
+        &["v0", "characters", &character_id.to_string(), "image"],
+    )?
+    .query("type", Some(image_type)))
+}
+
+/// `GET /v0/characters/{character_id}/subjects`，参见 [`Client::get_character_subjects`](crate::client::Client::get_character_subjects)
+pub fn get_character_subjects(
+    base_url: &str,
+    character_id: u64,
+) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/characters/{character_id}/subjects",
+        &["v0", "characters", &character_id.to_string(), "subjects"],
+    )
+}
+
+/// `GET /v0/characters/{character_id}/persons`，参见 [`Client::get_character_persons`](crate::client::Client::get_character_persons)
+pub fn get_character_persons(
+    base_url: &str,
+    character_id: u64,
+) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/characters/{character_id}/persons",
+        &["v0", "characters", &character_id.to_string(), "persons"],
+    )
+}
+
+/// `GET /v0/persons/{person_id}`，参见 [`Client::get_person`](crate::client::Client::get_person)
+pub fn get_person(base_url: &str, person_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/persons/{person_id}",
+        &["v0", "persons", &person_id.to_string()],
+    )
+}
+
+/// `GET /v0/persons/{person_id}/image`，参见 [`Client::get_person_image`](crate::client::Client::get_person_image)
+pub fn get_person_image(
+    base_url: &str,
+    person_id: u64,
+    image_type: ImageType,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/persons/{person_id}/image",
+        &["v0", "persons", &person_id.to_string(), "image"],
+    )?
+    .query("type", Some(image_type)))
+}
+
+/// `GET /v0/persons/{person_id}/subjects`，参见 [`Client::get_person_subjects`](crate::client::Client::get_person_subjects)
+pub fn get_person_subjects(base_url: &str, person_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/persons/{person_id}/subjects",
+        &["v0", "persons", &person_id.to_string(), "subjects"],
+    )
+}
+
+/// `GET /v0/persons/{person_id}/characters`，参见 [`Client::get_person_characters`](crate::client::Client::get_person_characters)
+pub fn get_person_characters(base_url: &str, person_id: u64) -> Result<Endpoint, url::ParseError> {
+    get(
+        base_url,
+        "/v0/persons/{person_id}/characters",
+        &["v0", "persons", &person_id.to_string(), "characters"],
+    )
+}
+
+/// `GET /v0/users/{username}`，参见 [`Client::get_user`](crate::client::Client::get_user)
+pub fn get_user(base_url: &str, username: &str) -> Result<Endpoint, url::ParseError> {
+    get(base_url, "/v0/users/{username}", &["v0", "users", username])
+}
+
+/// `GET /v0/users/{username}/avatar`，参见 [`Client::get_user_avatar`](crate::client::Client::get_user_avatar)
+pub fn get_user_avatar(
+    base_url: &str,
+    username: &str,
+    image_type: ImageType,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/users/{username}/avatar",
+        &["v0", "users", username, "avatar"],
+    )?
+    .query("type", Some(image_type)))
+}
+
+/// `GET /v0/me`，参见 [`Client::get_me`](crate::client::Client::get_me)
+pub fn get_me(base_url: &str) -> Result<Endpoint, url::ParseError> {
+    get(base_url, "/v0/me", &["v0", "me"])
+}
+
+/// `GET /v0/users/{username}/collections`，参见 [`Client::get_user_collections`](crate::client::Client::get_user_collections)
+pub fn get_user_collections(
+    base_url: &str,
+    query: &GetUserCollectionsQuery,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/users/{username}/collections",
+        &["v0", "users", &query.username, "collections"],
+    )?
+    .query("subject_type", query.subject_type)
+    .query("type", query.r#type)
+    .query("limit", query.limit)
+    .query("offset", query.offset))
+}
+
+/// `GET /v0/users/-/collections/{subject_id}/episodes`，参见 [`Client::get_user_episode_collections`](crate::client::Client::get_user_episode_collections)
+pub fn get_user_episode_collections(
+    base_url: &str,
+    subject_id: u64,
+    options: PageOptions,
+) -> Result<Endpoint, url::ParseError> {
+    Ok(get(
+        base_url,
+        "/v0/users/-/collections/{subject_id}/episodes",
+        &[
+            "v0",
+            "users",
+            "-",
+            "collections",
+            &subject_id.to_string(),
+            "episodes",
+        ],
+    )?
+    .query("limit", options.limit)
+    .query("offset", options.offset))
+}
+
+/// `POST /v0/users/-/collections/{subject_id}`，参见 [`Client::post_user_collection`](crate::client::Client::post_user_collection)
+///
+/// 与客户端不同，不会使用 [`default_private_collections`](crate::client::ClientBuilder::default_private_collections)。
+pub fn post_user_collection(
+    base_url: &str,
+    subject_id: u64,
+    payload: &UserCollectionModifyPayload,
+) -> Result<Endpoint, url::ParseError> {
+    let url = join(
+        &base_url.parse()?,
+        &["v0", "users", "-", "collections", &subject_id.to_string()],
+    )?;
+    Ok(Endpoint {
+        body: serde_json::to_value(payload).ok(),
+        ..Endpoint::new("POST", "/v0/users/-/collections/{subject_id}", url)
+    })
+}
+
+/// `PATCH /v0/users/-/collections/{subject_id}`，参见 [`Client::patch_user_collection`](crate::client::Client::patch_user_collection)
+pub fn patch_user_collection(
+    base_url: &str,
+    subject_id: u64,
+    payload: &UserCollectionModifyPayload,
+) -> Result<Endpoint, url::ParseError> {
+    let url = join(
+        &base_url.parse()?,
+        &["v0", "users", "-", "collections", &subject_id.to_string()],
+    )?;
+    Ok(Endpoint {
+        body: serde_json::to_value(payload).ok(),
+        ..Endpoint::new("PATCH", "/v0/users/-/collections/{subject_id}", url)
+    })
+}
+
+/// `POST /oauth/token_status`，参见 [`Client::token_status`](crate::client::Client::token_status)
+///
+/// `oauth_base_url` 为 OAuth API 的 base URL，例如 [`DEFAULT_OAUTH_BASE_URL`]。
+/// token 通过表单请求体发送，不会出现在 URL 中。
+pub fn token_status(oauth_base_url: &str, token: &str) -> Result<Endpoint, url::ParseError> {
+    let url = join(&oauth_base_url.parse()?, &["oauth", "token_status"])?;
+    Ok(Endpoint {
+        form: Some(vec![("access_token", token.to_string())]),
+        ..Endpoint::new("POST", "/oauth/token_status", url)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::Client,
+        mock::{MockResponse, MockServer},
+    };
+
+    #[tokio::test]
+    async fn test_endpoints_match_client() {
+        let page = r#"{"total":0,"limit":10,"offset":0,"data":[]}"#;
+        let server = MockServer::start(vec![
+            MockResponse::new(200, page),
+            MockResponse::new(200, page),
+            MockResponse::new(200, page),
+        ])
+        .await;
+        let base_url = server.base_url.as_str();
+
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+        client
+            .get_episodes(1)
+            .r#type(EpisodeType::SP)
+            .limit(10)
+            .send()
+            .await
+            .unwrap();
+        client
+            .get_subjects()
+            .r#type(SubjectType::Book)
+            .cat(SubjectCategory::Book(SubjectBookCategory::Novel))
+            .series(true)
+            .sort(BrowseSort::Rank)
+            .send()
+            .await
+            .unwrap();
+        client
+            .get_user_collections("a b")
+            .r#type(SubjectCollectionType::Doing)
+            .offset(5)
+            .send()
+            .await
+            .unwrap();
+
+        let expected = [
+            get_episodes(
+                base_url,
+                &GetEpisodesQuery::new(1).r#type(EpisodeType::SP).limit(10),
+            ),
+            get_subjects(
+                base_url,
+                &GetSubjectsQuery::new(SubjectType::Book)
+                    .cat(SubjectCategory::Book(SubjectBookCategory::Novel))
+                    .series(true)
+                    .sort(BrowseSort::Rank),
+            ),
+            get_user_collections(
+                base_url,
+                &GetUserCollectionsQuery::new("a b")
+                    .r#type(SubjectCollectionType::Doing)
+                    .offset(5),
+            ),
+        ];
+        for (request, endpoint) in server.requests().iter().zip(expected) {
+            let endpoint = endpoint.unwrap();
+            let target = &endpoint.url[url::Position::BeforePath..];
+            assert_eq!(request.line, format!("GET {} HTTP/1.1", target));
+        }
+
+        let endpoint = get_user(DEFAULT_BASE_URL, "a/b").unwrap();
+        assert_eq!(endpoint.url.as_str(), "https://api.bgm.tv/v0/users/a%2Fb");
+        assert_eq!(endpoint.path, "/v0/users/{username}");
+
+        let endpoint =
+            search_subjects(DEFAULT_BASE_URL, &SearchSubjectsQuery::new("k").limit(1)).unwrap();
+        assert_eq!(
+            endpoint.to_string(),
+            "POST https://api.bgm.tv/v0/search/subjects?limit=1"
+        );
+        assert_eq!(endpoint.body.unwrap()["keyword"], "k");
+
+        let endpoint = token_status(DEFAULT_OAUTH_BASE_URL, "secret").unwrap();
+        assert_eq!(endpoint.url.as_str(), "https://bgm.tv/oauth/token_status");
+        assert_eq!(
+            endpoint.form,
+            Some(vec![("access_token", "secret".to_string())])
+        );
+    }
+}
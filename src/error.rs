@@ -0,0 +1,528 @@
+//! Error types
+//!
+//! 此 mod 提供了本 crate 中所有 API 返回的错误类型，使用 `error_set!` 宏定义。
+
+use std::fmt;
+
+use error_set::error_set;
+use serde::{Deserialize, Serialize};
+error_set! {
+    /// Error from dependencies
+    DepsError = {
+        /// Error from reqwest
+        ///
+        /// 这是 [`reqwest`] 提供的基础错误类型，几乎大部分 API 调用都可能返回这个错误。
+        Reqwest(reqwest::Error),
+        /// Error of converting header value to string
+        ///
+        /// 这是 [`reqwest::header::HeaderValue`] 转换为字符串时可能返回的错误。
+        HeaderValueToStr(reqwest::header::ToStrError),
+        /// Error of building header value
+        ///
+        /// 这是 [`reqwest::header::HeaderValue`] 从字符串构建时可能返回的错误，例如 token 中包含非法字符。
+        InvalidHeaderValue(reqwest::header::InvalidHeaderValue),
+        /// Error of parsing URL
+        ///
+        /// 这是 [`url::ParseError`] 在解析 URL 时可能返回的错误。
+        InvalidUrl(url::ParseError),
+        /// Error returned by bgm.tv API
+        ///
+        /// 这是 API 返回非 2xx 状态码时解析错误响应体得到的 [`BgmApiError`]。
+        Api(BgmApiError),
+        /// Error from middleware
+        ///
+        /// 启用 `middleware` feature 并通过 `ClientBuilder::client_middleware` 设置中间件后，
+        /// 中间件返回的错误。底层 [`reqwest`] 的错误仍为 [`DepsError::Reqwest`]。
+        Middleware(crate::error::MiddlewareError),
+        /// Error of local I/O
+        ///
+        /// 读写本地文件时的错误，例如启用 `recorder` feature 后回放时找不到对应的 fixture 文件。
+        Io(std::io::Error),
+        /// Rate limited by bgm.tv API
+        ///
+        /// API 返回 `429 Too Many Requests` 且未启用重试 (或重试次数已用尽) 时返回，
+        /// `retry_after` 为 `Retry-After` 响应头中建议的等待时长，`history` 为各次尝试的记录，
+        /// `request_id` 为请求的 `X-Request-Id`。
+        #[allow(missing_docs)]
+        #[display("Rate limited by bgm.tv API after {} attempts, retry after {retry_after:?}", history.len())]
+        RateLimited {
+            retry_after: Option<std::time::Duration>,
+            history: crate::client::retry::RetryHistory,
+            request_id: Option<String>
+        },
+        /// Network error after retries
+        ///
+        /// 设置了 `RetryPolicy` 且网络错误 (连接失败、超时等) 在重试后仍然失败时返回，
+        /// `error` 为最后一次尝试的错误，`history` 为各次尝试的记录，`request_id` 为请求的 `X-Request-Id`。
+        /// 没有发生重试时直接返回原本的错误，例如 [`DepsError::Reqwest`]。
+        #[allow(missing_docs)]
+        #[display("Network error after {} attempts: {error}", history.len())]
+        Network {
+            error: Box<DepsError>,
+            history: crate::client::retry::RetryHistory,
+            request_id: Option<String>
+        },
+        /// Timed out
+        ///
+        /// 分页的 `timeout_per_page` 等本 crate 自身设置的超时，`timeout` 为设置的超时时间。
+        /// 底层 [`reqwest`] 的超时仍为 [`DepsError::Reqwest`]。
+        #[allow(missing_docs)]
+        #[display("Timed out after {timeout:?}")]
+        Timeout {
+            timeout: std::time::Duration
+        },
+        /// Response body too large
+        ///
+        /// 响应体超过了 `ClientBuilder::max_json_size` 或 `ClientBuilder::max_image_size` 设置的上限，
+        /// `limit` 为对应的上限 (字节)。
+        #[allow(missing_docs)]
+        #[display("Response body exceeds the limit of {limit} bytes")]
+        ResponseTooLarge {
+            limit: u64
+        },
+        /// No token configured
+        ///
+        /// 调用 [`token_status`](crate::client::Client::token_status) 等需要 token 的 API 但未设置 token 时返回，不会发送请求。
+        #[display("No token configured")]
+        MissingToken,
+        /// Error of serializing to / deserializing from JSON
+        ///
+        /// 这会出现在将某些类型序列化为 JSON 时，例如将一些 enum 转换为对应的 JSON 字符串；
+        /// 也会出现在将响应体反序列化为对应的类型时。
+        Serialize(serde_json::Error)
+    };
+
+    /// Error for [Client::search_subjects](crate::client::Client::search_subjects)
+    SearchSubjectsError = {
+        /// Error of building [SearchSubjectsExecutor](crate::client::subjects::SearchSubjectsExecutor)
+        #[display("Cannot build request to search subjects: {0}")]
+        Builder(crate::client::subjects::SearchSubjectsExecutorBuilderError)
+    } || DepsError;
+
+    /// Error for [Client::get_subjects](crate::client::Client::get_subjects)
+    GetSubjectsError = {
+        /// Error of building [GetSubjectsExecutor](crate::client::subjects::GetSubjectsExecutor)
+        #[display("Cannot build request to get subjects: {0}")]
+        Builder(crate::client::subjects::GetSubjectsExecutorBuilderError)
+    } || DepsError;
+
+    /// Error for [Client::get_episodes](crate::client::Client::get_episodes)
+    GetEpisodesError = {
+        /// Error of building [GetEpisodesExecutor](crate::client::episodes::GetEpisodesExecutor)
+        #[display("Cannot build request to get episodes: {0}")]
+        Builder(crate::client::episodes::GetEpisodesExecutorBuilderError)
+    } || DepsError;
+
+    /// Error for [Client::get_user_collections](crate::client::Client::get_user_collections)
+    GetUserCollectionsError = {
+        /// Error of building [GetUserCollectionsExecutor](crate::client::collections::GetUserCollectionsExecutor)
+        #[display("Cannot build request to get user collections: {0}")]
+        Builder(crate::client::collections::GetUserCollectionsExecutorBuilderError)
+    } || DepsError;
+
+    /// Error for write operations (修改类 API)
+    ///
+    /// 修改类 API 被服务器拒绝时，会尽量根据错误响应体区分拒绝原因，便于向用户解释。
+    WriteError = {
+        /// Token 缺少所需的权限 (scope)
+        #[allow(missing_docs)]
+        #[display("Insufficient scope: {description}")]
+        InsufficientScope {
+            description: String
+        },
+        /// 条目已被锁定，无法修改
+        #[allow(missing_docs)]
+        #[display("Subject is locked: {description}")]
+        SubjectLocked {
+            description: String
+        }
+    } || DepsError;
+}
+
+#[cfg(feature = "config")]
+error_set! {
+    /// Error for [Client::from_config_path](crate::client::Client::from_config_path)
+    ConfigError = {
+        /// Error of reading the config file or creating the cache directory
+        #[display("Cannot read config: {0}")]
+        Io(std::io::Error),
+        /// Error of parsing the config file
+        #[display("Invalid config file: {0}")]
+        Toml(toml::de::Error),
+        /// Error of building [Client](crate::client::Client) from the config
+        #[display("Cannot build client from config: {0}")]
+        Builder(crate::client::ClientBuilderError)
+    };
+}
+
+/// Error of parsing an enum from a string (枚举解析错误)
+///
+/// 由 [`SubjectType`](crate::types::SubjectType) 等枚举的 [`FromStr`](std::str::FromStr) 实现返回。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseEnumError {
+    type_name: &'static str,
+    value: String,
+}
+
+impl ParseEnumError {
+    pub(crate) fn new(type_name: &'static str, value: &str) -> Self {
+        Self {
+            type_name,
+            value: value.to_string(),
+        }
+    }
+
+    /// 无法解析的字符串
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid {}: {:?}", self.type_name, self.value)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
+/// Error of parsing a search filter (搜索条件解析错误)
+///
+/// 由 [`AirDateFilter`](crate::types::AirDateFilter) 等搜索条件的 [`FromStr`](std::str::FromStr) 实现返回。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFilterError {
+    value: String,
+}
+
+impl ParseFilterError {
+    pub(crate) fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+        }
+    }
+
+    /// 无法解析的字符串
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid search filter: {:?}", self.value)
+    }
+}
+
+impl std::error::Error for ParseFilterError {}
+
+/// Error returned by middleware (中间件错误)
+#[derive(Debug)]
+pub struct MiddlewareError(pub Box<dyn std::error::Error + Send + Sync>);
+
+impl fmt::Display for MiddlewareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for MiddlewareError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for DepsError {
+    fn from(err: reqwest_middleware::Error) -> Self {
+        match err {
+            reqwest_middleware::Error::Reqwest(err) => DepsError::Reqwest(err),
+            reqwest_middleware::Error::Middleware(err) => {
+                DepsError::Middleware(MiddlewareError(err.into()))
+            }
+        }
+    }
+}
+
+/// Error body returned by bgm.tv API (API 错误响应)
+///
+/// bgm.tv 在返回 400/401/404/422 等状态码时会附带 `{"title", "description", "details"}` 格式的响应体。
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BgmApiError {
+    /// HTTP 状态码
+    #[serde(skip)]
+    pub status: u16,
+
+    /// 错误标题
+    #[serde(default)]
+    pub title: String,
+
+    /// 错误描述
+    #[serde(default)]
+    pub description: String,
+
+    /// 错误详情
+    #[serde(default)]
+    pub details: Option<serde_json::Value>,
+
+    /// 请求的 `X-Request-Id`，参见 [`request_id`](crate::client::request_id) 模块
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+impl BgmApiError {
+    /// 根据状态码与响应体构建 [`BgmApiError`]
+    ///
+    /// 响应体无法解析时，使用状态码对应的描述作为标题，响应体文本作为描述。
+    pub fn from_response_body(status: u16, body: &[u8]) -> Self {
+        match serde_json::from_slice::<BgmApiError>(body) {
+            Ok(err) => BgmApiError { status, ..err },
+            Err(_) => BgmApiError {
+                status,
+                title: reqwest::StatusCode::from_u16(status)
+                    .ok()
+                    .and_then(|s| s.canonical_reason())
+                    .unwrap_or_default()
+                    .to_string(),
+                description: String::from_utf8_lossy(body).into_owned(),
+                details: None,
+                request_id: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for BgmApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.status, self.title, self.description)
+    }
+}
+
+impl std::error::Error for BgmApiError {}
+
+impl BgmApiError {
+    /// 建议代理服务返回给下游客户端的 HTTP 状态码
+    ///
+    /// `400`/`404`/`422` 等由请求参数引起的错误原样返回；认证失败 (`401`/`403`) 属于代理服务自身的配置问题，
+    /// 与 `5xx` 一样返回 `502 Bad Gateway`。
+    pub fn status_hint(&self) -> Option<u16> {
+        match self.status {
+            401 | 403 => Some(502),
+            400..=499 => Some(self.status),
+            500..=599 => Some(502),
+            _ => None,
+        }
+    }
+}
+
+fn reqwest_status_hint(err: &reqwest::Error) -> Option<u16> {
+    if err.is_timeout() {
+        Some(504)
+    } else if err.is_builder() {
+        None
+    } else {
+        Some(502)
+    }
+}
+
+/// 为包含 [`DepsError`] 的错误类型实现 `status_hint` 与到 [`http::StatusCode`] 的转换
+macro_rules! impl_status_hint {
+    ($ty:ident { $($pat:pat => $hint:expr),* $(,)? }) => {
+        impl $ty {
+            /// 建议代理服务返回给下游客户端的 HTTP 状态码
+            ///
+            /// 上游 bgm.tv 的错误与网络错误返回对应的状态码 (例如 `404`、`429`、`502`、`504`)，
+            /// 本地的配置错误返回 `None`，通常应视为 `500 Internal Server Error`。
+            pub fn status_hint(&self) -> Option<u16> {
+                match self {
+                    $ty::Reqwest(err) => reqwest_status_hint(err),
+                    $ty::Api(err) => err.status_hint(),
+                    $ty::RateLimited { .. } => Some(429),
+                    $ty::Network { error, .. } => error.status_hint(),
+                    $ty::Timeout { .. } => Some(504),
+                    $ty::ResponseTooLarge { .. } => Some(502),
+                    $ty::Serialize(_) | $ty::Middleware(_) => Some(502),
+                    $ty::HeaderValueToStr(_) | $ty::InvalidHeaderValue(_) | $ty::InvalidUrl(_) | $ty::Io(_) | $ty::MissingToken => None,
+                    $($pat => $hint,)*
+                }
+            }
+        }
+
+        #[cfg(feature = "http")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+        impl From<&$ty> for http::StatusCode {
+            fn from(err: &$ty) -> Self {
+                err.status_hint()
+                    .and_then(|status| http::StatusCode::from_u16(status).ok())
+                    .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+
+        #[cfg(feature = "http")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+        impl From<$ty> for http::StatusCode {
+            fn from(err: $ty) -> Self {
+                (&err).into()
+            }
+        }
+    };
+}
+
+/// 为包含 [`DepsError`] 的错误类型实现 `request_id`
+macro_rules! impl_request_id {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl $ty {
+                /// 请求的 `X-Request-Id`，用于关联客户端日志与服务端的排查
+                ///
+                /// 仅 API 返回错误响应 ([`Api`]($ty::Api)、[`RateLimited`]($ty::RateLimited))
+                /// 或重试后仍然网络错误 ([`Network`]($ty::Network)) 时存在。
+                pub fn request_id(&self) -> Option<&str> {
+                    match self {
+                        $ty::Api(err) => err.request_id.as_deref(),
+                        $ty::RateLimited { request_id, .. } => request_id.as_deref(),
+                        $ty::Network { request_id, .. } => request_id.as_deref(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_request_id!(
+    DepsError,
+    SearchSubjectsError,
+    GetSubjectsError,
+    GetEpisodesError,
+    GetUserCollectionsError,
+    WriteError,
+);
+
+impl_status_hint!(DepsError {});
+impl_status_hint!(SearchSubjectsError {
+    SearchSubjectsError::Builder(_) => None,
+});
+impl_status_hint!(GetSubjectsError {
+    GetSubjectsError::Builder(_) => None,
+});
+impl_status_hint!(GetEpisodesError {
+    GetEpisodesError::Builder(_) => None,
+});
+impl_status_hint!(GetUserCollectionsError {
+    GetUserCollectionsError::Builder(_) => None,
+});
+impl_status_hint!(WriteError {
+    WriteError::InsufficientScope { .. } => Some(502),
+    WriteError::SubjectLocked { .. } => Some(423),
+});
+
+impl WriteError {
+    /// 根据 API 错误判断修改被拒绝的原因
+    ///
+    /// 无法归类为 [`WriteError::InsufficientScope`] 或 [`WriteError::SubjectLocked`] 时返回 `None`。
+    pub fn from_api_error(err: &BgmApiError) -> Option<Self> {
+        let text = format!("{} {}", err.title, err.description).to_lowercase();
+
+        if text.contains("locked") {
+            Some(WriteError::SubjectLocked {
+                description: err.description.clone(),
+            })
+        } else if err.status == 403 || text.contains("scope") || text.contains("permission") {
+            Some(WriteError::InsufficientScope {
+                description: err.description.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// 将修改类 API 的 [`DepsError`] 转换为 [`WriteError`]，尽量区分拒绝原因
+    pub(crate) fn from_deps_error(err: DepsError) -> Self {
+        match err {
+            DepsError::Api(api) => Self::from_api_error(&api).unwrap_or(Self::Api(api)),
+            err => err.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bgm_api_error_from_response_body() {
+        let body = br#"{"title":"Not Found","description":"resource can't be found in the database or has been removed","details":{"path":"/v0/subjects/0"}}"#;
+        let err = BgmApiError::from_response_body(404, body);
+        assert_eq!(err.status, 404);
+        assert_eq!(err.title, "Not Found");
+        assert!(err.details.is_some());
+
+        let err = BgmApiError::from_response_body(502, b"Bad Gateway");
+        assert_eq!(err.title, "Bad Gateway");
+        assert_eq!(err.description, "Bad Gateway");
+    }
+
+    #[test]
+    fn test_status_hint() {
+        let api = |status| DepsError::Api(BgmApiError::from_response_body(status, b""));
+        assert_eq!(api(404).status_hint(), Some(404));
+        assert_eq!(api(422).status_hint(), Some(422));
+        assert_eq!(api(401).status_hint(), Some(502));
+        assert_eq!(api(500).status_hint(), Some(502));
+        assert_eq!(
+            DepsError::RateLimited {
+                retry_after: None,
+                history: Default::default(),
+                request_id: None,
+            }
+            .status_hint(),
+            Some(429)
+        );
+        assert_eq!(
+            DepsError::ResponseTooLarge { limit: 0 }.status_hint(),
+            Some(502)
+        );
+        assert_eq!(
+            DepsError::InvalidUrl(url::ParseError::EmptyHost).status_hint(),
+            None
+        );
+
+        let err = WriteError::SubjectLocked {
+            description: String::new(),
+        };
+        assert_eq!(err.status_hint(), Some(423));
+
+        #[cfg(feature = "http")]
+        {
+            assert_eq!(
+                http::StatusCode::from(api(404)),
+                http::StatusCode::NOT_FOUND
+            );
+            assert_eq!(
+                http::StatusCode::from(DepsError::InvalidUrl(url::ParseError::EmptyHost)),
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_error_from_api_error() {
+        let body = br#"{"title":"Bad Request","description":"subject is locked"}"#;
+        let err = BgmApiError::from_response_body(400, body);
+        assert!(matches!(
+            WriteError::from_api_error(&err),
+            Some(WriteError::SubjectLocked { .. })
+        ));
+
+        let body = br#"{"title":"Forbidden","description":"token has no write permission"}"#;
+        let err = BgmApiError::from_response_body(403, body);
+        assert!(matches!(
+            WriteError::from_api_error(&err),
+            Some(WriteError::InsufficientScope { .. })
+        ));
+
+        let body = br#"{"title":"Not Found","description":"subject not found"}"#;
+        let err = BgmApiError::from_response_body(404, body);
+        assert!(WriteError::from_api_error(&err).is_none());
+    }
+}
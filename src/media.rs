@@ -0,0 +1,46 @@
+//! # Media Helpers (媒体类型辅助)
+//!
+//! 不同类型的条目复用了相同的字段，但含义各不相同，例如音乐条目的 `eps` 表示曲目数，章节的 `disc` 表示碟片。
+//! 此模块为各类型条目提供了更贴近其本身的数据模型。
+//!
+//! - [`book`]：书籍条目的阅读进度
+//! - [`game`]：游戏条目的平台、发行日期与开发商
+//! - [`music`]：音乐条目的碟片与曲目
+//! - [`real`]：三次元条目的电视台、国家/地区与时长
+//! - [`season`]：按放送日期将长篇动画的章节划分为季度
+
+use crate::types::*;
+
+pub mod book;
+pub mod game;
+pub mod music;
+pub mod real;
+pub mod season;
+
+/// 信息框中的值，列表中的每一项为 `(k, v)`
+fn values(value: &InfoboxValue) -> Vec<(Option<&str>, &str)> {
+    match value {
+        InfoboxValue::Single(v) => vec![(None, v.as_str())],
+        InfoboxValue::List(items) => items
+            .iter()
+            .map(|item| match item {
+                InfoboxValueItem::KV { k, v } => (Some(k.as_str()), v.as_str()),
+                InfoboxValueItem::V { v } => (None, v.as_str()),
+            })
+            .collect(),
+    }
+    .into_iter()
+    .filter(|(_, v)| !v.trim().is_empty())
+    .collect()
+}
+
+/// 将值按常见的分隔符拆分
+fn split(values: &[(Option<&str>, &str)]) -> Vec<String> {
+    values
+        .iter()
+        .flat_map(|(_, v)| v.split(['、', '/', ',', '，']))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
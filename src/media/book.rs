@@ -0,0 +1,143 @@
+//! # Book (书籍)
+//!
+//! 书籍条目的收藏同时记录卷数进度 `vol_status` 与话数进度 `ep_status`，分别对应条目的 `volumes` 与 `eps`。
+//! [`ReadingProgress`] 结合二者解释阅读进度，并生成更新进度所需的请求体。
+//!
+//! ## Example
+//!
+//! ```
+//! # use bgmtv::prelude::*;
+//! use bgmtv::media::book::ReadingProgress;
+//!
+//! let mut progress = ReadingProgress::new(Some(22), None, 3, 0);
+//! progress.set_volumes(22);
+//!
+//! assert!(progress.is_finished());
+//! let payload = progress.to_payload();
+//! assert_eq!(payload.vol_status, Some(22));
+//! assert_eq!(payload.r#type, Some(SubjectCollectionType::Collect));
+//! ```
+
+use crate::types::*;
+
+/// Reading progress (阅读进度)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadingProgress {
+    /// 总卷数，未知时为 `None`
+    pub total_volumes: Option<u64>,
+
+    /// 总话数，未知时为 `None`
+    pub total_chapters: Option<u64>,
+
+    /// 已读卷数，对应收藏的 `vol_status`
+    pub volumes: u64,
+
+    /// 已读话数，对应收藏的 `ep_status`
+    pub chapters: u64,
+}
+
+impl ReadingProgress {
+    /// 由总卷数、总话数与当前进度创建阅读进度
+    pub fn new(
+        total_volumes: Option<u64>,
+        total_chapters: Option<u64>,
+        volumes: u64,
+        chapters: u64,
+    ) -> Self {
+        let mut progress = Self {
+            total_volumes: total_volumes.filter(|&n| n > 0),
+            total_chapters: total_chapters.filter(|&n| n > 0),
+            volumes: 0,
+            chapters: 0,
+        };
+        progress.set_volumes(volumes);
+        progress.set_chapters(chapters);
+        progress
+    }
+
+    /// 由书籍条目与收藏中的 `vol_status`、`ep_status` 创建阅读进度
+    ///
+    /// 条目的 `volumes` 或 `eps` 为 0 时视为未知。
+    pub fn from_subject(subject: &Subject, vol_status: u64, ep_status: u64) -> Self {
+        Self::new(
+            Some(subject.volumes),
+            Some(subject.eps),
+            vol_status,
+            ep_status,
+        )
+    }
+
+    /// 设置已读卷数，不超过总卷数
+    pub fn set_volumes(&mut self, volumes: u64) {
+        self.volumes = clamp(volumes, self.total_volumes);
+    }
+
+    /// 设置已读话数，不超过总话数
+    pub fn set_chapters(&mut self, chapters: u64) {
+        self.chapters = clamp(chapters, self.total_chapters);
+    }
+
+    /// 阅读进度的比例，范围为 0.0–1.0
+    ///
+    /// 优先按卷数计算，总卷数未知时按话数计算；二者都未知时返回 `None`。
+    pub fn fraction(&self) -> Option<f64> {
+        match (self.total_volumes, self.total_chapters) {
+            (Some(total), _) => Some(self.volumes as f64 / total as f64),
+            (None, Some(total)) => Some(self.chapters as f64 / total as f64),
+            (None, None) => None,
+        }
+    }
+
+    /// 是否已读完
+    ///
+    /// 已知的总卷数与总话数都已读完时为 `true`，二者都未知时为 `false`。
+    pub fn is_finished(&self) -> bool {
+        let volumes = self.total_volumes.map(|total| self.volumes >= total);
+        let chapters = self.total_chapters.map(|total| self.chapters >= total);
+        match (volumes, chapters) {
+            (None, None) => false,
+            (volumes, chapters) => volumes.unwrap_or(true) && chapters.unwrap_or(true),
+        }
+    }
+
+    /// 生成更新阅读进度的请求体
+    ///
+    /// 包含 `vol_status` 与 `ep_status`；已读完时同时将收藏类型设置为看过 (读过)。
+    pub fn to_payload(&self) -> UserCollectionModifyPayload {
+        UserCollectionModifyPayload {
+            r#type: self.is_finished().then_some(SubjectCollectionType::Collect),
+            ep_status: Some(self.chapters),
+            vol_status: Some(self.volumes),
+            ..Default::default()
+        }
+    }
+}
+
+fn clamp(value: u64, total: Option<u64>) -> u64 {
+    total.map_or(value, |total| value.min(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_progress() {
+        let mut progress = ReadingProgress::new(Some(10), Some(0), 2, 30);
+        assert_eq!(progress.total_chapters, None);
+        assert_eq!(progress.fraction(), Some(0.2));
+        assert!(!progress.is_finished());
+
+        progress.set_volumes(100);
+        assert_eq!(progress.volumes, 10);
+        assert!(progress.is_finished());
+
+        let payload = serde_json::to_value(progress.to_payload()).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({"type": 2, "ep_status": 30, "vol_status": 10})
+        );
+
+        assert!(!ReadingProgress::new(None, None, 5, 5).is_finished());
+    }
+}
@@ -0,0 +1,172 @@
+//! # Game (游戏)
+//!
+//! 游戏条目的信息框与动画、书籍差异较大，[`GameInfo`] 从中提取平台、各地区发行日期与开发商/发行商。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::media::game::GameInfo;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(137458).await?;
+//! let info = GameInfo::from_subject(&subject);
+//! println!("{:?} by {:?}", info.platforms, info.developers);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{split, values};
+use crate::types::*;
+
+/// Regional release (地区发行信息)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegionalRelease {
+    /// 地区或版本，例如 `日本`、`PS4`，未注明时为 `None`
+    pub region: Option<String>,
+
+    /// 发行日期
+    pub date: String,
+}
+
+/// Game information (游戏信息)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GameInfo {
+    /// 平台
+    pub platforms: Vec<String>,
+
+    /// 各地区的发行日期
+    pub release_dates: Vec<RegionalRelease>,
+
+    /// 开发商
+    pub developers: Vec<String>,
+
+    /// 发行商
+    pub publishers: Vec<String>,
+
+    /// 游戏类型
+    pub genre: Option<String>,
+
+    /// 游戏引擎
+    pub engine: Option<String>,
+}
+
+impl GameInfo {
+    /// 从游戏条目中提取游戏信息
+    ///
+    /// 信息框中没有平台时使用条目的 `platform`。
+    pub fn from_subject(subject: &Subject) -> Self {
+        let mut info = Self::from_infobox(&subject.infobox);
+        if info.platforms.is_empty() && !subject.platform.is_empty() {
+            info.platforms.push(subject.platform.clone());
+        }
+        info
+    }
+
+    /// 从信息框中提取游戏信息
+    pub fn from_infobox(infobox: &[Infobox]) -> Self {
+        let mut info = Self::default();
+
+        for entry in infobox {
+            let key = entry.key.trim();
+            let values = values(&entry.value);
+
+            match key {
+                "平台" => info.platforms.extend(split(&values)),
+                "开发" | "开发商" => info.developers.extend(split(&values)),
+                "发行" | "发行商" => info.publishers.extend(split(&values)),
+                "游戏类型" => info.genre = values.first().map(|(_, v)| v.to_string()),
+                "游戏引擎" => info.engine = values.first().map(|(_, v)| v.to_string()),
+                _ if key.ends_with("发行日期") || key.ends_with("发售日") => {
+                    let prefix = key
+                        .trim_end_matches("发行日期")
+                        .trim_end_matches("发售日")
+                        .trim();
+                    info.release_dates
+                        .extend(values.into_iter().map(|(k, date)| {
+                            RegionalRelease {
+                                region: k
+                                    .or((!prefix.is_empty()).then_some(prefix))
+                                    .map(str::to_string),
+                                date: date.to_string(),
+                            }
+                        }));
+                }
+                _ => {}
+            }
+        }
+
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, value: InfoboxValue) -> Infobox {
+        Infobox {
+            key: key.to_string(),
+            value,
+        }
+    }
+
+    fn single(v: &str) -> InfoboxValue {
+        InfoboxValue::Single(v.to_string())
+    }
+
+    #[test]
+    fn test_game_info() {
+        let infobox = vec![
+            entry(
+                "平台",
+                InfoboxValue::List(vec![
+                    InfoboxValueItem::V {
+                        v: "PS4".to_string(),
+                    },
+                    InfoboxValueItem::V {
+                        v: "PC".to_string(),
+                    },
+                ]),
+            ),
+            entry("开发", single("ATLUS")),
+            entry("发行", single("ATLUS、SEGA")),
+            entry("发行日期", single("2016-09-15")),
+            entry("北美发行日期", single("2017-04-04")),
+            entry(
+                "发售日",
+                InfoboxValue::List(vec![InfoboxValueItem::KV {
+                    k: "PC".to_string(),
+                    v: "2022-10-21".to_string(),
+                }]),
+            ),
+            entry("游戏类型", single("RPG")),
+        ];
+
+        let info = GameInfo::from_infobox(&infobox);
+        assert_eq!(info.platforms, vec!["PS4", "PC"]);
+        assert_eq!(info.developers, vec!["ATLUS"]);
+        assert_eq!(info.publishers, vec!["ATLUS", "SEGA"]);
+        assert_eq!(info.genre.as_deref(), Some("RPG"));
+        assert_eq!(
+            info.release_dates,
+            vec![
+                RegionalRelease {
+                    region: None,
+                    date: "2016-09-15".to_string()
+                },
+                RegionalRelease {
+                    region: Some("北美".to_string()),
+                    date: "2017-04-04".to_string()
+                },
+                RegionalRelease {
+                    region: Some("PC".to_string()),
+                    date: "2022-10-21".to_string()
+                },
+            ]
+        );
+    }
+}
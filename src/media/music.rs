@@ -0,0 +1,149 @@
+//! # Music (音乐)
+//!
+//! 音乐条目的章节即为曲目：`ep` 为碟片内的曲目序号，`disc` 为碟片序号。
+//! [`Album`] 将条目与其章节组合为按碟片分组的曲目列表。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::media::music::Album;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(14346).await?;
+//! let episodes = client.get_episodes(14346).build()?.fetch_all().await?;
+//!
+//! let album = Album::new(subject, episodes);
+//! for track in album.tracks() {
+//!     println!("{}-{:02} {}", track.disc, track.number, track.episode.name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::*;
+
+/// Track (曲目)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    /// 碟片序号，从 1 开始
+    pub disc: u64,
+
+    /// 碟片内的曲目序号，从 1 开始
+    pub number: u64,
+
+    /// 曲目对应的章节
+    pub episode: Episode,
+}
+
+/// Disc (碟片)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Disc {
+    /// 碟片序号，从 1 开始
+    pub number: u64,
+
+    /// 曲目，按曲目序号排序
+    pub tracks: Vec<Track>,
+}
+
+/// Album (专辑)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Album {
+    /// 条目
+    pub subject: Subject,
+
+    /// 碟片，按碟片序号排序
+    pub discs: Vec<Disc>,
+}
+
+impl Album {
+    /// 由音乐条目与其章节构建专辑
+    pub fn new(subject: Subject, episodes: impl IntoIterator<Item = Episode>) -> Self {
+        Self {
+            subject,
+            discs: group_by_disc(episodes),
+        }
+    }
+
+    /// 发售日期
+    pub fn release_date(&self) -> Option<&str> {
+        self.subject.date.as_deref().filter(|date| !date.is_empty())
+    }
+
+    /// 所有曲目，按碟片与曲目序号排序
+    pub fn tracks(&self) -> impl Iterator<Item = &Track> {
+        self.discs.iter().flat_map(|disc| disc.tracks.iter())
+    }
+
+    /// 曲目总数
+    ///
+    /// 优先使用已获取的章节数量，没有章节时使用条目的 `eps`。
+    pub fn track_count(&self) -> u64 {
+        match self.tracks().count() as u64 {
+            0 => self.subject.eps,
+            n => n,
+        }
+    }
+}
+
+/// 将音乐条目的章节按碟片分组
+///
+/// `disc` 为 0 的章节视为第 1 张碟片；没有 `ep` 的章节使用 `sort` 作为曲目序号。
+pub fn group_by_disc(episodes: impl IntoIterator<Item = Episode>) -> Vec<Disc> {
+    let mut tracks: Vec<_> = episodes
+        .into_iter()
+        .map(|episode| Track {
+            disc: episode.disc.max(1),
+            number: episode.ep.unwrap_or(episode.sort),
+            episode,
+        })
+        .collect();
+    tracks.sort_by_key(|track| (track.disc, track.number));
+
+    let mut discs: Vec<Disc> = Vec::new();
+    for track in tracks {
+        match discs.last_mut() {
+            Some(disc) if disc.number == track.disc => disc.tracks.push(track),
+            _ => discs.push(Disc {
+                number: track.disc,
+                tracks: vec![track],
+            }),
+        }
+    }
+    discs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: u64, disc: u64, ep: u64) -> Episode {
+        Episode {
+            id,
+            disc,
+            ..crate::mock::episode(ep)
+        }
+    }
+
+    #[test]
+    fn test_group_by_disc() {
+        let discs = group_by_disc(vec![track(3, 2, 1), track(2, 1, 2), track(1, 1, 1)]);
+
+        assert_eq!(discs.len(), 2);
+        assert_eq!(
+            discs[0]
+                .tracks
+                .iter()
+                .map(|t| t.episode.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(discs[1].number, 2);
+        assert_eq!(discs[1].tracks[0].number, 1);
+
+        assert_eq!(group_by_disc(vec![track(1, 0, 1)])[0].number, 1);
+    }
+}
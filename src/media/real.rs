@@ -0,0 +1,165 @@
+//! # Real (三次元)
+//!
+//! 电视剧、电影等三次元条目的信息框中记录了电视台、国家/地区与单集时长等信息，
+//! [`RealInfo`] 将其提取为结构化的数据。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::media::real::RealInfo;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let subject = client.get_subject(11691).await?;
+//! let info = RealInfo::from_subject(&subject);
+//! println!("{:?} / {:?} / {:?}", info.networks, info.countries, info.runtime);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use super::{split, values};
+use crate::types::*;
+
+/// Real subject information (三次元条目信息)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RealInfo {
+    /// 电视台或播出平台
+    pub networks: Vec<String>,
+
+    /// 国家/地区
+    pub countries: Vec<String>,
+
+    /// 单集时长或片长
+    pub runtime: Option<Duration>,
+
+    /// 集数
+    pub episodes: Option<u64>,
+
+    /// 开始播出或上映日期
+    pub start: Option<String>,
+
+    /// 结束播出日期
+    pub end: Option<String>,
+}
+
+impl RealInfo {
+    /// 从三次元条目中提取信息
+    ///
+    /// 信息框中没有集数时使用条目的 `total_episodes`。
+    pub fn from_subject(subject: &Subject) -> Self {
+        let mut info = Self::from_infobox(&subject.infobox);
+        if info.episodes.is_none() && subject.total_episodes > 0 {
+            info.episodes = Some(subject.total_episodes);
+        }
+        info
+    }
+
+    /// 从信息框中提取信息
+    pub fn from_infobox(infobox: &[Infobox]) -> Self {
+        let mut info = Self::default();
+
+        for entry in infobox {
+            let values = values(&entry.value);
+            let first = values.first().map(|(_, v)| v.trim());
+
+            match entry.key.trim() {
+                "电视台" | "播放电视台" | "首播电视台" | "播出平台" | "频道" => {
+                    info.networks.extend(split(&values))
+                }
+                "国家/地区" | "国家" | "地区" | "制片国家/地区" => {
+                    info.countries.extend(split(&values))
+                }
+                "片长" | "时长" | "单集片长" => {
+                    info.runtime = info.runtime.or(first.and_then(parse_runtime))
+                }
+                "集数" => info.episodes = first.and_then(|v| v.parse().ok()),
+                "开始" | "上映日期" | "上映时间" | "首播" => {
+                    info.start = info.start.or(first.map(str::to_string))
+                }
+                "结束" => info.end = first.map(str::to_string),
+                _ => {}
+            }
+        }
+
+        info
+    }
+}
+
+/// 解析 `45分钟`、`1小时30分`、`45 min` 等格式的时长，纯数字视为分钟
+fn parse_runtime(value: &str) -> Option<Duration> {
+    let mut minutes = 0;
+    let mut number: Option<u64> = None;
+    let mut found = false;
+
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(digit) = c.to_digit(10) {
+            number = Some(number.unwrap_or(0) * 10 + digit as u64);
+            continue;
+        }
+        let Some(n) = number else {
+            continue;
+        };
+        match c {
+            '小' | '时' | 'h' | 'H' => {
+                minutes += n * 60;
+                number = None;
+                found = true;
+            }
+            '分' | 'm' | 'M' | '\'' => {
+                minutes += n;
+                number = None;
+                found = true;
+            }
+            _ if c.is_whitespace() && chars.peek().is_some_and(|c| c.is_alphabetic()) => {}
+            _ => {
+                minutes += n;
+                number = None;
+                found = true;
+            }
+        }
+    }
+    if let Some(n) = number {
+        minutes += n;
+        found = true;
+    }
+
+    found.then(|| Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_runtime() {
+        assert_eq!(parse_runtime("45分钟"), Some(Duration::from_secs(45 * 60)));
+        assert_eq!(
+            parse_runtime("1小时30分"),
+            Some(Duration::from_secs(90 * 60))
+        );
+        assert_eq!(parse_runtime("45 min"), Some(Duration::from_secs(45 * 60)));
+        assert_eq!(parse_runtime("120"), Some(Duration::from_secs(120 * 60)));
+        assert_eq!(parse_runtime("未知"), None);
+    }
+
+    #[test]
+    fn test_real_info() {
+        let infobox = serde_json::from_str::<Vec<Infobox>>(
+            r#"[{"key":"集数","value":"11"},{"key":"国家/地区","value":"日本"},{"key":"电视台","value":"TBS"},{"key":"开始","value":"2013-07-07"},{"key":"片长","value":"约60分钟"}]"#,
+        )
+        .unwrap();
+
+        let info = RealInfo::from_infobox(&infobox);
+        assert_eq!(info.episodes, Some(11));
+        assert_eq!(info.countries, vec!["日本"]);
+        assert_eq!(info.networks, vec!["TBS"]);
+        assert_eq!(info.start.as_deref(), Some("2013-07-07"));
+        assert_eq!(info.runtime, Some(Duration::from_secs(60 * 60)));
+    }
+}
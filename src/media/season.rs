@@ -0,0 +1,173 @@
+//! # Season (分季)
+//!
+//! bangumi 常把放送多年的长篇动画收录为一个条目，[`split_seasons`] 根据本篇章节放送日期之间的间隔，
+//! 将章节划分为若干季 (cour)，便于界面分块展示上百集的条目。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::media::season::split_seasons;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::builder().build()?;
+//!
+//! let episodes = client.get_episodes(975).build()?.fetch_all().await?;
+//! for season in split_seasons(episodes) {
+//!     println!(
+//!         "Season {}: {} episodes, {:?} ~ {:?}",
+//!         season.number,
+//!         season.episodes.len(),
+//!         season.first_airdate(),
+//!         season.last_airdate()
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{time::parse_date, types::*};
+
+/// Season (季)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Season {
+    /// 季序号，从 1 开始
+    pub number: u64,
+
+    /// 本季的章节，按 `sort` 排序
+    pub episodes: Vec<Episode>,
+}
+
+impl Season {
+    /// 本季第一个有放送日期的章节的放送日期
+    pub fn first_airdate(&self) -> Option<&str> {
+        self.episodes
+            .iter()
+            .map(|episode| episode.airdate.as_str())
+            .find(|airdate| parse_date(airdate).is_some())
+    }
+
+    /// 本季最后一个有放送日期的章节的放送日期
+    pub fn last_airdate(&self) -> Option<&str> {
+        self.episodes
+            .iter()
+            .rev()
+            .map(|episode| episode.airdate.as_str())
+            .find(|airdate| parse_date(airdate).is_some())
+    }
+}
+
+/// [`split_seasons_with`] 的选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SeasonOptions {
+    /// 相邻两集的放送日期间隔超过此天数时划分为新的一季，默认为 28 天
+    pub gap_days: u32,
+
+    /// 是否只保留本篇章节，默认为 `true`
+    pub main_story_only: bool,
+}
+
+impl Default for SeasonOptions {
+    fn default() -> Self {
+        Self {
+            gap_days: 28,
+            main_story_only: true,
+        }
+    }
+}
+
+impl SeasonOptions {
+    /// 设置划分新一季的间隔天数
+    pub fn gap_days(mut self, gap_days: u32) -> Self {
+        self.gap_days = gap_days;
+        self
+    }
+
+    /// 设置是否只保留本篇章节
+    pub fn main_story_only(mut self, main_story_only: bool) -> Self {
+        self.main_story_only = main_story_only;
+        self
+    }
+}
+
+/// 使用默认选项划分季度，参见 [`split_seasons_with`]
+pub fn split_seasons(episodes: impl IntoIterator<Item = Episode>) -> Vec<Season> {
+    split_seasons_with(episodes, SeasonOptions::default())
+}
+
+/// 按放送日期的间隔将章节划分为季度
+///
+/// 章节按 `sort` 排序后依次比较相邻两集的放送日期，间隔超过 [`SeasonOptions::gap_days`] 时开始新的一季。
+/// 没有放送日期 (或格式无法解析) 的章节归入当前的季。
+pub fn split_seasons_with(
+    episodes: impl IntoIterator<Item = Episode>,
+    options: SeasonOptions,
+) -> Vec<Season> {
+    let mut episodes: Vec<_> = episodes
+        .into_iter()
+        .filter(|episode| !options.main_story_only || episode.r#type == EpisodeType::MainStory)
+        .collect();
+    episodes.sort_by_key(|episode| episode.sort);
+
+    let mut seasons: Vec<Season> = Vec::new();
+    let mut last_date = None;
+    for episode in episodes {
+        let date = parse_date(&episode.airdate);
+        let gap = match (last_date, date) {
+            (Some(last), Some(date)) => date - last > i64::from(options.gap_days),
+            _ => false,
+        };
+        last_date = date.or(last_date);
+
+        match seasons.last_mut() {
+            Some(season) if !gap => season.episodes.push(episode),
+            _ => seasons.push(Season {
+                number: seasons.len() as u64 + 1,
+                episodes: vec![episode],
+            }),
+        }
+    }
+    seasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(sort: u64, r#type: EpisodeType, airdate: &str) -> Episode {
+        Episode {
+            r#type,
+            airdate: airdate.to_string(),
+            ..crate::mock::episode(sort)
+        }
+    }
+
+    #[test]
+    fn test_split_seasons() {
+        let main = EpisodeType::MainStory;
+        let episodes = vec![
+            episode(3, main, "2024-01-15"),
+            episode(1, main, "2024-01-01"),
+            episode(2, main, "2024-01-08"),
+            episode(4, main, ""),
+            episode(5, main, "2024-04-01"),
+            episode(6, main, "2024-04-08"),
+            episode(1, EpisodeType::SP, "2024-02-01"),
+        ];
+
+        let seasons = split_seasons(episodes.clone());
+        let ids = |season: &Season| season.episodes.iter().map(|e| e.sort).collect::<Vec<_>>();
+        assert_eq!(seasons.len(), 2);
+        assert_eq!(ids(&seasons[0]), vec![1, 2, 3, 4]);
+        assert_eq!(seasons[0].first_airdate(), Some("2024-01-01"));
+        assert_eq!(seasons[0].last_airdate(), Some("2024-01-15"));
+        assert_eq!(seasons[1].number, 2);
+        assert_eq!(ids(&seasons[1]), vec![5, 6]);
+
+        let seasons = split_seasons_with(episodes, SeasonOptions::default().gap_days(100));
+        assert_eq!(seasons.len(), 1);
+        assert!(split_seasons(Vec::new()).is_empty());
+    }
+}
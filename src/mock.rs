@@ -0,0 +1,189 @@
+//! 测试用的本地 HTTP 服务器
+//!
+//! 按顺序返回预设的响应，并记录收到的请求，避免单元测试依赖 bgm.tv 的可用性。
+//! 请求的解析与响应的写入也用于 `test-util` feature 的 [`test_server`](crate::test_server)。
+//! 此外提供单元测试共用的条目与章节数据。
+
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// 预设的响应
+pub(crate) struct MockResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub(crate) fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub(crate) fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// 收到的请求
+#[derive(Clone, Debug)]
+pub(crate) struct MockRequest {
+    /// 请求行，例如 `GET /v0/subjects/1 HTTP/1.1`
+    pub(crate) line: String,
+    /// 请求头，名称均为小写
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 本地 HTTP 服务器
+pub(crate) struct MockServer {
+    pub(crate) base_url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// 启动服务器，依次返回 `responses` 中的响应，用尽后返回 404
+    pub(crate) async fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            let mut responses = responses.into_iter();
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let Some(req) = read_request(&mut stream).await else {
+                    continue;
+                };
+                recorded.lock().unwrap().push(req);
+
+                let res = responses
+                    .next()
+                    .unwrap_or_else(|| MockResponse::new(404, "{}"));
+                write_response(&mut stream, &res).await;
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    /// 已收到的请求
+    pub(crate) fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// 写入响应并关闭连接
+pub(crate) async fn write_response(stream: &mut tokio::net::TcpStream, res: &MockResponse) {
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\ncontent-length: {}\r\nconnection: close\r\n",
+        res.status,
+        res.body.len()
+    );
+    for (name, value) in &res.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&res.body).await;
+    let _ = stream.shutdown().await;
+}
+
+/// 读取一个请求
+pub(crate) async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<MockRequest> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let head_end = loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let line = lines.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(n, v)| (n.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let len = headers
+        .iter()
+        .find(|(n, _)| n == "content-length")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buf[head_end + 4..].to_vec();
+    while body.len() < len {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    Some(MockRequest {
+        line,
+        headers,
+        body,
+    })
+}
+
+/// 单元测试共用的条目 JSON，各字段均为空值或零值，测试按需修改解析后的字段
+#[cfg(test)]
+pub(crate) fn subject_json() -> &'static str {
+    r#"{"id":1,"type":2,"name":"name","name_cn":"","summary":"","series":false,"nsfw":false,"locked":false,"date":null,"platform":"TV","images":{"small":"","grid":"","large":"","medium":"","common":""},"infobox":[],"volumes":0,"eps":12,"total_episodes":12,"rating":{"rank":0,"total":0,"count":{"1":0,"2":0,"3":0,"4":0,"5":0,"6":0,"7":0,"8":0,"9":0,"10":0},"score":0},"collection":{"on_hold":0,"dropped":0,"wish":0,"collect":0,"doing":0},"tags":[]}"#
+}
+
+/// 解析 [`subject_json`] 得到的条目
+#[cfg(test)]
+pub(crate) fn subject() -> crate::types::Subject {
+    serde_json::from_str(subject_json()).unwrap()
+}
+
+/// 单元测试共用的正片章节，`id`、`sort` 与 `ep` 均为 `id`，其余字段为空值或零值
+#[cfg(test)]
+pub(crate) fn episode(id: u64) -> crate::types::Episode {
+    crate::types::Episode {
+        id,
+        r#type: crate::types::EpisodeType::MainStory,
+        name: String::new(),
+        name_cn: String::new(),
+        sort: id,
+        ep: Some(id),
+        airdate: String::new(),
+        comment: 0,
+        duration: String::new(),
+        desc: String::new(),
+        disc: 0,
+        duration_seconds: None,
+    }
+}
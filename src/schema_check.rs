@@ -0,0 +1,480 @@
+//! # Schema Check (数据结构检查)
+//!
+//! 此模块检查 API 返回的数据与本 crate 中的类型是否一致，报告无法反序列化的数据、类型中未包含的字段，
+//! 以及响应中缺失而使用了默认值的字段，需要启用 `schema-check` feature。
+//!
+//! 可以通过 [`SchemaCheck`] 获取一组实时的数据进行检查，也可以通过 [`check_recorded`] 检查
+//! [`Recorder`](crate::client::recorder::Recorder) 录制的 fixture 文件 (需要同时启用 `recorder` feature)。
+//! 检查结果 [`DriftReport`] 可以序列化为 JSON，便于在 API 更新后比较。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::schema_check::SchemaCheck;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let client = Client::new();
+//!
+//! let report = SchemaCheck::new()
+//!     .subjects([3559, 975])
+//!     .episodes([8])
+//!     .run(&client)
+//!     .await;
+//!
+//! println!("{}", report.to_json());
+//! assert!(!report.has_drift());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{client::Client, error::*, types::*};
+
+type Check = fn(&str, &str, &Value) -> EntityReport;
+
+/// Drift report (数据结构偏差报告)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DriftReport {
+    /// 每个被检查的数据的结果
+    pub entries: Vec<EntityReport>,
+}
+
+impl DriftReport {
+    /// 是否有任何数据存在偏差
+    pub fn has_drift(&self) -> bool {
+        self.entries.iter().any(EntityReport::has_drift)
+    }
+
+    /// 只包含存在偏差的结果
+    pub fn drifted(&self) -> impl Iterator<Item = &EntityReport> {
+        self.entries.iter().filter(|entry| entry.has_drift())
+    }
+
+    fn push(
+        &mut self,
+        schema: &str,
+        source: String,
+        check: Check,
+        value: Result<Value, impl std::fmt::Display>,
+    ) {
+        self.entries.push(match value {
+            Ok(value) => check(schema, &source, &value),
+            Err(err) => EntityReport::error(schema, &source, err),
+        });
+    }
+
+    /// 序列化为格式化的 JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Entity report (单个数据的检查结果)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct EntityReport {
+    /// 用于反序列化的类型，例如 `Subject`
+    pub schema: String,
+
+    /// 数据来源，例如请求的 URL 或 fixture 文件路径
+    pub source: String,
+
+    /// 请求或反序列化失败时的错误信息
+    pub error: Option<String>,
+
+    /// 响应中存在但类型中未包含的字段路径，例如 `infobox[].extra`
+    pub unknown_fields: Vec<String>,
+
+    /// 响应中缺失、反序列化时使用了默认值的字段路径
+    pub missing_fields: Vec<String>,
+}
+
+impl EntityReport {
+    /// 是否存在偏差
+    pub fn has_drift(&self) -> bool {
+        self.error.is_some() || !self.unknown_fields.is_empty() || !self.missing_fields.is_empty()
+    }
+
+    fn error(schema: &str, source: &str, error: impl ToString) -> Self {
+        Self {
+            schema: schema.to_string(),
+            source: source.to_string(),
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 检查 `value` 能否反序列化为 `T`，并比较 `T` 重新序列化后的字段
+///
+/// `schema` 与 `source` 只用于填写报告。
+pub fn check_value<T: DeserializeOwned + Serialize>(
+    schema: &str,
+    source: &str,
+    value: &Value,
+) -> EntityReport {
+    let typed = match T::deserialize(value).map(|typed| serde_json::to_value(&typed)) {
+        Ok(Ok(typed)) => typed,
+        Ok(Err(err)) | Err(err) => return EntityReport::error(schema, source, err),
+    };
+
+    let mut unknown = BTreeSet::new();
+    let mut missing = BTreeSet::new();
+    diff("", value, &typed, &mut unknown, &mut missing);
+
+    EntityReport {
+        schema: schema.to_string(),
+        source: source.to_string(),
+        error: None,
+        unknown_fields: unknown.into_iter().collect(),
+        missing_fields: missing.into_iter().collect(),
+    }
+}
+
+/// 递归比较原始数据与重新序列化的数据，数组的下标统一记为 `[]`
+fn diff(
+    path: &str,
+    raw: &Value,
+    typed: &Value,
+    unknown: &mut BTreeSet<String>,
+    missing: &mut BTreeSet<String>,
+) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match (raw, typed) {
+        (Value::Object(raw), Value::Object(typed)) => {
+            for (key, value) in raw {
+                match typed.get(key) {
+                    Some(typed) => diff(&join(key), value, typed, unknown, missing),
+                    None => {
+                        unknown.insert(join(key));
+                    }
+                }
+            }
+            for (key, value) in typed {
+                if !raw.contains_key(key) && !value.is_null() {
+                    missing.insert(join(key));
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(typed)) => {
+            let path = format!("{}[]", path);
+            for (raw, typed) in raw.iter().zip(typed) {
+                diff(&path, raw, typed, unknown, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 检查列表，未分页的列表与分页结果都可以接受
+fn check_list<T: DeserializeOwned + Serialize>(
+    schema: &str,
+    source: &str,
+    value: &Value,
+) -> EntityReport {
+    if value.is_array() {
+        check_value::<Vec<T>>(schema, source, value)
+    } else {
+        check_value::<Paged<T>>(schema, source, value)
+    }
+}
+
+/// 根据 URL 的路径判断数据的类型并检查，无法识别路径时返回 `None`
+///
+/// `url` 可以是完整的 URL 或 `/v0/subjects/1` 形式的路径。
+pub fn check_body(url: &str, body: &[u8]) -> Option<EntityReport> {
+    let path = url::Url::parse(url)
+        .map(|url| url.path().to_string())
+        .unwrap_or_else(|_| url.split(['?', '#']).next().unwrap_or_default().to_string());
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let start = segments.iter().position(|s| *s == "v0")?;
+
+    let (schema, check): (&str, Check) = match &segments[start + 1..] {
+        ["subjects"] => ("PagedSubject", check_value::<PagedSubject>),
+        ["search", "subjects"] => ("SearchSubjects", check_value::<SearchSubjects>),
+        ["subjects", _] => ("Subject", check_value::<Subject>),
+        ["subjects", _, "persons"] => ("RelatedPerson", check_list::<RelatedPerson>),
+        ["subjects", _, "characters"] => ("RelatedCharacter", check_list::<RelatedCharacter>),
+        ["subjects", _, "subjects"] => ("SubjectRelation", check_list::<SubjectRelation>),
+        ["episodes"] => ("PagedEpisode", check_value::<PagedEpisode>),
+        ["episodes", _] => ("Episode", check_value::<Episode>),
+        ["characters", _] => ("CharacterDetail", check_value::<CharacterDetail>),
+        ["characters", _, "subjects"] => ("RelatedSubject", check_list::<RelatedSubject>),
+        ["characters", _, "persons"] => ("CharacterPerson", check_list::<CharacterPerson>),
+        ["persons", _] => ("PersonDetail", check_value::<PersonDetail>),
+        ["persons", _, "subjects"] => ("RelatedSubject", check_list::<RelatedSubject>),
+        ["persons", _, "characters"] => ("PersonCharacter", check_list::<PersonCharacter>),
+        ["me"] | ["users", _] => ("User", check_value::<User>),
+        ["users", _, "collections"] => ("PagedUserCollection", check_value::<PagedUserCollection>),
+        ["users", "-", "collections", _, "episodes"] => {
+            ("UserEpisodeCollection", check_list::<UserEpisodeCollection>)
+        }
+        _ => return None,
+    };
+
+    Some(match serde_json::from_slice::<Value>(body) {
+        Ok(value) => check(schema, url, &value),
+        Err(err) => EntityReport::error(schema, url, err),
+    })
+}
+
+/// 检查 `dir` 中录制的所有 fixture 文件，跳过非 2xx 的响应与无法识别路径的请求
+///
+/// 参见 [`Recorder`](crate::client::recorder::Recorder)。
+#[cfg(feature = "recorder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+pub fn check_recorded(dir: impl AsRef<std::path::Path>) -> Result<DriftReport, DepsError> {
+    let entries = crate::client::recorder::read_fixtures(dir.as_ref())?
+        .into_iter()
+        .filter_map(|(url, body)| check_body(&url, &body))
+        .collect();
+    Ok(DriftReport { entries })
+}
+
+/// Schema check (数据结构检查)
+///
+/// 设置需要获取的实时数据，参见 [模块文档](self)。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaCheck {
+    /// 条目 ID
+    pub subjects: Vec<u64>,
+
+    /// 章节 ID
+    pub episodes: Vec<u64>,
+
+    /// 角色 ID
+    pub characters: Vec<u64>,
+
+    /// 人物 ID
+    pub persons: Vec<u64>,
+
+    /// 用户名
+    pub users: Vec<String>,
+}
+
+impl SchemaCheck {
+    /// 创建空的检查
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加条目，同时检查条目的章节列表与相关人物、角色、条目
+    pub fn subjects(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        self.subjects.extend(ids);
+        self
+    }
+
+    /// 添加章节
+    pub fn episodes(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        self.episodes.extend(ids);
+        self
+    }
+
+    /// 添加角色
+    pub fn characters(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        self.characters.extend(ids);
+        self
+    }
+
+    /// 添加人物
+    pub fn persons(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        self.persons.extend(ids);
+        self
+    }
+
+    /// 添加用户
+    pub fn users(mut self, usernames: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.users.extend(usernames.into_iter().map(Into::into));
+        self
+    }
+
+    /// 依次获取数据并检查，请求失败的数据会记录在报告中
+    pub async fn run(&self, client: &Client) -> DriftReport {
+        let mut report = DriftReport::default();
+        for &id in &self.subjects {
+            let source = |suffix: &str| format!("/v0/subjects/{}{}", id, suffix);
+            report.push(
+                "Subject",
+                source(""),
+                check_value::<Subject>,
+                client.get_subject_raw(id).await,
+            );
+            report.push(
+                "PagedEpisode",
+                format!("/v0/episodes?subject_id={}", id),
+                check_value::<PagedEpisode>,
+                paged_episodes(client, id).await,
+            );
+            report.push(
+                "RelatedPerson",
+                source("/persons"),
+                check_list::<RelatedPerson>,
+                client.get_subject_persons_raw(id).await,
+            );
+            report.push(
+                "RelatedCharacter",
+                source("/characters"),
+                check_list::<RelatedCharacter>,
+                client.get_subject_characters_raw(id).await,
+            );
+            report.push(
+                "SubjectRelation",
+                source("/subjects"),
+                check_list::<SubjectRelation>,
+                client.get_subject_subjects_raw(id).await,
+            );
+        }
+        for &id in &self.episodes {
+            report.push(
+                "Episode",
+                format!("/v0/episodes/{}", id),
+                check_value::<Episode>,
+                client.get_episode_raw(id).await,
+            );
+        }
+        for &id in &self.characters {
+            report.push(
+                "CharacterDetail",
+                format!("/v0/characters/{}", id),
+                check_value::<CharacterDetail>,
+                client.get_character_raw(id).await,
+            );
+        }
+        for &id in &self.persons {
+            report.push(
+                "PersonDetail",
+                format!("/v0/persons/{}", id),
+                check_value::<PersonDetail>,
+                client.get_person_raw(id).await,
+            );
+        }
+        for username in &self.users {
+            report.push(
+                "User",
+                format!("/v0/users/{}", username),
+                check_value::<User>,
+                client.get_user_raw(username).await,
+            );
+        }
+
+        report
+    }
+}
+
+async fn paged_episodes(client: &Client, subject_id: u64) -> Result<Value, GetEpisodesError> {
+    client.get_episodes(subject_id).build()?.send_raw().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    #[test]
+    fn test_check_body() {
+        let mut body = serde_json::to_value(crate::mock::episode(1)).unwrap();
+        body["extra"] = serde_json::json!({"a": 1});
+        let body = serde_json::to_vec(&body).unwrap();
+        let report = check_body("https://api.bgm.tv/v0/episodes/1", &body).unwrap();
+        assert_eq!(report.schema, "Episode");
+        assert_eq!(report.error, None);
+        assert_eq!(report.unknown_fields, vec!["extra"]);
+        assert!(report.missing_fields.is_empty());
+
+        let report = check_body("/v0/episodes/1", br#"{"id":"x"}"#).unwrap();
+        assert!(report.error.unwrap().contains("invalid type"));
+
+        let report = check_body(
+            "/v0/subjects/1/persons",
+            br#"[{"id":1,"name":"","type":1,"career":[],"relation":"","eps":"","new":1}]"#,
+        )
+        .unwrap();
+        assert_eq!(report.unknown_fields, vec!["[].new"]);
+
+        let episode = std::str::from_utf8(&body).unwrap();
+        let report = check_body(
+            "/v0/users/-/collections/1/episodes",
+            format!(
+                r#"{{"total":1,"limit":100,"offset":0,"data":[{{"episode":{},"type":2}}]}}"#,
+                episode
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(report.unknown_fields, vec!["data[].episode.extra"]);
+        assert_eq!(report.missing_fields, vec!["data[].updated_at"]);
+
+        assert!(check_body("/v0/subjects/1/image", b"").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_schema_check() {
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                r#"{"id":1,"username":"sai","nickname":"Sai","sign":"","avatar":{}}"#,
+            ),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .build()
+            .unwrap();
+
+        let report = SchemaCheck::new()
+            .users(["sai", "missing"])
+            .run(&client)
+            .await;
+        assert!(report.has_drift());
+        assert_eq!(report.entries[0].unknown_fields, vec!["avatar"]);
+        assert!(report.entries[1].error.is_some());
+        assert_eq!(report.drifted().count(), 2);
+
+        let json: Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["entries"][0]["schema"], "User");
+    }
+
+    #[cfg(feature = "recorder")]
+    #[tokio::test]
+    async fn test_check_recorded() {
+        use crate::client::recorder::Recorder;
+
+        let dir = std::env::temp_dir().join(format!("bgmtv-schema-{}", std::process::id()));
+        let server = MockServer::start(vec![
+            MockResponse::new(
+                200,
+                r#"{"id":1,"username":"sai","nickname":"Sai","sign":""}"#,
+            ),
+            MockResponse::new(404, r#"{"title":"Not Found","description":""}"#),
+        ])
+        .await;
+        let client = Client::builder()
+            .base_url(server.base_url.clone())
+            .transport(Recorder::record(&dir))
+            .build()
+            .unwrap();
+        client.get_user("sai").await.unwrap();
+        client.get_user("missing").await.unwrap_err();
+
+        let report = check_recorded(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].schema, "User");
+        assert!(!report.has_drift());
+    }
+}
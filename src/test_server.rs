@@ -0,0 +1,368 @@
+//! # Test Server (测试服务器)
+//!
+//! [`TestServer`] 是一个嵌入式的 HTTP 服务器，按 [`ApiSnapshot`] 中录制的响应模拟 bgm.tv API，
+//! 使集成测试不依赖网络与 API 的可用性。本 crate 支持的每个 API 在内置快照 ([`ApiSnapshot::builtin`]) 中都有对应的响应，
+//! 下游应用也可以在集成测试中复用，或通过 [`ApiSnapshot::route`] 覆盖、追加自己的响应。
+//! 本 crate 中请求 API 的文档示例也通过它回放内置快照，不访问网络。
+//!
+//! 快照中的资源为条目 `3559`、章节 `8`、角色 `3498`、人物 `1` 与用户 `sai`，
+//! 快照的格式与内容变化时 [`SNAPSHOT_VERSION`] 会递增。
+//!
+//! ## Example
+//!
+//! ```
+//! use bgmtv::test_server::TestServer;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! let server = TestServer::start().await?;
+//! let client = server.client()?;
+//!
+//! let subject = client.get_subject(3559).await?;
+//! assert_eq!(subject.name, "とある魔術の禁書目録");
+//! assert_eq!(server.requests(), vec!["GET /v0/subjects/3559"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{
+    client::{Client, ClientBuilder, ClientBuilderError},
+    mock::{read_request, write_response, MockResponse},
+};
+
+/// 内置快照的版本
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// API snapshot (API 快照)
+///
+/// 录制的 API 响应，可以通过 [`from_json`](Self::from_json) 从 JSON 读取，格式与内置快照相同。
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ApiSnapshot {
+    /// 快照的版本
+    pub version: u32,
+
+    /// 录制的响应
+    pub routes: Vec<SnapshotRoute>,
+}
+
+/// 快照中的一个响应
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct SnapshotRoute {
+    /// 请求方法，例如 `GET`
+    pub method: String,
+
+    /// 请求路径，例如 `/v0/subjects/3559`
+    pub path: String,
+
+    /// 请求必须包含的查询参数，为空时匹配任意查询参数
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub query: BTreeMap<String, String>,
+
+    /// 状态码，默认为 `200`
+    #[serde(default = "default_status")]
+    pub status: u16,
+
+    /// 响应头
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// JSON 响应体
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+
+    /// 非 JSON 的响应体，例如图片
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+impl SnapshotRoute {
+    /// 创建一个返回 JSON 响应体的响应
+    pub fn json(
+        method: impl Into<String>,
+        path: impl Into<String>,
+        status: u16,
+        body: serde_json::Value,
+    ) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            query: BTreeMap::new(),
+            status,
+            headers: BTreeMap::new(),
+            body: Some(body),
+            text: None,
+        }
+    }
+
+    /// 追加一个请求必须包含的查询参数
+    pub fn query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.insert(key.into(), value.into());
+        self
+    }
+
+    /// 是否匹配请求
+    fn matches(&self, method: &str, url: &url::Url) -> bool {
+        self.method.eq_ignore_ascii_case(method)
+            && self.path == url.path()
+            && self
+                .query
+                .iter()
+                .all(|(key, value)| url.query_pairs().any(|(k, v)| k == *key && v == *value))
+    }
+
+    fn response(&self) -> MockResponse {
+        let (content_type, body) = match (&self.body, &self.text) {
+            (Some(body), _) => ("application/json", body.to_string().into_bytes()),
+            (None, Some(text)) => ("text/plain", text.clone().into_bytes()),
+            (None, None) => ("text/plain", Vec::new()),
+        };
+        let mut res = MockResponse::new(self.status, body);
+        if !self.headers.contains_key("content-type") {
+            res = res.header("content-type", content_type);
+        }
+        for (name, value) in &self.headers {
+            res = res.header(name, value);
+        }
+        res
+    }
+}
+
+impl ApiSnapshot {
+    /// 内置的快照，包含本 crate 支持的每个 API 的响应
+    pub fn builtin() -> Self {
+        let snapshot = Self::from_json(include_str!("test_server/snapshot.json"))
+            .expect("builtin snapshot is valid");
+        debug_assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        snapshot
+    }
+
+    /// 从 JSON 读取快照
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// 添加一个响应，优先于已有的响应匹配
+    pub fn route(mut self, route: SnapshotRoute) -> Self {
+        self.routes.insert(0, route);
+        self
+    }
+
+    /// 查找匹配请求的响应，有多个匹配时使用查询参数条件最多的一个
+    fn find(&self, method: &str, url: &url::Url) -> Option<&SnapshotRoute> {
+        self.routes
+            .iter()
+            .enumerate()
+            .filter(|(_, route)| route.matches(method, url))
+            .max_by_key(|(i, route)| (route.query.len(), std::cmp::Reverse(*i)))
+            .map(|(_, route)| route)
+    }
+}
+
+/// Test server (测试服务器)
+///
+/// 参见 [模块文档](self)。服务器在 [`TestServer`] 被 drop 时停止。
+#[derive(Debug)]
+pub struct TestServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+    task: JoinHandle<()>,
+}
+
+impl TestServer {
+    /// 使用内置的快照启动服务器
+    pub async fn start() -> io::Result<Self> {
+        Self::with_snapshot(ApiSnapshot::builtin()).await
+    }
+
+    /// 使用 `snapshot` 启动服务器
+    ///
+    /// 没有匹配的响应时返回 `404 Not Found`。
+    pub async fn with_snapshot(snapshot: ApiSnapshot) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let snapshot = Arc::new(snapshot);
+        let recorded = requests.clone();
+        let origin = url::Url::parse(&base_url).expect("local address is a valid URL");
+        let task = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let snapshot = snapshot.clone();
+                let recorded = recorded.clone();
+                let origin = origin.clone();
+                tokio::spawn(async move {
+                    let Some(req) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    let mut parts = req.line.split(' ');
+                    let (method, target) = (parts.next().unwrap_or_default(), parts.next());
+                    let Some(url) = target.and_then(|target| origin.join(target).ok()) else {
+                        return;
+                    };
+                    recorded
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push(format!("{} {}", method, url.path()));
+
+                    let res = match snapshot.find(method, &url) {
+                        Some(route) => route.response(),
+                        None => MockResponse::new(
+                            404,
+                            serde_json::json!({
+                                "title": "Not Found",
+                                "description": format!("no route in snapshot for {} {}", method, url.path()),
+                            })
+                            .to_string(),
+                        ),
+                    };
+                    write_response(&mut stream, &res).await;
+                });
+            }
+        });
+
+        Ok(Self {
+            base_url,
+            requests,
+            task,
+        })
+    }
+
+    /// 服务器的 base URL，例如 `http://127.0.0.1:12345`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 将 API 与 OAuth API 的 base URL 设置为此服务器的 [`ClientBuilder`]
+    pub fn client_builder(&self) -> ClientBuilder {
+        let mut builder = Client::builder();
+        builder
+            .base_url(self.base_url.clone())
+            .oauth_base_url(self.base_url.clone());
+        builder
+    }
+
+    /// 使用 [`client_builder`](Self::client_builder) 构建 [`Client`]
+    pub fn client(&self) -> Result<Client, ClientBuilderError> {
+        self.client_builder().build()
+    }
+
+    /// 已收到的请求，按顺序排列，格式为 `GET /v0/subjects/3559`
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::*, prelude::*};
+
+    #[tokio::test]
+    async fn test_client_surface() {
+        let server = TestServer::start().await.unwrap();
+        let client = server.client_builder().token("token").build().unwrap();
+        let api: &dyn BgmApi = &client;
+
+        let search = SearchSubjectsQuery::new("禁书目录");
+        assert_eq!(api.search_subjects(search).await.unwrap().data[0].id, 3559);
+        let subjects = GetSubjectsQuery::new(SubjectType::Book);
+        assert_eq!(api.get_subjects(subjects).await.unwrap().data.len(), 1);
+        assert_eq!(api.get_subject(3559).await.unwrap().volumes, 24);
+        let image = api.get_subject_image(3559, ImageType::Large).await;
+        assert_eq!(image.unwrap(), "image");
+        assert_eq!(api.get_subject_persons(3559).await.unwrap()[0].id, 4736);
+        let characters = api.get_subject_characters(3559).await.unwrap();
+        assert_eq!(characters[0].actors[0].name, "阿部敦");
+        assert_eq!(api.get_subject_subjects(3559).await.unwrap()[0].id, 14514);
+        let episodes = GetEpisodesQuery::new(3559);
+        assert_eq!(api.get_episodes(episodes).await.unwrap().data[0].id, 8);
+        assert_eq!(api.get_episode(8).await.unwrap().sort, 1);
+
+        assert_eq!(api.get_character(3498).await.unwrap().name, "上条当麻");
+        assert!(api
+            .get_character_image(3498, ImageType::Small)
+            .await
+            .is_ok());
+        assert_eq!(api.get_character_subjects(3498).await.unwrap()[0].id, 3559);
+        assert_eq!(api.get_character_persons(3498).await.unwrap()[0].id, 4878);
+
+        assert_eq!(api.get_person(1).await.unwrap().birth_year, Some(1980));
+        assert!(api.get_person_image(1, ImageType::Small).await.is_ok());
+        assert_eq!(api.get_person_subjects(1).await.unwrap()[0].id, 1014);
+        assert_eq!(api.get_person_characters(1).await.unwrap()[0].id, 3575);
+
+        assert_eq!(api.get_user("sai").await.unwrap().id, 1);
+        assert!(api.get_user_avatar("sai", ImageType::Large).await.is_ok());
+        assert_eq!(api.get_me().await.unwrap().username, "sai");
+        let collections = GetUserCollectionsQuery::new("sai");
+        let collections = api.get_user_collections(collections).await.unwrap();
+        assert_eq!(collections.data[0].subject_id, 3559);
+        let episodes = api.get_user_episode_collections(3559).await.unwrap();
+        assert_eq!(episodes[0].episode.id, 8);
+        let payload = UserCollectionModifyPayload::default().ep_status(1);
+        api.patch_user_collection(3559, &payload).await.unwrap();
+        assert!(api
+            .token_status()
+            .await
+            .unwrap()
+            .scope
+            .contains(Scopes::WRITE_COLLECTION));
+
+        // 每个内置的响应都被请求过
+        let requests = server.requests();
+        for route in ApiSnapshot::builtin().routes {
+            let request = format!("{} {}", route.method, route.path);
+            assert!(requests.contains(&request), "{} is not covered", request);
+        }
+
+        let err = client.get_subject(1).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DepsError::Api(BgmApiError { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_route() {
+        let snapshot = ApiSnapshot::builtin().route(
+            SnapshotRoute::json(
+                "GET",
+                "/v0/episodes",
+                200,
+                serde_json::json!({"total": 0, "limit": 100, "offset": 0, "data": []}),
+            )
+            .query("type", "1"),
+        );
+        let server = TestServer::with_snapshot(snapshot).await.unwrap();
+        let api: &dyn BgmApi = &server.client().unwrap();
+
+        let query = GetEpisodesQuery::new(3559);
+        assert_eq!(api.get_episodes(query).await.unwrap().total, 1);
+        let query = GetEpisodesQuery::new(3559).r#type(EpisodeType::SP);
+        assert_eq!(api.get_episodes(query).await.unwrap().total, 0);
+    }
+}
@@ -0,0 +1,619 @@
+//! # Time (时间抽象)
+//!
+//! 本 crate 内部所有的等待 (重试退避、限流、轮询等) 都通过 [`Clock`] 进行，
+//! 可以通过 [`ClientBuilder::clock`](crate::client::ClientBuilder::clock) 注入 [`ManualClock`]，
+//! 使测试中的时间可以确定性地快进。
+//!
+//! 此外提供了 [`Date`] 与 [`DateTime`]，用于解析 API 返回的 `2004-04-24` 格式的日期与
+//! `2024-01-01T12:00:00+08:00` 格式的时间。启用 `date` feature 后，[`Date`] 与 [`DateTime`] 可以分别与
+//! `chrono::NaiveDate`、`chrono::DateTime<FixedOffset>` 相互转换，API 类型中的时间也提供了直接返回 chrono 类型的方法。
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Future returned by [`Clock::sleep`]
+pub type SleepFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// # Clock
+///
+/// 提供当前时间与等待的能力。
+pub trait Clock: Send + Sync {
+    /// 当前时间
+    fn now(&self) -> Instant;
+
+    /// 等待 `duration`
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_>;
+
+    /// 当前的系统时间，用于计算 `Retry-After` 等 HTTP 日期距今的时长
+    ///
+    /// 默认为 [`SystemTime::now`]。
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// 基于 tokio 的默认 [`Clock`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// 手动推进的 [`Clock`]
+///
+/// [`sleep`](Clock::sleep) 会立即返回并将时间推进 `duration`，适用于测试。
+///
+/// ## Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bgmtv::time::{Clock, ManualClock};
+/// # #[tokio::main]
+/// # async fn main() {
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+///
+/// clock.sleep(Duration::from_secs(60)).await;
+/// clock.advance(Duration::from_secs(1));
+///
+/// assert_eq!(clock.now() - start, Duration::from_secs(61));
+/// assert_eq!(clock.slept(), vec![Duration::from_secs(60)]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    start: Instant,
+    system_start: SystemTime,
+    state: Arc<Mutex<ManualClockState>>,
+}
+
+#[derive(Debug, Default)]
+struct ManualClockState {
+    elapsed: Duration,
+    slept: Vec<Duration>,
+}
+
+impl ManualClock {
+    /// 创建一个从当前时间开始的 [`ManualClock`]
+    pub fn new() -> Self {
+        Self::at(SystemTime::now())
+    }
+
+    /// 创建一个系统时间从 `system_time` 开始的 [`ManualClock`]
+    pub fn at(system_time: SystemTime) -> Self {
+        Self {
+            start: Instant::now(),
+            system_start: system_time,
+            state: Default::default(),
+        }
+    }
+
+    /// 将时间推进 `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.state().elapsed += duration;
+    }
+
+    /// 所有 [`sleep`](Clock::sleep) 调用的等待时长，按调用顺序排列
+    pub fn slept(&self) -> Vec<Duration> {
+        self.state().slept.clone()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, ManualClockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.state().elapsed
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.system_start + self.state().elapsed
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        {
+            let mut state = self.state();
+            state.elapsed += duration;
+            state.slept.push(duration);
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// 将 `2024-01-01T12:00:00+08:00` 格式的 RFC 3339 时间解析为 Unix 时间戳 (秒)
+///
+/// 秒以下的部分会被忽略，格式不正确时返回 `None`。
+pub(crate) fn parse_rfc3339(s: &str) -> Option<i64> {
+    DateTime::parse(s).as_ref().map(DateTime::unix_timestamp)
+}
+
+/// 解析章节时长，例如 `24m`、`1h30m`、`24分`、`24:30`、`00:24:30`
+///
+/// 冒号分隔的格式为 `时:分:秒` 或 `分:秒`，只有数字时视为分钟，格式不正确或溢出时返回 `None`。
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    let digits = |part: &str| -> Option<u64> {
+        (!part.is_empty() && part.bytes().all(|b| b.is_ascii_digit())).then(|| part.parse().ok())?
+    };
+
+    if s.contains(':') {
+        let parts = s.split(':').map(digits).collect::<Option<Vec<_>>>()?;
+        let seconds = match parts[..] {
+            [minutes, seconds] => minutes.checked_mul(60)?.checked_add(seconds)?,
+            [hours, minutes, seconds] => hours
+                .checked_mul(3600)?
+                .checked_add(minutes.checked_mul(60)?)?
+                .checked_add(seconds)?,
+            _ => return None,
+        };
+        return Some(Duration::from_secs(seconds));
+    }
+    if let Some(minutes) = digits(s) {
+        return Some(Duration::from_secs(minutes.checked_mul(60)?));
+    }
+
+    let mut seconds: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .filter(|&end| end > 0)?;
+        let value: u64 = rest[..end].parse().ok()?;
+        rest = rest[end..].trim_start();
+
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let scale = match rest[..unit_end].to_ascii_lowercase().as_str() {
+            "h" | "hr" | "hrs" | "hour" | "hours" | "小时" | "時間" => 3600,
+            "m" | "min" | "mins" | "minute" | "minutes" | "分" | "分钟" | "分鐘" => 60,
+            "s" | "sec" | "secs" | "second" | "seconds" | "秒" => 1,
+            _ => return None,
+        };
+        seconds = seconds.checked_add(value.checked_mul(scale)?)?;
+        rest = rest[unit_end..].trim_start();
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// # Date (日期)
+///
+/// 公历日期，不含时间与时区，例如 [`Subject::date`](crate::types::Subject::date) 中的 `2004-04-24`。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::time::Date;
+/// let date = Date::parse("2004-04-24").unwrap();
+/// assert_eq!((date.year(), date.month(), date.day()), (2004, 4, 24));
+/// assert_eq!(date.to_string(), "2004-04-24");
+///
+/// assert_eq!(Date::parse("2004-02-30"), None);
+/// assert_eq!(Date::parse(""), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// 根据年、月、日创建日期，日期不存在时返回 `None`
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month).contains(&day).then_some(Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// 解析 `2004-04-24` 格式的日期
+    ///
+    /// 忽略首尾的空白，月与日可以省略前导零；格式不正确或日期不存在时返回 `None`。
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().splitn(3, '-').map(|part| {
+            part.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| part.parse::<u32>().ok())
+                .flatten()
+        });
+        let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+        Self::from_ymd(i32::try_from(year).ok()?, month, day)
+    }
+
+    /// 自 1970-01-01 起的天数对应的日期
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// 年
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// 月，从 1 开始
+    pub fn month(&self) -> u32 {
+        u32::from(self.month)
+    }
+
+    /// 日，从 1 开始
+    pub fn day(&self) -> u32 {
+        u32::from(self.day)
+    }
+
+    /// 自 1970-01-01 起的天数
+    pub fn days_since_epoch(&self) -> i64 {
+        days_from_civil(
+            i64::from(self.year),
+            i64::from(self.month),
+            i64::from(self.day),
+        )
+    }
+
+    /// 转换为 `chrono::NaiveDate`，超出 chrono 可表示的范围时返回 `None`
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn to_chrono(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(self.year, self.month(), self.day())
+    }
+}
+
+#[cfg(feature = "date")]
+#[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+impl From<chrono::NaiveDate> for Date {
+    fn from(date: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        Self {
+            year: date.year(),
+            month: date.month() as u8,
+            day: date.day() as u8,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// # DateTime (时间)
+///
+/// 带有 UTC 偏移的时间，精确到秒，例如 [`PersonDetail::last_modified`](crate::types::PersonDetail::last_modified)
+/// 中的 `2024-01-01T12:00:00+08:00`。
+///
+/// 比较与排序按时间先后进行，同一时刻的不同偏移按偏移排序；
+/// 需要判断是否为同一时刻时比较 [`unix_timestamp`](DateTime::unix_timestamp)。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::time::DateTime;
+/// let a = DateTime::parse("2024-01-01T12:00:00+08:00").unwrap();
+/// let b = DateTime::parse("2024-01-01T05:00:00Z").unwrap();
+/// assert!(a < b);
+/// assert_eq!(b.unix_timestamp() - a.unix_timestamp(), 3600);
+/// assert_eq!(a.to_string(), "2024-01-01T12:00:00+08:00");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    timestamp: i64,
+    offset: i32,
+}
+
+impl DateTime {
+    /// 根据 Unix 时间戳 (秒) 与 UTC 偏移 (秒) 创建时间，偏移不在 ±24 小时内时返回 `None`
+    pub fn from_unix_timestamp(timestamp: i64, offset: i32) -> Option<Self> {
+        (offset.abs() < 86400).then_some(Self { timestamp, offset })
+    }
+
+    /// 解析 `2024-01-01T12:00:00+08:00` 格式的 RFC 3339 时间
+    ///
+    /// 秒以下的部分会被忽略，格式不正确或时间不存在时返回 `None`。
+    pub fn parse(s: &str) -> Option<Self> {
+        let num = |range: std::ops::Range<usize>| -> Option<u32> {
+            let part = s.get(range)?;
+            part.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| part.parse().ok())?
+        };
+        let bytes = s.as_bytes();
+        if bytes.len() < 20
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || !matches!(bytes[10], b'T' | b't' | b' ')
+            || bytes[13] != b':'
+            || bytes[16] != b':'
+        {
+            return None;
+        }
+
+        let date = Date::from_ymd(num(0..4)? as i32, num(5..7)?, num(8..10)?)?;
+        let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let mut rest = &s[19..];
+        if let Some(fraction) = rest.strip_prefix('.') {
+            rest = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+        }
+        let digits = |a: u8, b: u8| -> Option<i32> {
+            (a.is_ascii_digit() && b.is_ascii_digit())
+                .then(|| i32::from(a - b'0') * 10 + i32::from(b - b'0'))
+        };
+        let offset = match *rest.as_bytes() {
+            [b'Z' | b'z'] => 0,
+            [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+                let (hours, minutes) = (digits(h1, h2)?, digits(m1, m2)?);
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+                let sign = if sign == b'+' { 1 } else { -1 };
+                sign * (hours * 3600 + minutes * 60)
+            }
+            _ => return None,
+        };
+
+        let seconds = i64::from(hour * 3600 + minute * 60 + second);
+        Some(Self {
+            timestamp: date.days_since_epoch() * 86400 + seconds - i64::from(offset),
+            offset,
+        })
+    }
+
+    /// Unix 时间戳 (秒)
+    pub fn unix_timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// UTC 偏移 (秒)，例如 `+08:00` 为 `28800`
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// 所在偏移下的日期
+    pub fn date(&self) -> Date {
+        Date::from_days_since_epoch(self.local_seconds().div_euclid(86400))
+    }
+
+    /// 转换为 `chrono::DateTime<FixedOffset>`，超出 chrono 可表示的范围时返回 `None`
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        let offset = chrono::FixedOffset::east_opt(self.offset)?;
+        Some(chrono::DateTime::from_timestamp(self.timestamp, 0)?.with_timezone(&offset))
+    }
+
+    /// 所在偏移下的本地秒数
+    fn local_seconds(&self) -> i64 {
+        self.timestamp + i64::from(self.offset)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.local_seconds().rem_euclid(86400);
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date(),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+        if self.offset == 0 {
+            return f.write_str("Z");
+        }
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let offset = self.offset.abs();
+        write!(f, "{}{:02}:{:02}", sign, offset / 3600, offset / 60 % 60)
+    }
+}
+
+#[cfg(feature = "date")]
+#[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+impl From<chrono::DateTime<chrono::FixedOffset>> for DateTime {
+    /// 秒以下的部分会被忽略
+    fn from(time: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self {
+            timestamp: time.timestamp(),
+            offset: time.offset().local_minus_utc(),
+        }
+    }
+}
+
+/// 将 `2024-01-01` 格式的日期解析为自 1970-01-01 起的天数，格式不正确时返回 `None`
+pub(crate) fn parse_date(s: &str) -> Option<i64> {
+    Date::parse(s).as_ref().map(Date::days_since_epoch)
+}
+
+/// 自 1970-01-01 起的天数转换为公历日期 `(year, month, day)`
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// 公历日期转换为自 1970-01-01 起的天数
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// [`Clock`] trait object held by [`Client`](crate::client::Client)
+#[derive(Clone)]
+pub(crate) struct SharedClock(pub(crate) Arc<dyn Clock>);
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self(Arc::new(TokioClock))
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_rfc3339("2024-01-01T12:00:00+08:00"),
+            Some(1_704_081_600)
+        );
+        assert_eq!(
+            parse_rfc3339("2024-01-01T04:00:00.123Z"),
+            parse_rfc3339("2024-01-01T12:00:00+08:00")
+        );
+        assert_eq!(parse_rfc3339("2024-01-01"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00"), None);
+        assert_eq!(parse_rfc3339("2024-02-30T12:00:00Z"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T24:00:00Z"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00中中"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00+中:0"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00.5中"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00++8:00"), None);
+        assert_eq!(parse_rfc3339("2024-01-01T12:00:00+08:-1"), None);
+
+        let time = DateTime::parse("2023-12-31T20:30:00-05:00").unwrap();
+        assert_eq!(time.offset(), -18000);
+        assert_eq!(time.date().to_string(), "2023-12-31");
+        assert_eq!(time.to_string(), "2023-12-31T20:30:00-05:00");
+        assert_eq!(
+            DateTime::from_unix_timestamp(time.unix_timestamp(), 0)
+                .unwrap()
+                .to_string(),
+            "2024-01-01T01:30:00Z"
+        );
+        assert_eq!(DateTime::from_unix_timestamp(0, 86400), None);
+
+        assert_eq!(parse_date("1970-01-02"), Some(1));
+        assert_eq!(parse_date("2024-03-01"), Some(19783));
+        assert_eq!(parse_date(""), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-02-29"), Some(19782));
+        assert_eq!(parse_date("2024-+3-01"), None);
+        assert_eq!(Date::from_days_since_epoch(19782).to_string(), "2024-02-29");
+
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19783), (2024, 3, 1));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[cfg(feature = "date")]
+    #[test]
+    fn test_chrono() {
+        let time = DateTime::parse("2024-01-01T12:00:00+08:00").unwrap();
+        let chrono_time = time.to_chrono().unwrap();
+        assert_eq!(
+            chrono_time,
+            chrono::DateTime::parse_from_rfc3339("2024-01-01T12:00:00+08:00").unwrap()
+        );
+        assert_eq!(chrono_time.offset().local_minus_utc(), 28800);
+        assert_eq!(DateTime::from(chrono_time), time);
+        assert_eq!(
+            DateTime::from_unix_timestamp(i64::MAX, 0)
+                .unwrap()
+                .to_chrono(),
+            None
+        );
+
+        let date = Date::from_ymd(2004, 4, 24).unwrap();
+        let naive = date.to_chrono().unwrap();
+        assert_eq!(naive, chrono::NaiveDate::from_ymd_opt(2004, 4, 24).unwrap());
+        assert_eq!(Date::from(naive), date);
+        assert_eq!(Date::from_ymd(i32::MAX, 1, 1).unwrap().to_chrono(), None);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let minutes = |m: u64| Some(Duration::from_secs(m * 60));
+        assert_eq!(parse_duration("24m"), minutes(24));
+        assert_eq!(parse_duration("24min"), minutes(24));
+        assert_eq!(parse_duration("24 mins"), minutes(24));
+        assert_eq!(parse_duration("24分"), minutes(24));
+        assert_eq!(parse_duration("24分钟"), minutes(24));
+        assert_eq!(parse_duration("24"), minutes(24));
+        assert_eq!(parse_duration("1h30m"), minutes(90));
+        assert_eq!(parse_duration("1 hour 30 minutes"), minutes(90));
+        assert_eq!(parse_duration("1時間30分"), minutes(90));
+        assert_eq!(parse_duration("24m30s"), Some(Duration::from_secs(1470)));
+        assert_eq!(parse_duration("00:24:30"), Some(Duration::from_secs(1470)));
+        assert_eq!(parse_duration("24:30"), Some(Duration::from_secs(1470)));
+        assert_eq!(parse_duration(" 01:30:00 "), minutes(90));
+
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("24x"), None);
+        assert_eq!(parse_duration("1:2:3:4"), None);
+        assert_eq!(parse_duration("24::30"), None);
+        assert_eq!(parse_duration("-24m"), None);
+        assert_eq!(parse_duration("99999999999999999:00:00"), None);
+        assert_eq!(parse_duration("999999999999999999:00"), None);
+        assert_eq!(parse_duration("999999999999999999"), None);
+        assert_eq!(parse_duration("9999999999999999h"), None);
+        assert_eq!(parse_duration("18446744073709551615s 1s"), None);
+    }
+}
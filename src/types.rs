@@ -2,10 +2,156 @@
 
 #![allow(missing_docs)]
 
+use std::{fmt, str::FromStr};
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
+use crate::{
+    error::{ParseEnumError, ParseFilterError},
+    time::Date,
+};
+
+/// 为带有兜底变体的数字枚举实现与数字之间的转换
+///
+/// 枚举通过 `#[serde(from = "..", into = "..")]` 以数字序列化，未知的数字会转换为兜底变体，
+/// 服务端新增取值时不会导致整个响应反序列化失败。
+macro_rules! impl_repr_enum {
+    ($ty:ident: $repr:ty, $fallback:ident { $($variant:ident = $value:literal),* $(,)? }) => {
+        impl $ty {
+            /// 所有已知的取值，按代码排序，不包含兜底变体
+            pub const ALL: &'static [$ty] = &[$($ty::$variant),*];
+        }
+
+        impl From<$repr> for $ty {
+            fn from(value: $repr) -> Self {
+                match value {
+                    $($value => $ty::$variant,)*
+                    other => $ty::$fallback(other),
+                }
+            }
+        }
+
+        impl From<$ty> for $repr {
+            fn from(value: $ty) -> Self {
+                match value {
+                    $($ty::$variant => $value,)*
+                    $ty::$fallback(other) => other,
+                }
+            }
+        }
+    };
+}
+
+/// 为枚举实现中文与英文名称，[`Display`](fmt::Display) 输出中文名称
+macro_rules! impl_labels {
+    ($ty:ident { $($pat:pat => ($zh:expr, $en:expr)),* $(,)? }) => {
+        impl $ty {
+            /// bgm.tv 上显示的中文名称
+            pub fn label(&self) -> &str {
+                match self {
+                    $($pat => $zh,)*
+                }
+            }
+
+            /// 英文名称
+            pub fn label_en(&self) -> &str {
+                match self {
+                    $($pat => $en,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.label())
+            }
+        }
+    };
+}
+
+/// 定义包装 `u64` 的 ID 类型
+macro_rules! define_ids {
+    ($($(#[$meta:meta])* $ty:ident;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+            #[serde(transparent)]
+            pub struct $ty(pub u64);
+
+            impl From<u64> for $ty {
+                fn from(id: u64) -> Self {
+                    $ty(id)
+                }
+            }
+
+            impl From<$ty> for u64 {
+                fn from(id: $ty) -> Self {
+                    id.0
+                }
+            }
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+        )*
+    };
+}
+
+define_ids! {
+    /// Subject ID (条目 ID)
+    ///
+    /// 接受条目 ID 的方法参数为 `impl Into<SubjectId>`，可以直接传入 `u64`，
+    /// 也可以传入 `SubjectId` 以避免与其他 ID 混用。
+    SubjectId;
+    /// Episode ID (章节 ID)
+    EpisodeId;
+    /// Character ID (角色 ID)
+    CharacterId;
+    /// Person ID (人物 ID)
+    PersonId;
+    /// Index ID (目录 ID)
+    IndexId;
+}
+
+/// Access Token (授权 Token 信息)
+///
+/// 由 [`Client::token_status`](crate::client::Client::token_status) 返回。
+/// [`Debug`] 输出中的 `access_token` 会被隐去。
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct AccessToken {
+    /// Token
+    pub access_token: String,
+
+    /// 应用 ID
+    pub client_id: String,
+
+    /// 用户 ID
+    pub user_id: u64,
+
+    /// 过期时间 (Unix 时间戳)
+    pub expires: u64,
+
+    /// 授予的权限
+    #[serde(default)]
+    pub scope: Scopes,
+}
+
+impl fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("access_token", &"***")
+            .field("client_id", &self.client_id)
+            .field("user_id", &self.user_id)
+            .field("expires", &self.expires)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
 /// Blood Type (血型)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
 #[repr(u8)]
@@ -16,6 +162,70 @@ pub enum BloodType {
     O = 4,
 }
 
+impl_labels!(BloodType {
+    BloodType::A => ("A型", "A"),
+    BloodType::B => ("B型", "B"),
+    BloodType::AB => ("AB型", "AB"),
+    BloodType::O => ("O型", "O"),
+});
+
+impl BloodType {
+    /// 所有取值
+    pub const ALL: &'static [BloodType] =
+        &[BloodType::A, BloodType::B, BloodType::AB, BloodType::O];
+}
+
+/// Browse Sort (浏览条目的排序方式)
+///
+/// 用于 [`Client::get_subjects`](crate::client::Client::get_subjects)，序列化为查询参数 `sort` 的值。
+/// API 新增的排序方式可以通过 [`BrowseSort::Raw`] 原样发送。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrowseSort {
+    /// 按发布日期
+    Date,
+
+    /// 按排名
+    Rank,
+
+    /// 原样发送的排序值
+    #[serde(untagged)]
+    Raw(String),
+}
+
+impl BrowseSort {
+    /// 所有已知的取值，不包含 [`BrowseSort::Raw`]
+    pub const ALL: &'static [BrowseSort] = &[BrowseSort::Date, BrowseSort::Rank];
+
+    /// 查询参数的值
+    pub fn as_str(&self) -> &str {
+        match self {
+            BrowseSort::Date => "date",
+            BrowseSort::Rank => "rank",
+            BrowseSort::Raw(sort) => sort,
+        }
+    }
+}
+
+impl fmt::Display for BrowseSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BrowseSort {
+    type Err = ParseEnumError;
+
+    /// 解析 `date` 或 `rank`，不区分大小写；其他值需要显式使用 [`BrowseSort::Raw`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_enum(
+            s,
+            "browse sort",
+            &[(BrowseSort::Date, &["date"]), (BrowseSort::Rank, &["rank"])],
+        )
+    }
+}
+
 /// Character Detail (角色详情)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct CharacterDetail {
@@ -29,6 +239,7 @@ pub struct CharacterDetail {
     pub r#type: CharacterType,
 
     /// 角色图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 角色简介
@@ -51,6 +262,13 @@ pub struct CharacterDetail {
     pub stat: Stat,
 }
 
+impl CharacterDetail {
+    /// 角色是否已被锁定
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
 /// Character Person (角色人物)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct CharacterPerson {
@@ -64,6 +282,7 @@ pub struct CharacterPerson {
     pub r#type: CharacterType,
 
     /// 人物图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 条目 ID
@@ -82,22 +301,40 @@ pub struct CharacterPerson {
 }
 
 /// Character Type (角色类型)
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
 pub enum CharacterType {
     /// 角色
-    Character = 1,
+    Character,
 
     /// 机体
-    Mechanic = 2,
+    Mechanic,
 
     /// 舰船
-    Ship = 3,
+    Ship,
 
     /// 组织
-    Organization = 4,
+    Organization,
+
+    /// 未知的角色类型
+    Unknown(u8),
 }
 
+impl_repr_enum!(CharacterType: u8, Unknown {
+    Character = 1,
+    Mechanic = 2,
+    Ship = 3,
+    Organization = 4,
+});
+
+impl_labels!(CharacterType {
+    CharacterType::Character => ("角色", "Character"),
+    CharacterType::Mechanic => ("机体", "Mechanic"),
+    CharacterType::Ship => ("舰船", "Ship"),
+    CharacterType::Organization => ("组织", "Organization"),
+    CharacterType::Unknown(_) => ("未知", "Unknown"),
+});
+
 /// Episode (章节)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Episode {
@@ -119,13 +356,13 @@ pub struct Episode {
     /// 条目内的集数，从 1 开始。非本篇剧集此字段无意义
     pub ep: Option<u64>,
 
-    /// 发布日期
+    /// 发布日期，例如 `2024-04-01`。未定档时为空字符串，可以通过 [`Episode::airdate_parsed`] 解析
     pub airdate: String,
 
     /// 评论数
     pub comment: u64,
 
-    /// 原始时长
+    /// 原始时长，例如 `24m`、`00:24:30`，可以通过 [`Episode::duration_parsed`] 解析
     pub duration: String,
 
     /// 简介
@@ -138,35 +375,115 @@ pub struct Episode {
     pub duration_seconds: Option<u64>,
 }
 
+impl Episode {
+    /// 解析后的发布日期，未定档或只有年月等不完整日期时返回 `None`
+    pub fn airdate_parsed(&self) -> Option<crate::time::Date> {
+        crate::time::Date::parse(&self.airdate)
+    }
+
+    /// 章节是否已在 `today` 或之前发布，发布日期未知时返回 `false`
+    pub fn has_aired(&self, today: crate::time::Date) -> bool {
+        self.airdate_parsed()
+            .is_some_and(|airdate| airdate <= today)
+    }
+
+    /// 距离发布还有多少天，当天发布为 `0`，已发布时为负数，发布日期未知时返回 `None`
+    pub fn days_until_air(&self, today: crate::time::Date) -> Option<i64> {
+        self.airdate_parsed()
+            .map(|airdate| airdate.days_since_epoch() - today.days_since_epoch())
+    }
+
+    /// 章节时长
+    ///
+    /// 优先使用服务器解析的 [`duration_seconds`](Episode::duration_seconds)，
+    /// 其为空或为 `0` 时解析原始时长 [`duration`](Episode::duration)，两者都无法使用时返回 `None`。
+    pub fn duration_parsed(&self) -> Option<std::time::Duration> {
+        match self.duration_seconds {
+            Some(seconds) if seconds > 0 => Some(std::time::Duration::from_secs(seconds)),
+            _ => crate::time::parse_duration(&self.duration),
+        }
+    }
+}
+
 /// Episode Type (章节类型)
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
 pub enum EpisodeType {
     /// 本篇
-    MainStory = 0,
+    MainStory,
 
     /// 特别篇
-    SP = 1,
+    SP,
 
     /// OP
-    OP = 2,
+    OP,
 
     /// ED
-    ED = 3,
+    ED,
 
     /// 预告/宣传/广告
-    PV = 4,
+    PV,
 
     /// MAD
-    MAD = 5,
+    MAD,
 
     /// 其他
+    Other,
+
+    /// 未知的章节类型
+    Unknown(u8),
+}
+
+impl_repr_enum!(EpisodeType: u8, Unknown {
+    MainStory = 0,
+    SP = 1,
+    OP = 2,
+    ED = 3,
+    PV = 4,
+    MAD = 5,
     Other = 6,
+});
+
+impl_labels!(EpisodeType {
+    EpisodeType::MainStory => ("本篇", "Main Story"),
+    EpisodeType::SP => ("特别篇", "Special"),
+    EpisodeType::OP => ("OP", "Opening"),
+    EpisodeType::ED => ("ED", "Ending"),
+    EpisodeType::PV => ("预告/宣传/广告", "Trailer"),
+    EpisodeType::MAD => ("MAD", "MAD"),
+    EpisodeType::Other => ("其他", "Other"),
+    EpisodeType::Unknown(_) => ("未知", "Unknown"),
+});
+
+impl FromStr for EpisodeType {
+    type Err = ParseEnumError;
+
+    /// 解析数字代码 (`0`)、英文名称 (`main_story`、`sp`) 或中文名称 (`本篇`)，英文不区分大小写
+    ///
+    /// 未知的数字代码解析为 [`EpisodeType::Unknown`]。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(code) = s.trim().parse::<u8>() {
+            return Ok(code.into());
+        }
+        parse_enum(
+            s,
+            "episode type",
+            &[
+                (EpisodeType::MainStory, &["main", "mainstory", "本篇"]),
+                (EpisodeType::SP, &["sp", "special", "特别篇"]),
+                (EpisodeType::OP, &["op", "opening"]),
+                (EpisodeType::ED, &["ed", "ending"]),
+                (EpisodeType::PV, &["pv", "trailer", "预告"]),
+                (EpisodeType::MAD, &["mad"]),
+                (EpisodeType::Other, &["other", "其他"]),
+            ],
+        )
+    }
 }
 
 /// Images (图片)
 ///
-/// 存储不同尺寸的图片链接。
+/// 存储不同尺寸的图片链接。缺失的尺寸为空字符串，可以通过 [`ImageUrls::url`] 获取 `Option<&str>`。
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, utility_types::Pick)]
 #[pick(
     arg(ident = PersonImages, fields(large, medium, small, grid), derive(Clone, Debug, PartialEq, Deserialize, Serialize)),
@@ -184,7 +501,7 @@ pub struct Images {
 }
 
 /// Image Type (图片类型)
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ImageType {
     Small,
@@ -194,6 +511,116 @@ pub enum ImageType {
     Grid,
 }
 
+impl ImageType {
+    /// 所有取值
+    pub const ALL: &'static [ImageType] = &[
+        ImageType::Small,
+        ImageType::Common,
+        ImageType::Medium,
+        ImageType::Large,
+        ImageType::Grid,
+    ];
+}
+
+impl FromStr for ImageType {
+    type Err = ParseEnumError;
+
+    /// 解析尺寸名称，例如 `large`，不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_enum(
+            s,
+            "image type",
+            &[
+                (ImageType::Small, &["small"]),
+                (ImageType::Common, &["common"]),
+                (ImageType::Medium, &["medium"]),
+                (ImageType::Large, &["large"]),
+                (ImageType::Grid, &["grid"]),
+            ],
+        )
+    }
+}
+
+/// Image URLs of different sizes (不同尺寸的图片链接)
+///
+/// 为 [`Images`] 与 [`PersonImages`] 提供统一的按尺寸访问方式，供 [`ImagePreference`] 使用。
+pub trait ImageUrls {
+    /// 获取 `size` 尺寸的图片链接，不存在或为空时返回 `None`
+    fn url(&self, size: ImageType) -> Option<&str>;
+}
+
+impl ImageUrls for Images {
+    fn url(&self, size: ImageType) -> Option<&str> {
+        let url = match size {
+            ImageType::Small => &self.small,
+            ImageType::Common => &self.common,
+            ImageType::Medium => &self.medium,
+            ImageType::Large => &self.large,
+            ImageType::Grid => &self.grid,
+        };
+        Some(url.as_str()).filter(|url| !url.is_empty())
+    }
+}
+
+impl ImageUrls for PersonImages {
+    fn url(&self, size: ImageType) -> Option<&str> {
+        let url = match size {
+            ImageType::Small => &self.small,
+            ImageType::Common => return None,
+            ImageType::Medium => &self.medium,
+            ImageType::Large => &self.large,
+            ImageType::Grid => &self.grid,
+        };
+        Some(url.as_str()).filter(|url| !url.is_empty())
+    }
+}
+
+/// Image Preference (图片尺寸偏好)
+///
+/// 按顺序尝试各个尺寸，返回第一个存在且不为空的图片链接。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// let images = PersonImages {
+///     large: String::new(),
+///     medium: "medium.jpg".to_string(),
+///     small: "small.jpg".to_string(),
+///     grid: "grid.jpg".to_string(),
+/// };
+///
+/// let preference = ImagePreference::new([ImageType::Large, ImageType::Common, ImageType::Medium]);
+/// assert_eq!(preference.pick(&images), Some("medium.jpg"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImagePreference(Vec<ImageType>);
+
+impl ImagePreference {
+    /// 按 `order` 的顺序创建尺寸偏好
+    pub fn new(order: impl IntoIterator<Item = ImageType>) -> Self {
+        Self(order.into_iter().collect())
+    }
+
+    /// 从 `images` 中选出第一个可用的图片链接
+    pub fn pick<'a>(&self, images: &'a impl ImageUrls) -> Option<&'a str> {
+        self.0.iter().find_map(|&size| images.url(size))
+    }
+}
+
+impl Default for ImagePreference {
+    /// 从大到小：`Large`、`Common`、`Medium`、`Small`、`Grid`
+    fn default() -> Self {
+        Self::new([
+            ImageType::Large,
+            ImageType::Common,
+            ImageType::Medium,
+            ImageType::Small,
+            ImageType::Grid,
+        ])
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Infobox {
     pub key: String,
@@ -214,40 +641,113 @@ pub enum InfoboxValueItem {
     V { v: String },
 }
 
+/// 分页结果
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub struct PagedEpisode {
-    /// 条目总数
+pub struct Paged<T> {
+    /// 数据总数
     pub total: u64,
 
     /// 每页数量
     pub limit: u64,
 
-    /// 当前页码
+    /// 当前分页的偏移量
     pub offset: u64,
 
     /// 数据
-    pub data: Vec<Episode>,
+    pub data: Vec<T>,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub struct PagedSubject {
-    /// 条目总数
-    pub total: u64,
+impl<T> Paged<T> {
+    /// 下一页的偏移量
+    pub fn next_offset(&self) -> u64 {
+        self.offset + self.data.len() as u64
+    }
 
-    /// 每页数量
-    pub limit: u64,
+    /// 是否还有下一页
+    pub fn has_next(&self) -> bool {
+        !self.data.is_empty() && self.next_offset() < self.total
+    }
 
-    /// 当前页码
-    pub offset: u64,
+    /// 是否为最后一页
+    pub fn is_last_page(&self) -> bool {
+        !self.has_next()
+    }
 
-    /// 数据
-    pub data: Vec<Subject>,
+    /// 对每一条数据应用 `f`，保留分页信息
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paged<U> {
+        Paged {
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            data: self.data.into_iter().map(f).collect(),
+        }
+    }
+}
+
+/// 未解析的分页结果
+///
+/// 每一条数据保留为原始的 JSON 文本，需要时再通过 [`decode`](Paged::decode) 或
+/// [`serde_json::from_str`] 解析，避免在爬取时完整构建每一个结构。
+pub type PagedRaw = Paged<Box<serde_json::value::RawValue>>;
+
+impl PagedRaw {
+    /// 将每一条数据解析为 `U`，`U` 可以是只包含部分字段的结构
+    pub fn decode<U: serde::de::DeserializeOwned>(&self) -> Result<Paged<U>, serde_json::Error> {
+        Ok(Paged {
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            data: self
+                .data
+                .iter()
+                .map(|raw| serde_json::from_str(raw.get()))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// 未分页的列表或分页结果，用于可能在服务端增加分页的 API
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ListOrPaged<T> {
+    List(Vec<T>),
+    Paged(Paged<T>),
+}
+
+impl<T> ListOrPaged<T> {
+    /// 转换为分页结果，未分页的列表按 `limit` 与 `offset` 在本地截取
+    pub(crate) fn into_paged(self, limit: Option<u64>, offset: Option<u64>) -> Paged<T> {
+        match self {
+            ListOrPaged::Paged(paged) => paged,
+            ListOrPaged::List(data) => {
+                let total = data.len() as u64;
+                let offset = offset.unwrap_or(0).min(total);
+                let limit = limit.unwrap_or(total);
+                Paged {
+                    total,
+                    limit,
+                    offset,
+                    data: data
+                        .into_iter()
+                        .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+                        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+                        .collect(),
+                }
+            }
+        }
+    }
 }
 
+/// 章节分页结果
+pub type PagedEpisode = Paged<Episode>;
+
+/// 条目分页结果
+pub type PagedSubject = Paged<Subject>;
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Person {
     /// ID
-    pub id: usize,
+    pub id: u64,
 
     /// 名称
     pub name: String,
@@ -259,6 +759,7 @@ pub struct Person {
     pub career: Vec<PersonCareer>,
 
     /// 人物图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 人物简介
@@ -267,7 +768,14 @@ pub struct Person {
     pub locked: bool,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+impl Person {
+    /// 人物是否已被锁定
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PersonCareer {
     Producer,
@@ -277,6 +785,34 @@ pub enum PersonCareer {
     Writer,
     Illustrator,
     Actor,
+
+    /// 未知的职业
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl_labels!(PersonCareer {
+    PersonCareer::Producer => ("制作人员", "Producer"),
+    PersonCareer::Mangaka => ("漫画家", "Mangaka"),
+    PersonCareer::Artist => ("音乐人", "Artist"),
+    PersonCareer::Seiyu => ("声优", "Voice Actor"),
+    PersonCareer::Writer => ("作家", "Writer"),
+    PersonCareer::Illustrator => ("绘师", "Illustrator"),
+    PersonCareer::Actor => ("演员", "Actor"),
+    PersonCareer::Unknown(career) => (career, career),
+});
+
+impl PersonCareer {
+    /// 所有已知的取值，不包含 [`PersonCareer::Unknown`]
+    pub const ALL: &'static [PersonCareer] = &[
+        PersonCareer::Producer,
+        PersonCareer::Mangaka,
+        PersonCareer::Artist,
+        PersonCareer::Seiyu,
+        PersonCareer::Writer,
+        PersonCareer::Illustrator,
+        PersonCareer::Actor,
+    ];
 }
 
 /// Person Character (人物相关角色)
@@ -292,6 +828,7 @@ pub struct PersonCharacter {
     pub r#type: CharacterType,
 
     /// 角色图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 条目 ID
@@ -325,6 +862,7 @@ pub struct PersonDetail {
     pub career: Vec<PersonCareer>,
 
     /// 人物图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 人物简介
@@ -332,7 +870,7 @@ pub struct PersonDetail {
 
     pub locked: bool,
 
-    /// 最后修改时间
+    /// 最后修改时间，例如 `2024-01-01T12:00:00+08:00`，可以通过 [`PersonDetail::last_modified_parsed`] 解析
     pub last_modified: String,
 
     /// 附加信息
@@ -356,24 +894,59 @@ pub struct PersonDetail {
     pub stat: Stat,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u8)]
+impl PersonDetail {
+    /// 人物是否已被锁定
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// 解析后的最后修改时间，格式不正确时返回 `None`
+    pub fn last_modified_parsed(&self) -> Option<crate::time::DateTime> {
+        crate::time::DateTime::parse(&self.last_modified)
+    }
+
+    /// 最后修改时间，参见 [`last_modified_parsed`](Self::last_modified_parsed)
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn last_modified_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.last_modified_parsed()?.to_chrono()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
 pub enum PersonType {
     /// 个人
-    Individual = 1,
+    Individual,
 
     /// 公司
-    Corporation = 2,
+    Corporation,
 
     /// 组合
-    Association = 3,
+    Association,
+
+    /// 未知的人物类型
+    Unknown(u8),
 }
 
+impl_repr_enum!(PersonType: u8, Unknown {
+    Individual = 1,
+    Corporation = 2,
+    Association = 3,
+});
+
+impl_labels!(PersonType {
+    PersonType::Individual => ("个人", "Individual"),
+    PersonType::Corporation => ("公司", "Corporation"),
+    PersonType::Association => ("组合", "Association"),
+    PersonType::Unknown(_) => ("未知", "Unknown"),
+});
+
 /// Related Character (条目相关角色)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct RelatedCharacter {
     /// ID
-    pub id: usize,
+    pub id: u64,
 
     /// 名称
     pub name: String,
@@ -382,6 +955,7 @@ pub struct RelatedCharacter {
     pub r#type: CharacterType,
 
     /// 角色图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 和条目的关系
@@ -407,6 +981,7 @@ pub struct RelatedPerson {
     pub career: Vec<PersonCareer>,
 
     /// 人物图片
+    #[serde(default, deserialize_with = "empty_images_as_none")]
     pub images: Option<PersonImages>,
 
     /// 和条目的关系
@@ -432,25 +1007,78 @@ pub struct RelatedSubject {
     /// 中文名称
     pub name_cn: String,
 
-    /// 图片
+    /// 图片，API 返回空字符串时为 `None`
+    #[serde(default, deserialize_with = "empty_string_as_none")]
     pub image: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub struct SearchSubjects {
-    /// 搜索结果数量
-    pub total: u64,
+bitflags::bitflags! {
+    /// Token Scopes (授权权限)
+    ///
+    /// 序列化为以空格分隔的权限名称，例如 `"read write_collection"`，无法识别的名称会被忽略。
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Scopes: u32 {
+        /// 读取公开与私有数据
+        const READ = 1;
 
-    /// 当前分页数量
-    pub limit: u64,
+        /// 修改收藏
+        const WRITE_COLLECTION = 1 << 1;
 
-    /// 当前分页参数
-    pub offset: u64,
+        /// 修改目录
+        const WRITE_INDEX = 1 << 2;
 
-    /// 数据
-    pub data: Vec<SearchSubjectsItem>,
+        /// 修改条目、角色、人物等维基数据
+        const WRITE_WIKI = 1 << 3;
+    }
+}
+
+impl Scopes {
+    const NAMES: [(&'static str, Scopes); 4] = [
+        ("read", Scopes::READ),
+        ("write_collection", Scopes::WRITE_COLLECTION),
+        ("write_index", Scopes::WRITE_INDEX),
+        ("write_wiki", Scopes::WRITE_WIKI),
+    ];
+
+    /// 解析以空格或逗号分隔的权限名称
+    pub fn parse(s: &str) -> Self {
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|name| {
+                Self::NAMES
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, scope)| *scope)
+            })
+            .fold(Scopes::empty(), |acc, scope| acc | scope)
+    }
+
+    /// 以空格分隔的权限名称
+    pub fn names(&self) -> String {
+        Self::NAMES
+            .iter()
+            .filter(|(_, scope)| self.contains(*scope))
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Serialize for Scopes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.names())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scopes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s: Option<String> = Option::deserialize(deserializer)?;
+        Ok(s.as_deref().map(Scopes::parse).unwrap_or_default())
+    }
 }
 
+/// 条目搜索结果
+pub type SearchSubjects = Paged<SearchSubjectsItem>;
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct SearchSubjectsBody {
     /// 搜索关键词
@@ -465,6 +1093,7 @@ pub struct SearchSubjectsBody {
 
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Builder)]
 #[builder(default)]
+#[non_exhaustive]
 pub struct SearchSubjectsFilter {
     /// 条目类型
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -476,34 +1105,34 @@ pub struct SearchSubjectsFilter {
     #[builder(setter(name = "tags", each = "tag"))]
     pub tag: Vec<String>,
 
-    /// 日期条件
+    /// 日期条件，可以通过 [`AirDateFilter`] 构建
     ///
     /// ## Example
     ///
     /// - `>=2020-07-01`
     /// - `<2020-10-01`
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    #[builder(setter(name = "air_dates", each = "air_date"))]
+    #[builder(setter(name = "air_dates", each(name = "air_date", into)))]
     pub air_date: Vec<String>,
 
-    /// 评分条件
+    /// 评分条件，可以通过 [`RatingFilter`] 构建
     ///
     /// ## Example
     ///
     /// - `>=6`
     /// - `<8`
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    #[builder(setter(name = "ratings", each = "rating"))]
+    #[builder(setter(name = "ratings", each(name = "rating", into)))]
     pub rating: Vec<String>,
 
-    /// 排名条件
+    /// 排名条件，可以通过 [`RankFilter`] 构建
     ///
     /// ## Example
     ///
     /// - `>10`
     /// - `<=18`
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    #[builder(setter(name = "ranks", each = "rank"))]
+    #[builder(setter(name = "ranks", each(name = "rank", into)))]
     pub rank: Vec<String>,
 
     /// 是否为 NSFW
@@ -519,64 +1148,487 @@ impl SearchSubjectsFilter {
     }
 }
 
-/// Search Subjects Item (搜索条目数据)
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub struct SearchSubjectsItem {
-    /// ID
-    pub id: u64,
+/// Comparison (比较运算符)
+///
+/// 用于 [`AirDateFilter`]、[`RatingFilter`] 与 [`RankFilter`]。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// `>`
+    Gt,
 
-    /// 条目类型
-    pub r#type: SubjectType,
+    /// `>=`
+    Gte,
 
-    /// 发布日期
-    pub date: String,
+    /// `<`
+    Lt,
 
-    /// 图片
-    pub image: String,
+    /// `<=`
+    Lte,
+}
 
-    /// 简介
-    pub summary: String,
+impl Comparison {
+    /// 运算符的字符串形式，例如 `>=`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Comparison::Gt => ">",
+            Comparison::Gte => ">=",
+            Comparison::Lt => "<",
+            Comparison::Lte => "<=",
+        }
+    }
 
-    /// 名称
-    pub name: String,
+    /// 拆分条件字符串开头的运算符与剩余部分
+    fn split(s: &str) -> Option<(Self, &str)> {
+        let s = s.trim();
+        [
+            Comparison::Gte,
+            Comparison::Lte,
+            Comparison::Gt,
+            Comparison::Lt,
+        ]
+        .into_iter()
+        .find_map(|op| Some((op, s.strip_prefix(op.as_str())?.trim_start())))
+    }
+}
 
-    /// 中文名称
-    pub name_cn: String,
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-    /// 标签    
-    pub tags: Vec<SubjectTag>,
+/// Air Date Filter (日期条件)
+///
+/// 生成 [`SearchSubjectsFilter::air_date`] 中 `>=2020-07-01` 格式的条件。
+///
+/// ## Example
+///
+/// ```
+/// # use bgmtv::prelude::*;
+/// # use bgmtv::time::Date;
+/// let filter = SearchSubjectsFilter::builder()
+///     .air_date(AirDateFilter::on_or_after(Date::from_ymd(2020, 7, 1).unwrap()))
+///     .air_date(AirDateFilter::before(Date::from_ymd(2020, 10, 1).unwrap()))
+///     .rating(RatingFilter::gte(7.0).unwrap())
+///     .rank(RankFilter::lte(100))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(filter.air_date, vec![">=2020-07-01", "<2020-10-01"]);
+/// assert_eq!(filter.rating, vec![">=7"]);
+/// assert_eq!(filter.rank, vec!["<=100"]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AirDateFilter {
+    op: Comparison,
+    date: Date,
+}
 
-    /// 评分
-    pub score: f64,
+impl AirDateFilter {
+    /// `date` 之后，不含当天
+    pub fn after(date: Date) -> Self {
+        Self::new(Comparison::Gt, date)
+    }
 
-    /// 排名
-    pub rank: u64,
+    /// `date` 当天及之后
+    pub fn on_or_after(date: Date) -> Self {
+        Self::new(Comparison::Gte, date)
+    }
+
+    /// `date` 之前，不含当天
+    pub fn before(date: Date) -> Self {
+        Self::new(Comparison::Lt, date)
+    }
+
+    /// `date` 当天及之前
+    pub fn on_or_before(date: Date) -> Self {
+        Self::new(Comparison::Lte, date)
+    }
+
+    /// 使用运算符 `op` 与日期 `date` 创建条件
+    pub fn new(op: Comparison, date: Date) -> Self {
+        Self { op, date }
+    }
+
+    /// 运算符
+    pub fn op(&self) -> Comparison {
+        self.op
+    }
+
+    /// 日期
+    pub fn date(&self) -> Date {
+        self.date
+    }
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum SortType {
-    /// 匹配程度，meilisearch 默认排序
-    #[default]
-    Match,
-    /// 收藏人数
-    Heat,
-    /// 排名由高到低
-    Rank,
-    /// 评分由高到低
-    Score,
+impl fmt::Display for AirDateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.date)
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub struct Stat {
-    /// 评论数
-    pub comments: u64,
+impl FromStr for AirDateFilter {
+    type Err = ParseFilterError;
 
-    /// 收藏数
-    pub collects: u64,
+    /// 解析 `>=2020-07-01` 格式的条件，日期不存在时返回错误
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Comparison::split(s)
+            .and_then(|(op, date)| Some(Self::new(op, Date::parse(date)?)))
+            .ok_or_else(|| ParseFilterError::new(s))
+    }
 }
 
-/// Subject (条目)
+impl From<AirDateFilter> for String {
+    fn from(filter: AirDateFilter) -> Self {
+        filter.to_string()
+    }
+}
+
+/// Rating Filter (评分条件)
+///
+/// 生成 [`SearchSubjectsFilter::rating`] 中 `>=6` 格式的条件，评分需要在 0–10 之间。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RatingFilter {
+    op: Comparison,
+    score: f64,
+}
+
+impl RatingFilter {
+    /// 评分高于 `score`，`score` 不在 0–10 之间时返回 `None`
+    pub fn gt(score: f64) -> Option<Self> {
+        Self::new(Comparison::Gt, score)
+    }
+
+    /// 评分不低于 `score`，`score` 不在 0–10 之间时返回 `None`
+    pub fn gte(score: f64) -> Option<Self> {
+        Self::new(Comparison::Gte, score)
+    }
+
+    /// 评分低于 `score`，`score` 不在 0–10 之间时返回 `None`
+    pub fn lt(score: f64) -> Option<Self> {
+        Self::new(Comparison::Lt, score)
+    }
+
+    /// 评分不高于 `score`，`score` 不在 0–10 之间时返回 `None`
+    pub fn lte(score: f64) -> Option<Self> {
+        Self::new(Comparison::Lte, score)
+    }
+
+    /// 使用运算符 `op` 与评分 `score` 创建条件，`score` 不在 0–10 之间时返回 `None`
+    pub fn new(op: Comparison, score: f64) -> Option<Self> {
+        (0.0..=10.0).contains(&score).then_some(Self { op, score })
+    }
+
+    /// 运算符
+    pub fn op(&self) -> Comparison {
+        self.op
+    }
+
+    /// 评分
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl fmt::Display for RatingFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.score)
+    }
+}
+
+impl FromStr for RatingFilter {
+    type Err = ParseFilterError;
+
+    /// 解析 `>=6` 格式的条件，评分不在 0–10 之间时返回错误
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Comparison::split(s)
+            .and_then(|(op, score)| Self::new(op, score.parse().ok()?))
+            .ok_or_else(|| ParseFilterError::new(s))
+    }
+}
+
+impl From<RatingFilter> for String {
+    fn from(filter: RatingFilter) -> Self {
+        filter.to_string()
+    }
+}
+
+/// Rank Filter (排名条件)
+///
+/// 生成 [`SearchSubjectsFilter::rank`] 中 `<=18` 格式的条件。排名从 1 开始，数值越小排名越高。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RankFilter {
+    op: Comparison,
+    rank: u64,
+}
+
+impl RankFilter {
+    /// 排名数值大于 `rank`，即排名更低
+    pub fn gt(rank: u64) -> Self {
+        Self::new(Comparison::Gt, rank)
+    }
+
+    /// 排名数值不小于 `rank`
+    pub fn gte(rank: u64) -> Self {
+        Self::new(Comparison::Gte, rank)
+    }
+
+    /// 排名数值小于 `rank`，即排名更高
+    pub fn lt(rank: u64) -> Self {
+        Self::new(Comparison::Lt, rank)
+    }
+
+    /// 排名数值不大于 `rank`，例如 `lte(100)` 为前 100 名
+    pub fn lte(rank: u64) -> Self {
+        Self::new(Comparison::Lte, rank)
+    }
+
+    /// 使用运算符 `op` 与排名 `rank` 创建条件
+    pub fn new(op: Comparison, rank: u64) -> Self {
+        Self { op, rank }
+    }
+
+    /// 运算符
+    pub fn op(&self) -> Comparison {
+        self.op
+    }
+
+    /// 排名
+    pub fn rank(&self) -> u64 {
+        self.rank
+    }
+}
+
+impl fmt::Display for RankFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.rank)
+    }
+}
+
+impl FromStr for RankFilter {
+    type Err = ParseFilterError;
+
+    /// 解析 `<=18` 格式的条件
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Comparison::split(s)
+            .and_then(|(op, rank)| {
+                rank.bytes()
+                    .all(|b| b.is_ascii_digit())
+                    .then(|| Some(Self::new(op, rank.parse().ok()?)))?
+            })
+            .ok_or_else(|| ParseFilterError::new(s))
+    }
+}
+
+impl From<RankFilter> for String {
+    fn from(filter: RankFilter) -> Self {
+        filter.to_string()
+    }
+}
+
+/// Search Subjects Item (搜索条目数据)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SearchSubjectsItem {
+    /// ID
+    pub id: u64,
+
+    /// 条目类型
+    pub r#type: SubjectType,
+
+    /// 发布日期
+    pub date: String,
+
+    /// 图片
+    pub image: String,
+
+    /// 简介
+    pub summary: String,
+
+    /// 名称
+    pub name: String,
+
+    /// 中文名称
+    pub name_cn: String,
+
+    /// 标签    
+    pub tags: Vec<SubjectTag>,
+
+    /// 评分
+    pub score: f64,
+
+    /// 排名
+    pub rank: u64,
+
+    /// 平台，例如 `TV`、`剧场版`，较早的搜索结果中没有此字段
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub platform: Option<String>,
+}
+
+/// 搜索结果中的条目，只包含完整条目的部分字段，参见 [`SearchSubjectsItem`]
+pub type SearchSubject = SearchSubjectsItem;
+
+impl SearchSubjectsItem {
+    /// 图片链接，API 返回空字符串时为 `None`
+    pub fn image_url(&self) -> Option<&str> {
+        Some(self.image.as_str()).filter(|url| !url.is_empty())
+    }
+
+    /// 定点表示的分数，参见 [`score_to_x10`]
+    pub fn score_x10(&self) -> u16 {
+        score_to_x10(self.score)
+    }
+}
+
+/// 将分数转换为乘以 10 后四舍五入的整数，例如 `7.6` 转换为 `76`
+///
+/// 分数只有一位小数，定点表示可以精确地比较与存储，避免浮点数的误差。
+///
+/// ```
+/// # use bgmtv::types::{score_from_x10, score_to_x10};
+/// assert_eq!(score_to_x10(7.6), 76);
+/// assert_eq!(score_to_x10(0.1 + 0.2), 3);
+/// assert_eq!(score_from_x10(76), 7.6);
+/// ```
+pub fn score_to_x10(score: f64) -> u16 {
+    (score * 10.0).round().clamp(0.0, u16::MAX as f64) as u16
+}
+
+/// 将定点表示的分数转换回 [`f64`]，参见 [`score_to_x10`]
+pub fn score_from_x10(score_x10: u16) -> f64 {
+    f64::from(score_x10) / 10.0
+}
+
+/// Slim Subject (精简条目数据)
+///
+/// 用户收藏等接口中嵌套的条目，只包含 [`Subject`] 的部分字段。
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SlimSubject {
+    /// ID
+    pub id: u64,
+
+    /// 条目类型
+    pub r#type: SubjectType,
+
+    /// 名称
+    pub name: String,
+
+    /// 中文名称
+    pub name_cn: String,
+
+    /// 简介的前 120 个字符
+    pub short_summary: String,
+
+    /// 发布日期
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub date: Option<String>,
+
+    /// 图片
+    pub images: Images,
+
+    /// 书籍条目的册数
+    pub volumes: u64,
+
+    /// 章节数
+    pub eps: u64,
+
+    /// 收藏人数
+    pub collection_total: u64,
+
+    /// 评分
+    pub score: f64,
+
+    /// 排名
+    pub rank: u64,
+
+    /// 标签，最多 10 个
+    pub tags: Vec<SubjectTag>,
+}
+
+impl SlimSubject {
+    /// 定点表示的分数，参见 [`score_to_x10`]
+    pub fn score_x10(&self) -> u16 {
+        score_to_x10(self.score)
+    }
+}
+
+impl From<Subject> for SlimSubject {
+    /// 按 API 的规则截取简介与标签，收藏人数为各收藏类型之和
+    fn from(subject: Subject) -> Self {
+        let collection = &subject.collection;
+        Self {
+            id: subject.id,
+            r#type: subject.r#type,
+            name: subject.name,
+            name_cn: subject.name_cn,
+            short_summary: subject.summary.chars().take(120).collect(),
+            date: subject.date,
+            images: subject.images,
+            volumes: subject.volumes,
+            eps: subject.eps,
+            collection_total: collection.wish
+                + collection.collect
+                + collection.doing
+                + collection.on_hold
+                + collection.dropped,
+            score: subject.rating.score,
+            rank: subject.rating.rank,
+            tags: subject.tags.into_iter().take(10).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortType {
+    /// 匹配程度，meilisearch 默认排序
+    #[default]
+    Match,
+    /// 收藏人数
+    Heat,
+    /// 排名由高到低
+    Rank,
+    /// 评分由高到低
+    Score,
+}
+
+impl SortType {
+    /// 所有取值
+    pub const ALL: &'static [SortType] = &[
+        SortType::Match,
+        SortType::Heat,
+        SortType::Rank,
+        SortType::Score,
+    ];
+}
+
+impl FromStr for SortType {
+    type Err = ParseEnumError;
+
+    /// 解析排序名称，例如 `rank`，不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_enum(
+            s,
+            "sort type",
+            &[
+                (SortType::Match, &["match"]),
+                (SortType::Heat, &["heat"]),
+                (SortType::Rank, &["rank"]),
+                (SortType::Score, &["score"]),
+            ],
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Stat {
+    /// 评论数
+    pub comments: u64,
+
+    /// 收藏数
+    pub collects: u64,
+}
+
+/// Subject (条目)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Subject {
     /// ID
@@ -602,7 +1654,7 @@ pub struct Subject {
 
     pub locked: bool,
 
-    /// 发布日期
+    /// 发布日期，可以通过 [`date_parsed`](Self::date_parsed) 解析
     pub date: Option<String>,
 
     /// 发布平台
@@ -633,108 +1685,357 @@ pub struct Subject {
     pub tags: Vec<SubjectTag>,
 }
 
+impl Subject {
+    /// 条目是否已被锁定，被锁定的条目无法修改收藏等信息
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// 解析后的发布日期，没有日期或格式不正确时返回 `None`
+    pub fn date_parsed(&self) -> Option<crate::time::Date> {
+        crate::time::Date::parse(self.date.as_deref()?)
+    }
+
+    /// 合并同一条目的两份数据，`other` 视为较新的数据
+    ///
+    /// 两者都有值的字段使用 `other` 的值，缺失的字段 (空字符串、空列表、`0` 与 `None`) 由另一方补全；
+    /// 图片按尺寸分别补全，简介在其中一方被截断时使用完整的一方。
+    pub fn merge_prefer_newer(self, other: Subject) -> Subject {
+        Subject {
+            id: other.id,
+            r#type: other.r#type,
+            name: prefer_string(self.name, other.name),
+            name_cn: prefer_string(self.name_cn, other.name_cn),
+            summary: merge_summary(self.summary, other.summary),
+            series: other.series,
+            nsfw: other.nsfw,
+            locked: other.locked,
+            date: other.date.or(self.date),
+            platform: prefer_string(self.platform, other.platform),
+            images: self.images.merge_prefer_newer(other.images),
+            infobox: prefer_vec(self.infobox, other.infobox),
+            volumes: prefer_count(self.volumes, other.volumes),
+            eps: prefer_count(self.eps, other.eps),
+            total_episodes: prefer_count(self.total_episodes, other.total_episodes),
+            rating: if other.rating.total == 0 {
+                self.rating
+            } else {
+                other.rating
+            },
+            collection: other.collection,
+            tags: prefer_vec(self.tags, other.tags),
+        }
+    }
+
+    /// 使用搜索结果 `item` 补全缺失的字段，不会覆盖已有的值
+    ///
+    /// 搜索结果只有一张图片，缺失的图片尺寸都使用它补全。
+    pub fn merge_search_item(self, item: &SearchSubjectsItem) -> Subject {
+        let image = |url: String| prefer_string(item.image.clone(), url);
+        Subject {
+            name: prefer_string(item.name.clone(), self.name),
+            name_cn: prefer_string(item.name_cn.clone(), self.name_cn),
+            summary: merge_summary(item.summary.clone(), self.summary),
+            date: self
+                .date
+                .or_else(|| Some(item.date.clone()).filter(|date| !date.is_empty())),
+            images: Images {
+                large: image(self.images.large),
+                common: image(self.images.common),
+                medium: image(self.images.medium),
+                small: image(self.images.small),
+                grid: image(self.images.grid),
+            },
+            tags: prefer_vec(item.tags.clone(), self.tags),
+            platform: prefer_string(item.platform.clone().unwrap_or_default(), self.platform),
+            rating: if self.rating.total == 0 && self.rating.rank == 0 {
+                SubjectRating {
+                    rank: item.rank,
+                    score: item.score,
+                    ..self.rating
+                }
+            } else {
+                self.rating
+            },
+            ..self
+        }
+    }
+}
+
+impl Images {
+    fn merge_prefer_newer(self, other: Images) -> Images {
+        Images {
+            large: prefer_string(self.large, other.large),
+            common: prefer_string(self.common, other.common),
+            medium: prefer_string(self.medium, other.medium),
+            small: prefer_string(self.small, other.small),
+            grid: prefer_string(self.grid, other.grid),
+        }
+    }
+}
+
+fn prefer_string(older: String, newer: String) -> String {
+    if newer.is_empty() {
+        older
+    } else {
+        newer
+    }
+}
+
+fn prefer_vec<T>(older: Vec<T>, newer: Vec<T>) -> Vec<T> {
+    if newer.is_empty() {
+        older
+    } else {
+        newer
+    }
+}
+
+fn prefer_count(older: u64, newer: u64) -> u64 {
+    if newer == 0 {
+        older
+    } else {
+        newer
+    }
+}
+
+/// 合并简介，其中一方是另一方被截断 (可能带有省略号) 的前缀时使用较长的一方
+fn merge_summary(older: String, newer: String) -> String {
+    let stem = |s: &str| {
+        s.trim_end()
+            .trim_end_matches(['.', '…'])
+            .trim_end()
+            .to_string()
+    };
+    let (older_stem, newer_stem) = (stem(&older), stem(&newer));
+    if older.len() > newer.len() && older.starts_with(&newer_stem) {
+        older
+    } else if newer.len() >= older.len() && newer.starts_with(&older_stem) {
+        newer
+    } else {
+        prefer_string(older, newer)
+    }
+}
+
 /// Subject Category (条目分类)
+///
+/// 从数字转换时按书籍、动画、游戏、三次元的顺序匹配已知的分类，都不匹配时为 [`SubjectCategory::Unknown`]。
+/// 各分类的取值存在重叠，已知条目类型时应直接转换为对应的分类类型。
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(untagged)]
+#[serde(from = "u16", into = "u16")]
 pub enum SubjectCategory {
     Book(SubjectBookCategory),
     Anime(SubjectAnimeCategory),
     Game(SubjectGameCategory),
     Real(SubjectRealCategory),
+
+    /// 未知的分类
+    Unknown(u16),
+}
+
+impl From<u16> for SubjectCategory {
+    fn from(value: u16) -> Self {
+        let book = SubjectBookCategory::from(value);
+        if !matches!(book, SubjectBookCategory::Unknown(_)) {
+            return SubjectCategory::Book(book);
+        }
+        let anime = SubjectAnimeCategory::from(value);
+        if !matches!(anime, SubjectAnimeCategory::Unknown(_)) {
+            return SubjectCategory::Anime(anime);
+        }
+        let game = SubjectGameCategory::from(value);
+        if !matches!(game, SubjectGameCategory::Unknown(_)) {
+            return SubjectCategory::Game(game);
+        }
+        match SubjectRealCategory::from(value) {
+            SubjectRealCategory::Unknown(value) => SubjectCategory::Unknown(value),
+            real => SubjectCategory::Real(real),
+        }
+    }
+}
+
+impl From<SubjectCategory> for u16 {
+    fn from(value: SubjectCategory) -> Self {
+        match value {
+            SubjectCategory::Book(cat) => cat.into(),
+            SubjectCategory::Anime(cat) => cat.into(),
+            SubjectCategory::Game(cat) => cat.into(),
+            SubjectCategory::Real(cat) => cat.into(),
+            SubjectCategory::Unknown(value) => value,
+        }
+    }
 }
 
 /// Subject Book Category (书籍条目分类)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u16)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u16", into = "u16")]
 pub enum SubjectBookCategory {
     /// 其他
-    Other = 0,
+    Other,
 
     /// 漫画
-    Comic = 1001,
+    Comic,
 
     /// 小说
-    Novel = 1002,
+    Novel,
 
     /// 图集
-    Illustration = 1003,
+    Illustration,
+
+    /// 未知的分类
+    Unknown(u16),
 }
 
+impl_repr_enum!(SubjectBookCategory: u16, Unknown {
+    Other = 0,
+    Comic = 1001,
+    Novel = 1002,
+    Illustration = 1003,
+});
+
 /// Subject Anime Category (动画条目分类)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u16)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u16", into = "u16")]
 pub enum SubjectAnimeCategory {
     /// TV
-    TV = 1,
+    TV,
 
     /// OVA
-    OVA = 2,
+    OVA,
 
     /// 电影
-    Movie = 3,
+    Movie,
 
     /// 网络
-    Web = 4,
+    Web,
+
+    /// 未知的分类
+    Unknown(u16),
 }
 
+impl_repr_enum!(SubjectAnimeCategory: u16, Unknown {
+    TV = 1,
+    OVA = 2,
+    Movie = 3,
+    Web = 4,
+});
+
 /// Subject Game Category (游戏条目分类)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u16)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u16", into = "u16")]
 pub enum SubjectGameCategory {
     /// 其他
-    Other = 0,
+    Other,
 
     /// 游戏
-    Games = 4001,
+    Games,
 
     /// 软件
-    Software = 4002,
+    Software,
 
     /// 扩展包
-    DLC = 4003,
+    DLC,
 
     /// 桌游
-    Tabletop = 4005,
+    Tabletop,
+
+    /// 未知的分类
+    Unknown(u16),
 }
 
+impl_repr_enum!(SubjectGameCategory: u16, Unknown {
+    Other = 0,
+    Games = 4001,
+    Software = 4002,
+    DLC = 4003,
+    Tabletop = 4005,
+});
+
 /// Subject Real Category (三次元条目分类)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
-#[repr(u16)]
+///
+/// 注意 `JP`、`EN`、`CN` 的取值与 [`SubjectAnimeCategory`] 重叠，反序列化 [`SubjectCategory`] 时无法区分，
+/// 需要结合条目类型直接反序列化为此类型。
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u16", into = "u16")]
 pub enum SubjectRealCategory {
-    Other = 0,
+    /// 其他
+    Other,
     /// 日剧
-    JP = 1,
+    JP,
     /// 欧美剧
-    EN = 2,
+    EN,
     /// 华语剧
-    CN = 3,
+    CN,
     /// 电视剧
-    TV = 6001,
+    TV,
     /// 电影
-    Movie = 6002,
+    Movie,
     /// 演出
-    Live = 6003,
+    Live,
     /// 综艺
-    Show = 6004,
+    Show,
+    /// 未知的分类
+    Unknown(u16),
 }
 
+impl_repr_enum!(SubjectRealCategory: u16, Unknown {
+    Other = 0,
+    JP = 1,
+    EN = 2,
+    CN = 3,
+    TV = 6001,
+    Movie = 6002,
+    Live = 6003,
+    Show = 6004,
+});
+
 /// Subject Collection (条目收藏)
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct SubjectCollection {
     /// 想看
-    pub wish: usize,
+    pub wish: u64,
+
+    /// 看过
+    pub collect: u64,
+
+    /// 在看
+    pub doing: u64,
+
+    /// 搁置
+    pub on_hold: u64,
+
+    /// 抛弃
+    pub dropped: u64,
+}
+
+/// Subject Collection Type (条目收藏类型)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum SubjectCollectionType {
+    /// 想看
+    Wish = 1,
 
     /// 看过
-    pub collect: usize,
+    Collect = 2,
 
     /// 在看
-    pub doing: usize,
+    Doing = 3,
 
     /// 搁置
-    pub on_hold: usize,
+    OnHold = 4,
 
     /// 抛弃
-    pub dropped: usize,
+    Dropped = 5,
+}
+
+impl SubjectCollectionType {
+    /// 所有取值
+    pub const ALL: &'static [SubjectCollectionType] = &[
+        SubjectCollectionType::Wish,
+        SubjectCollectionType::Collect,
+        SubjectCollectionType::Doing,
+        SubjectCollectionType::OnHold,
+        SubjectCollectionType::Dropped,
+    ];
 }
 
 /// Subject Rating (条目评分)
@@ -753,6 +2054,13 @@ pub struct SubjectRating {
     pub score: f64,
 }
 
+impl SubjectRating {
+    /// 定点表示的分数，参见 [`score_to_x10`]
+    pub fn score_x10(&self) -> u16 {
+        score_to_x10(self.score)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct SubjectRatingCount {
     #[serde(rename = "1")]
@@ -809,7 +2117,7 @@ pub struct SubjectTag {
 }
 
 /// Subject Type (条目类型)
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize_repr, Serialize_repr)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize_repr, Serialize_repr)]
 #[repr(u8)]
 pub enum SubjectType {
     /// 书籍
@@ -829,6 +2137,44 @@ pub enum SubjectType {
     Real = 6,
 }
 
+impl_labels!(SubjectType {
+    SubjectType::Book => ("书籍", "Book"),
+    SubjectType::Anime => ("动画", "Anime"),
+    SubjectType::Music => ("音乐", "Music"),
+    SubjectType::Game => ("游戏", "Game"),
+    SubjectType::Real => ("三次元", "Real"),
+});
+
+impl SubjectType {
+    /// 所有取值
+    pub const ALL: &'static [SubjectType] = &[
+        SubjectType::Book,
+        SubjectType::Anime,
+        SubjectType::Music,
+        SubjectType::Game,
+        SubjectType::Real,
+    ];
+}
+
+impl FromStr for SubjectType {
+    type Err = ParseEnumError;
+
+    /// 解析数字代码 (`2`)、英文名称 (`anime`) 或中文名称 (`动画`)，英文不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_enum(
+            s,
+            "subject type",
+            &[
+                (SubjectType::Book, &["1", "book", "书籍"]),
+                (SubjectType::Anime, &["2", "anime", "动画"]),
+                (SubjectType::Music, &["3", "music", "音乐"]),
+                (SubjectType::Game, &["4", "game", "游戏"]),
+                (SubjectType::Real, &["6", "real", "三次元"]),
+            ],
+        )
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct User {
     /// Id
@@ -844,6 +2190,241 @@ pub struct User {
     pub sign: String,
 }
 
+/// User Collection Modify Payload (修改收藏的请求体)
+///
+/// 用于 [`Client::post_user_collection`](crate::client::Client::post_user_collection) 与
+/// [`Client::patch_user_collection`](crate::client::Client::patch_user_collection)，为 `None` 的字段不会被修改。
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct UserCollectionModifyPayload {
+    /// 收藏类型
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<SubjectCollectionType>,
+
+    /// 评分，0 表示删除评分
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<u8>,
+
+    /// 章节进度，书籍条目为话数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ep_status: Option<u64>,
+
+    /// 卷数进度，仅书籍条目有效
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vol_status: Option<u64>,
+
+    /// 吐槽
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// 是否仅自己可见
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<bool>,
+
+    /// 标签
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+impl UserCollectionModifyPayload {
+    /// 设置收藏类型
+    pub fn r#type(mut self, r#type: SubjectCollectionType) -> Self {
+        self.r#type = Some(r#type);
+        self
+    }
+
+    /// 设置评分
+    pub fn rate(mut self, rate: u8) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// 设置章节进度
+    pub fn ep_status(mut self, ep_status: u64) -> Self {
+        self.ep_status = Some(ep_status);
+        self
+    }
+
+    /// 设置卷数进度
+    pub fn vol_status(mut self, vol_status: u64) -> Self {
+        self.vol_status = Some(vol_status);
+        self
+    }
+
+    /// 设置吐槽
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// 设置是否仅自己可见
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = Some(private);
+        self
+    }
+
+    /// 设置标签
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+}
+
+/// User Subject Collection (用户的条目收藏)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserSubjectCollection {
+    /// 条目 ID
+    pub subject_id: u64,
+
+    /// 条目类型
+    pub subject_type: SubjectType,
+
+    /// 评分，0 表示未评分
+    pub rate: u8,
+
+    /// 收藏类型
+    pub r#type: SubjectCollectionType,
+
+    /// 吐槽
+    #[serde(default)]
+    pub comment: Option<String>,
+
+    /// 标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// 章节进度
+    pub ep_status: u64,
+
+    /// 卷数进度
+    pub vol_status: u64,
+
+    /// 最后修改时间，例如 `2024-01-01T12:00:00+08:00`，可以通过 [`UserSubjectCollection::updated_at_parsed`] 解析
+    pub updated_at: String,
+
+    /// 是否仅自己可见
+    pub private: bool,
+
+    /// 收藏的条目，较早的响应中没有此字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<SlimSubject>,
+}
+
+impl UserSubjectCollection {
+    /// 解析后的最后修改时间，格式不正确时返回 `None`
+    pub fn updated_at_parsed(&self) -> Option<crate::time::DateTime> {
+        crate::time::DateTime::parse(&self.updated_at)
+    }
+
+    /// 最后修改时间，参见 [`updated_at_parsed`](Self::updated_at_parsed)
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn updated_at_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.updated_at_parsed()?.to_chrono()
+    }
+}
+
+/// 用户收藏列表
+pub type PagedUserCollection = Paged<UserSubjectCollection>;
+
+/// Episode Collection Type (章节收藏类型)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize_repr, Serialize_repr)]
+#[repr(u8)]
+pub enum EpisodeCollectionType {
+    /// 未收藏
+    NotCollected = 0,
+
+    /// 想看
+    Wish = 1,
+
+    /// 看过
+    Done = 2,
+
+    /// 抛弃
+    Dropped = 3,
+}
+
+impl EpisodeCollectionType {
+    /// 所有取值
+    pub const ALL: &'static [EpisodeCollectionType] = &[
+        EpisodeCollectionType::NotCollected,
+        EpisodeCollectionType::Wish,
+        EpisodeCollectionType::Done,
+        EpisodeCollectionType::Dropped,
+    ];
+}
+
+/// User Episode Collection (用户的章节收藏)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserEpisodeCollection {
+    /// 章节
+    pub episode: Episode,
+
+    /// 收藏类型
+    pub r#type: EpisodeCollectionType,
+
+    /// 最后修改时间，Unix 时间戳 (秒)，未收藏时为 0
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl UserEpisodeCollection {
+    /// 最后修改时间 (UTC)，未收藏时返回 `None`
+    pub fn updated_at_parsed(&self) -> Option<crate::time::DateTime> {
+        (self.updated_at != 0)
+            .then(|| crate::time::DateTime::from_unix_timestamp(self.updated_at, 0))
+            .flatten()
+    }
+
+    /// 最后修改时间 (UTC)，参见 [`updated_at_parsed`](Self::updated_at_parsed)
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn updated_at_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.updated_at_parsed()?.to_chrono()
+    }
+}
+
+/// 按名称解析枚举
+///
+/// 比较时忽略首尾空白、ASCII 大小写以及 `_`、`-` 与空格，`variants` 中的名称应为小写且不含这些分隔符。
+fn parse_enum<T: Clone>(
+    s: &str,
+    type_name: &'static str,
+    variants: &[(T, &[&str])],
+) -> Result<T, ParseEnumError> {
+    let normalized: String = s
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    variants
+        .iter()
+        .find(|(_, names)| names.contains(&normalized.as_str()))
+        .map(|(variant, _)| variant.clone())
+        .ok_or_else(|| ParseEnumError::new(type_name, s))
+}
+
+/// 将空字符串反序列化为 `None`
+fn empty_string_as_none<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    Ok(s.filter(|s| !s.is_empty()))
+}
+
+/// 所有尺寸的图片链接都为空时反序列化为 `None`
+fn empty_images_as_none<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<PersonImages>, D::Error> {
+    let images: Option<PersonImages> = Option::deserialize(deserializer)?;
+    Ok(images.filter(|images| {
+        [&images.large, &images.medium, &images.small, &images.grid]
+            .iter()
+            .any(|url| !url.is_empty())
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -926,6 +2507,37 @@ mod tests {
         assert_eq!(filter.r#type, vec![SubjectType::Anime]);
     }
 
+    #[test]
+    fn test_search_filters() {
+        let date = Date::from_ymd(2020, 7, 1).unwrap();
+        assert_eq!(AirDateFilter::on_or_after(date).to_string(), ">=2020-07-01");
+        assert_eq!(AirDateFilter::before(date).to_string(), "<2020-07-01");
+        assert_eq!("<= 2020-7-1".parse(), Ok(AirDateFilter::on_or_before(date)));
+        assert!("2020-07-01".parse::<AirDateFilter>().is_err());
+        assert!(">=2020-02-30".parse::<AirDateFilter>().is_err());
+
+        assert_eq!(RatingFilter::gte(7.0).unwrap().to_string(), ">=7");
+        assert_eq!(RatingFilter::lt(8.5).unwrap().to_string(), "<8.5");
+        assert_eq!(RatingFilter::gt(11.0), None);
+        assert_eq!(RatingFilter::gt(f64::NAN), None);
+        assert_eq!(">6".parse(), Ok(RatingFilter::gt(6.0).unwrap()));
+        assert!(">=-1".parse::<RatingFilter>().is_err());
+
+        assert_eq!(RankFilter::lte(100).to_string(), "<=100");
+        assert_eq!(">10".parse(), Ok(RankFilter::gt(10)));
+        assert!("=10".parse::<RankFilter>().is_err());
+        assert!("<+10".parse::<RankFilter>().is_err());
+
+        let filter = SearchSubjectsFilter::builder()
+            .air_date(AirDateFilter::on_or_after(date))
+            .air_date("<2020-10-01")
+            .rank(RankFilter::lte(100))
+            .build()
+            .unwrap();
+        assert_eq!(filter.air_date, vec![">=2020-07-01", "<2020-10-01"]);
+        assert_eq!(filter.rank, vec!["<=100"]);
+    }
+
     #[test]
     fn test_subject() {
         // Subject data from https://bgm.tv/subject/3559 on 2024-10-10
@@ -937,17 +2549,345 @@ mod tests {
         assert_eq!(subject.r#type, SubjectType::Book);
         assert_eq!(subject.name, "とある魔術の禁書目録");
         assert_eq!(subject.name_cn, "魔法禁书目录");
-        assert_eq!(subject.series, true);
-        assert_eq!(subject.nsfw, false);
-        assert_eq!(subject.locked, false);
+        assert!(subject.series);
+        assert!(!subject.nsfw);
+        assert!(!subject.locked);
         assert_eq!(subject.date, Some("2004-04-24".to_string()));
+        assert_eq!(
+            subject.date_parsed(),
+            crate::time::Date::from_ymd(2004, 4, 24)
+        );
         assert_eq!(subject.platform, "小说");
         assert_eq!(subject.volumes, 24);
         assert_eq!(subject.eps, 0);
         assert_eq!(subject.total_episodes, 0);
         assert_eq!(subject.rating.rank, 1824);
+        assert_eq!(subject.rating.score_x10(), 76);
         assert_eq!(subject.collection.wish, 274);
-        assert!(subject.tags.len() > 0);
+        assert!(!subject.tags.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_enum_values() {
+        assert_eq!(
+            serde_json::from_str::<EpisodeType>("6").unwrap(),
+            EpisodeType::Other
+        );
+        assert_eq!(
+            serde_json::from_str::<EpisodeType>("9").unwrap(),
+            EpisodeType::Unknown(9)
+        );
+        assert_eq!(serde_json::to_string(&EpisodeType::SP).unwrap(), "1");
+        assert_eq!(
+            serde_json::to_string(&EpisodeType::Unknown(9)).unwrap(),
+            "9"
+        );
+
+        assert_eq!(
+            serde_json::from_str::<CharacterType>("5").unwrap(),
+            CharacterType::Unknown(5)
+        );
+        assert_eq!(
+            serde_json::from_str::<PersonType>("1").unwrap(),
+            PersonType::Individual
+        );
+
+        let careers: Vec<PersonCareer> = serde_json::from_str(r#"["seiyu","composer"]"#).unwrap();
+        assert_eq!(
+            careers,
+            vec![
+                PersonCareer::Seiyu,
+                PersonCareer::Unknown("composer".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&careers).unwrap(),
+            r#"["seiyu","composer"]"#
+        );
+    }
+
+    #[test]
+    fn test_subject_category_codes() {
+        assert_eq!(u16::from(SubjectBookCategory::Novel), 1002);
+        assert_eq!(
+            SubjectGameCategory::from(4005),
+            SubjectGameCategory::Tabletop
+        );
+        assert_eq!(
+            SubjectGameCategory::from(4006),
+            SubjectGameCategory::Unknown(4006)
+        );
+        assert_eq!(
+            serde_json::from_str::<SubjectAnimeCategory>("5").unwrap(),
+            SubjectAnimeCategory::Unknown(5)
+        );
+
+        assert_eq!(
+            SubjectCategory::from(0),
+            SubjectCategory::Book(SubjectBookCategory::Other)
+        );
+        assert_eq!(
+            SubjectCategory::from(1),
+            SubjectCategory::Anime(SubjectAnimeCategory::TV)
+        );
+        assert_eq!(
+            SubjectCategory::from(4001),
+            SubjectCategory::Game(SubjectGameCategory::Games)
+        );
+        assert_eq!(
+            SubjectCategory::from(6003),
+            SubjectCategory::Real(SubjectRealCategory::Live)
+        );
+        assert_eq!(SubjectCategory::from(7001), SubjectCategory::Unknown(7001));
+        assert_eq!(
+            serde_json::to_string(&SubjectCategory::Real(SubjectRealCategory::Unknown(6005)))
+                .unwrap(),
+            "6005"
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(SubjectType::Anime.to_string(), "动画");
+        assert_eq!(SubjectType::Real.label_en(), "Real");
+        assert_eq!(EpisodeType::MainStory.to_string(), "本篇");
+        assert_eq!(EpisodeType::Unknown(9).label_en(), "Unknown");
+        assert_eq!(CharacterType::Mechanic.label(), "机体");
+        assert_eq!(PersonType::Corporation.to_string(), "公司");
+        assert_eq!(PersonCareer::Seiyu.to_string(), "声优");
+        assert_eq!(
+            PersonCareer::Unknown("composer".to_string()).label(),
+            "composer"
+        );
+        assert_eq!(BloodType::AB.to_string(), "AB型");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("anime".parse(), Ok(SubjectType::Anime));
+        assert_eq!("Anime".parse(), Ok(SubjectType::Anime));
+        assert_eq!("动画".parse(), Ok(SubjectType::Anime));
+        assert_eq!(" 6 ".parse(), Ok(SubjectType::Real));
+        assert!("5".parse::<SubjectType>().is_err());
+
+        assert_eq!("main_story".parse(), Ok(EpisodeType::MainStory));
+        assert_eq!("Main Story".parse(), Ok(EpisodeType::MainStory));
+        assert_eq!("1".parse(), Ok(EpisodeType::SP));
+        assert_eq!("9".parse(), Ok(EpisodeType::Unknown(9)));
+
+        assert_eq!("LARGE".parse(), Ok(ImageType::Large));
+        assert_eq!("rank".parse(), Ok(SortType::Rank));
+
+        let err = "cartoon".parse::<SubjectType>().unwrap_err();
+        assert_eq!(err.value(), "cartoon");
+        assert_eq!(err.to_string(), r#"Invalid subject type: "cartoon""#);
+    }
+
+    #[test]
+    fn test_all_variants() {
+        assert_eq!(SubjectType::ALL.len(), 5);
+        assert_eq!(EpisodeType::ALL.first(), Some(&EpisodeType::MainStory));
+        assert!(!EpisodeType::ALL.contains(&EpisodeType::Unknown(0)));
+
+        for r#type in SubjectType::ALL {
+            assert_eq!(r#type.label().parse(), Ok(*r#type));
+        }
+        for r#type in EpisodeType::ALL {
+            assert_eq!(EpisodeType::from(u8::from(*r#type)), *r#type);
+        }
+        for cat in SubjectRealCategory::ALL {
+            assert_eq!(SubjectRealCategory::from(u16::from(cat.clone())), *cat);
+        }
+    }
+
+    #[test]
+    fn test_ids() {
+        let id = SubjectId::from(3559);
+        assert_eq!(u64::from(id), 3559);
+        assert_eq!(id.to_string(), "3559");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "3559");
+        assert_eq!(
+            serde_json::from_str::<EpisodeId>("8").unwrap(),
+            EpisodeId(8)
+        );
+    }
+
+    #[test]
+    fn test_slim_subject() {
+        let data = r#"{"subject_id":3559,"subject_type":1,"rate":8,"type":2,"comment":null,"tags":[],"ep_status":0,"vol_status":24,"updated_at":"2024-01-01T12:00:00+08:00","private":false,"subject":{"id":3559,"type":1,"name":"とある魔術の禁書目録","name_cn":"魔法禁书目录","short_summary":"故事开始于……","date":"2004-04-24","images":{"small":"s.jpg","grid":"g.jpg","large":"l.jpg","medium":"m.jpg","common":"c.jpg"},"volumes":24,"eps":0,"collection_total":1962,"score":7.6,"rank":1824,"tags":[{"name":"轻小说","count":281}]}}"#;
+        let collection: UserSubjectCollection = serde_json::from_str(data).unwrap();
+        let subject = collection.subject.unwrap();
+        assert_eq!(subject.id, 3559);
+        assert_eq!(subject.score_x10(), 76);
+        assert_eq!(subject.collection_total, 1962);
+        assert_eq!(subject.date.as_deref(), Some("2004-04-24"));
+        assert_eq!(subject.short_summary, "故事开始于……");
+
+        let legacy = format!("{}}}", &data[..data.find(r#","subject""#).unwrap()]);
+        let collection: UserSubjectCollection = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(collection.subject, None);
+
+        let mut subject = crate::mock::subject();
+        subject.summary = "summary".to_string();
+        subject.rating.rank = 10;
+        subject.rating.score = 8.1;
+        subject.collection = SubjectCollection {
+            on_hold: 1,
+            dropped: 2,
+            wish: 3,
+            collect: 4,
+            doing: 5,
+        };
+        let slim = SlimSubject::from(subject);
+        assert_eq!(slim.short_summary, "summary");
+        assert_eq!(slim.collection_total, 15);
+        assert_eq!((slim.score, slim.rank), (8.1, 10));
+    }
+
+    #[test]
+    fn test_browse_sort() {
+        assert_eq!(
+            serde_json::to_string(&BrowseSort::Rank).unwrap(),
+            r#""rank""#
+        );
+        assert_eq!(
+            serde_json::to_string(&BrowseSort::Raw("collects".to_string())).unwrap(),
+            r#""collects""#
+        );
+        assert_eq!(
+            serde_json::from_str::<BrowseSort>(r#""date""#).unwrap(),
+            BrowseSort::Date
+        );
+        assert_eq!("Rank".parse(), Ok(BrowseSort::Rank));
+        assert!("ranks".parse::<BrowseSort>().is_err());
+    }
+
+    #[test]
+    fn test_episode_airdate() {
+        let episode = |airdate: &str| Episode {
+            airdate: airdate.to_string(),
+            ..crate::mock::episode(1)
+        };
+        let today = crate::time::Date::from_ymd(2024, 4, 7).unwrap();
+
+        let aired = episode("2024-04-01");
+        assert_eq!(
+            aired.airdate_parsed(),
+            crate::time::Date::from_ymd(2024, 4, 1)
+        );
+        assert!(aired.has_aired(today));
+        assert_eq!(aired.days_until_air(today), Some(-6));
+
+        let airing = episode("2024-04-07");
+        assert!(airing.has_aired(today));
+        assert_eq!(airing.days_until_air(today), Some(0));
+
+        let upcoming = episode("2024-05-01");
+        assert!(!upcoming.has_aired(today));
+        assert_eq!(upcoming.days_until_air(today), Some(24));
+
+        for airdate in ["", "2024", "2024-04", "2024-04-31"] {
+            let unknown = episode(airdate);
+            assert_eq!(unknown.airdate_parsed(), None);
+            assert!(!unknown.has_aired(today));
+            assert_eq!(unknown.days_until_air(today), None);
+        }
+    }
+
+    #[test]
+    fn test_episode_duration() {
+        let episode = |duration: &str, duration_seconds: Option<u64>| Episode {
+            duration: duration.to_string(),
+            duration_seconds,
+            ..crate::mock::episode(1)
+        };
+        let secs = |s: u64| Some(std::time::Duration::from_secs(s));
+
+        assert_eq!(
+            episode("00:24:30", Some(1470)).duration_parsed(),
+            secs(1470)
+        );
+        assert_eq!(episode("24m", Some(1440)).duration_parsed(), secs(1440));
+        assert_eq!(episode("24m", None).duration_parsed(), secs(1440));
+        assert_eq!(episode("00:24:30", Some(0)).duration_parsed(), secs(1470));
+        assert_eq!(episode("", Some(0)).duration_parsed(), None);
+        assert_eq!(episode("未知", None).duration_parsed(), None);
+    }
+
+    #[test]
+    fn test_merge_prefer_newer() {
+        let mut subject = crate::mock::subject();
+        subject.summary = "A long summary...".to_string();
+        subject.date = Some("2024-04-01".to_string());
+        subject.images.small = "s.jpg".to_string();
+
+        let mut newer = subject.clone();
+        newer.name_cn = "中文名".to_string();
+        newer.summary = "A long summary with details".to_string();
+        newer.date = None;
+        newer.images.small = String::new();
+        newer.images.large = "l.jpg".to_string();
+        newer.eps = 13;
+
+        let merged = subject.clone().merge_prefer_newer(newer.clone());
+        assert_eq!(merged.name_cn, "中文名");
+        assert_eq!(merged.summary, "A long summary with details");
+        assert_eq!(merged.date.as_deref(), Some("2024-04-01"));
+        assert_eq!(merged.images.small, "s.jpg");
+        assert_eq!(merged.images.large, "l.jpg");
+        assert_eq!(merged.eps, 13);
+        assert_eq!(
+            newer.merge_prefer_newer(subject.clone()).summary,
+            merged.summary
+        );
+
+        let item: SearchSubjectsItem = serde_json::from_str(r#"{"id":1,"type":2,"date":"","image":"i.jpg","summary":"A long","name":"name","name_cn":"名","tags":[],"score":8.1,"rank":10}"#).unwrap();
+        let merged = subject.merge_search_item(&item);
+        assert_eq!(merged.name_cn, "名");
+        assert_eq!(merged.summary, "A long summary...");
+        assert_eq!(merged.images.small, "s.jpg");
+        assert_eq!(merged.images.large, "i.jpg");
+        assert_eq!(item.platform, None);
+        assert_eq!(merged.platform, "TV");
+
+        let item: SearchSubject = serde_json::from_str(r#"{"id":1,"type":2,"date":"","image":"","summary":"","name":"","name_cn":"","tags":[],"score":0,"rank":0,"platform":"剧场版"}"#).unwrap();
+        assert_eq!(item.platform.as_deref(), Some("剧场版"));
+    }
+
+    #[test]
+    fn test_scopes() {
+        let token: AccessToken = serde_json::from_str(
+            r#"{"access_token":"xxx","client_id":"bgm123","user_id":1,"expires":1700000000,"scope":"read write_collection unknown"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.scope, Scopes::READ | Scopes::WRITE_COLLECTION);
+        assert_eq!(token.scope.names(), "read write_collection");
+
+        let token: AccessToken = serde_json::from_str(
+            r#"{"access_token":"xxx","client_id":"bgm123","user_id":1,"expires":1700000000,"scope":null}"#,
+        )
+        .unwrap();
+        assert!(token.scope.is_empty());
+    }
+
+    #[test]
+    fn test_subject_real_category() {
+        for (cat, value) in [
+            (SubjectRealCategory::Other, 0),
+            (SubjectRealCategory::JP, 1),
+            (SubjectRealCategory::EN, 2),
+            (SubjectRealCategory::CN, 3),
+            (SubjectRealCategory::TV, 6001),
+            (SubjectRealCategory::Movie, 6002),
+            (SubjectRealCategory::Live, 6003),
+            (SubjectRealCategory::Show, 6004),
+        ] {
+            assert_eq!(serde_json::to_value(&cat).unwrap(), value);
+            assert_eq!(
+                serde_json::from_value::<SubjectRealCategory>(value.into()).unwrap(),
+                cat
+            );
+        }
     }
 
     #[test]
@@ -956,4 +2896,61 @@ mod tests {
 
         assert_eq!(serde_json::to_string(&cat).unwrap(), r#"1001"#);
     }
+
+    #[test]
+    fn test_paged() {
+        let paged: Paged<u64> =
+            serde_json::from_str(r#"{"total":5,"limit":2,"offset":2,"data":[3,4]}"#).unwrap();
+        assert_eq!(paged.next_offset(), 4);
+        assert!(paged.has_next());
+
+        let paged = Paged {
+            offset: 4,
+            data: vec![5],
+            ..paged
+        };
+        assert!(paged.is_last_page());
+        assert_eq!(paged.map(|n| n * 10).data, vec![50]);
+
+        let list: ListOrPaged<u64> = serde_json::from_str("[1,2,3,4,5]").unwrap();
+        let paged = list.into_paged(Some(2), Some(2));
+        assert_eq!((paged.total, paged.offset, paged.data), (5, 2, vec![3, 4]));
+        let list: ListOrPaged<u64> = serde_json::from_str("[1,2,3]").unwrap();
+        let paged = list.into_paged(Some(u64::MAX), Some(1));
+        assert_eq!((paged.limit, paged.data), (u64::MAX, vec![2, 3]));
+        let list: ListOrPaged<u64> =
+            serde_json::from_str(r#"{"total":5,"limit":2,"offset":2,"data":[3,4]}"#).unwrap();
+        assert!(list.into_paged(None, None).has_next());
+    }
+
+    #[test]
+    fn test_empty_image_urls() {
+        let subject: RelatedSubject = serde_json::from_str(
+            r#"{"id":1,"type":2,"staff":"","name":"","name_cn":"","image":""}"#,
+        )
+        .unwrap();
+        assert_eq!(subject.image, None);
+
+        let person: RelatedPerson = serde_json::from_str(
+            r#"{"id":1,"name":"","type":1,"career":[],"relation":"","eps":"","images":{"large":"","medium":"","small":"","grid":""}}"#,
+        )
+        .unwrap();
+        assert_eq!(person.images, None);
+    }
+
+    #[test]
+    fn test_large_ids() {
+        // 超出 32 位平台 `usize` 范围的 ID
+        let id = u64::from(u32::MAX) + 1;
+
+        let character: RelatedCharacter = serde_json::from_str(&format!(
+            r#"{{"id":{id},"name":"","type":1,"relation":"","actors":[{{"id":{id},"name":"","type":1,"career":[],"short_summary":"","locked":false}}]}}"#
+        ))
+        .unwrap();
+        assert_eq!(character.id, id);
+        assert_eq!(character.actors[0].id, id);
+
+        let json = serde_json::to_string(&character).unwrap();
+        assert!(json.contains(&format!(r#""id":{id}"#)));
+    }
 }
@@ -0,0 +1,272 @@
+//! # Validation (数据校验)
+//!
+//! 此模块提供了对 API 返回数据的反序列化后校验，例如 ID 不为 0、评分在 0–10 之间、分页偏移量合理等。
+//!
+//! 校验不会拒绝数据，而是返回 [`ValidationWarning`] 列表，供数据质量相关的流程标记可疑记录。
+//!
+//! ## Example
+//!
+//! ```
+//! # use bgmtv::prelude::*;
+//! use bgmtv::validate::Validate;
+//!
+//! let paged = PagedEpisode {
+//!     total: 1,
+//!     limit: 10,
+//!     offset: 5,
+//!     data: vec![],
+//! };
+//!
+//! let warnings = paged.validate();
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(warnings[0].field, "offset");
+//! ```
+
+use std::fmt;
+
+use crate::types::*;
+
+/// Validation warning (校验警告)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationWarning {
+    /// 出现问题的字段路径，例如 `data[0].rating.score`
+    pub field: String,
+
+    /// 问题类型
+    pub kind: ValidationWarningKind,
+}
+
+/// Validation warning kind (校验警告类型)
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationWarningKind {
+    /// ID 为 0
+    ZeroId,
+
+    /// 评分不在 0–10 之间
+    ScoreOutOfRange(f64),
+
+    /// 分页偏移量超出总数
+    OffsetBeyondTotal {
+        /// 偏移量
+        offset: u64,
+        /// 总数
+        total: u64,
+    },
+
+    /// 分页数据数量超出每页数量
+    TooManyItems {
+        /// 数据数量
+        len: u64,
+        /// 每页数量
+        limit: u64,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationWarningKind::ZeroId => write!(f, "{}: id is zero", self.field),
+            ValidationWarningKind::ScoreOutOfRange(score) => {
+                write!(f, "{}: score {} is out of range 0-10", self.field, score)
+            }
+            ValidationWarningKind::OffsetBeyondTotal { offset, total } => {
+                write!(
+                    f,
+                    "{}: offset {} is beyond total {}",
+                    self.field, offset, total
+                )
+            }
+            ValidationWarningKind::TooManyItems { len, limit } => {
+                write!(f, "{}: {} items exceed limit {}", self.field, len, limit)
+            }
+        }
+    }
+}
+
+/// 对反序列化后的数据进行校验
+pub trait Validate {
+    /// 校验数据，返回所有发现的问题
+    fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        self.validate_into("", &mut warnings);
+        warnings
+    }
+
+    /// 校验数据，并将问题以 `prefix` 为字段路径前缀追加到 `warnings`
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>);
+}
+
+fn field(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn check_id(prefix: &str, id: u64, warnings: &mut Vec<ValidationWarning>) {
+    if id == 0 {
+        warnings.push(ValidationWarning {
+            field: field(prefix, "id"),
+            kind: ValidationWarningKind::ZeroId,
+        });
+    }
+}
+
+fn check_score(prefix: &str, name: &str, score: f64, warnings: &mut Vec<ValidationWarning>) {
+    if !(0.0..=10.0).contains(&score) {
+        warnings.push(ValidationWarning {
+            field: field(prefix, name),
+            kind: ValidationWarningKind::ScoreOutOfRange(score),
+        });
+    }
+}
+
+fn check_page<T: Validate>(
+    prefix: &str,
+    total: u64,
+    limit: u64,
+    offset: u64,
+    data: &[T],
+    warnings: &mut Vec<ValidationWarning>,
+) {
+    // 请求恰好从末尾开始时 offset == total 是合法的
+    if offset > total {
+        warnings.push(ValidationWarning {
+            field: field(prefix, "offset"),
+            kind: ValidationWarningKind::OffsetBeyondTotal { offset, total },
+        });
+    }
+
+    let len = data.len() as u64;
+    if len > limit {
+        warnings.push(ValidationWarning {
+            field: field(prefix, "data"),
+            kind: ValidationWarningKind::TooManyItems { len, limit },
+        });
+    }
+
+    for (i, item) in data.iter().enumerate() {
+        item.validate_into(&field(prefix, &format!("data[{}]", i)), warnings);
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        for (i, item) in self.iter().enumerate() {
+            item.validate_into(&format!("{}[{}]", prefix, i), warnings);
+        }
+    }
+}
+
+impl Validate for Subject {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+        check_score(prefix, "rating.score", self.rating.score, warnings);
+    }
+}
+
+impl Validate for SearchSubjectsItem {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+        check_score(prefix, "score", self.score, warnings);
+    }
+}
+
+impl Validate for Episode {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for CharacterDetail {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for PersonDetail {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for RelatedSubject {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for RelatedPerson {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for RelatedCharacter {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl Validate for SubjectRelation {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_id(prefix, self.id, warnings);
+    }
+}
+
+impl<T: Validate> Validate for Paged<T> {
+    fn validate_into(&self, prefix: &str, warnings: &mut Vec<ValidationWarning>) {
+        check_page(
+            prefix,
+            self.total,
+            self.limit,
+            self.offset,
+            &self.data,
+            warnings,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_paged_episode() {
+        let paged = PagedEpisode {
+            total: 2,
+            limit: 1,
+            offset: 0,
+            data: vec![crate::mock::episode(1), crate::mock::episode(0)],
+        };
+
+        let warnings = paged.validate();
+
+        assert_eq!(
+            warnings,
+            vec![
+                ValidationWarning {
+                    field: "data".to_string(),
+                    kind: ValidationWarningKind::TooManyItems { len: 2, limit: 1 },
+                },
+                ValidationWarning {
+                    field: "data[1].id".to_string(),
+                    kind: ValidationWarningKind::ZeroId,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_clean_page() {
+        let paged = PagedEpisode {
+            total: 1,
+            limit: 10,
+            offset: 1,
+            data: vec![],
+        };
+
+        assert!(paged.validate().is_empty());
+    }
+}
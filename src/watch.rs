@@ -0,0 +1,290 @@
+//! # Watch Time (观看时长)
+//!
+//! 此模块根据章节时长与用户的章节收藏统计观看时长，可以按条目、按月汇总，适合「年度总结」一类的功能。
+//!
+//! 只有收藏类型为 [`EpisodeCollectionType::Done`] (看过) 的章节会被统计，
+//! 章节的时长取自服务器解析的 [`Episode::duration_seconds`]。
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use bgmtv::prelude::*;
+//! use bgmtv::watch::library_watch_time;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! # let token = std::env::var("BGMTV_TOKEN").expect("Please set BGMTV_TOKEN to run this example");
+//! let client = Client::builder().token(token).build()?;
+//!
+//! let mut library = Vec::new();
+//! for subject_id in [3559, 975] {
+//!     library.push((subject_id, client.get_user_episode_collections(subject_id).await?));
+//! }
+//!
+//! let watch = library_watch_time(library.iter().map(|(id, eps)| (*id, eps.as_slice())));
+//! println!("total: {:?}", watch.total.total);
+//! for (month, duration) in &watch.total.monthly {
+//!     println!("{}: {} min", month, duration.as_secs() / 60);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use crate::{time::civil_from_days, types::*};
+
+/// Year and month (年月)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearMonth {
+    /// 年
+    pub year: i32,
+
+    /// 月，从 1 开始
+    pub month: u8,
+}
+
+impl YearMonth {
+    /// 创建年月
+    pub fn new(year: i32, month: u8) -> Self {
+        Self { year, month }
+    }
+
+    /// 将 Unix 时间戳 (秒) 按 `utc_offset` 秒的时区转换为年月
+    fn from_timestamp(timestamp: i64, utc_offset: i32) -> Self {
+        let (year, month, _) =
+            civil_from_days((timestamp + i64::from(utc_offset)).div_euclid(86400));
+        Self {
+            year: year as i32,
+            month: month as u8,
+        }
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Watch time (观看时长)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WatchTime {
+    /// 总时长
+    pub total: Duration,
+
+    /// 看过的章节数
+    pub episodes: u64,
+
+    /// 时长未知而未计入 [`total`](WatchTime::total) 的章节数
+    pub unknown: u64,
+
+    /// 按章节收藏的修改时间汇总的每月时长，没有修改时间的章节不会计入
+    pub monthly: BTreeMap<YearMonth, Duration>,
+}
+
+impl WatchTime {
+    /// 某月的观看时长
+    pub fn month(&self, year: i32, month: u8) -> Duration {
+        self.monthly
+            .get(&YearMonth::new(year, month))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 某年的观看时长
+    pub fn year(&self, year: i32) -> Duration {
+        self.monthly
+            .range(YearMonth::new(year, 1)..=YearMonth::new(year, 12))
+            .map(|(_, duration)| *duration)
+            .sum()
+    }
+
+    /// 合并另一组统计
+    pub fn merge(&mut self, other: &WatchTime) {
+        self.total += other.total;
+        self.episodes += other.episodes;
+        self.unknown += other.unknown;
+        for (month, duration) in &other.monthly {
+            *self.monthly.entry(*month).or_default() += *duration;
+        }
+    }
+
+    fn add(&mut self, collection: &UserEpisodeCollection, options: WatchTimeOptions) {
+        if collection.r#type != EpisodeCollectionType::Done {
+            return;
+        }
+        self.episodes += 1;
+
+        let duration = collection
+            .episode
+            .duration_seconds
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+            .or(options.default_duration);
+        let Some(duration) = duration else {
+            self.unknown += 1;
+            return;
+        };
+
+        self.total += duration;
+        if collection.updated_at > 0 {
+            let month = YearMonth::from_timestamp(collection.updated_at, options.utc_offset);
+            *self.monthly.entry(month).or_default() += duration;
+        }
+    }
+}
+
+/// Library watch time (收藏的观看时长)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LibraryWatchTime {
+    /// 所有条目的合计
+    pub total: WatchTime,
+
+    /// 每个条目的观看时长，键为条目 ID
+    pub subjects: BTreeMap<u64, WatchTime>,
+}
+
+impl LibraryWatchTime {
+    /// 按观看时长从长到短排序的条目
+    pub fn ranked(&self) -> Vec<(u64, &WatchTime)> {
+        let mut subjects: Vec<_> = self
+            .subjects
+            .iter()
+            .map(|(id, watch)| (*id, watch))
+            .collect();
+        subjects.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(&b.0)));
+        subjects
+    }
+}
+
+/// [`watch_time_with`] 与 [`library_watch_time_with`] 的选项
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WatchTimeOptions {
+    /// 章节没有时长时使用的默认时长，默认不计入
+    pub default_duration: Option<Duration>,
+
+    /// 划分月份时使用的时区与 UTC 的偏移 (秒)，默认为 0
+    pub utc_offset: i32,
+}
+
+impl WatchTimeOptions {
+    /// 设置章节没有时长时使用的默认时长
+    pub fn default_duration(mut self, duration: Duration) -> Self {
+        self.default_duration = Some(duration);
+        self
+    }
+
+    /// 设置时区与 UTC 的偏移 (秒)，例如东八区为 `8 * 3600`
+    pub fn utc_offset(mut self, utc_offset: i32) -> Self {
+        self.utc_offset = utc_offset;
+        self
+    }
+}
+
+/// 使用默认选项统计一个条目的观看时长，参见 [`watch_time_with`]
+pub fn watch_time<'a>(episodes: impl IntoIterator<Item = &'a UserEpisodeCollection>) -> WatchTime {
+    watch_time_with(episodes, WatchTimeOptions::default())
+}
+
+/// 统计一个条目的观看时长
+pub fn watch_time_with<'a>(
+    episodes: impl IntoIterator<Item = &'a UserEpisodeCollection>,
+    options: WatchTimeOptions,
+) -> WatchTime {
+    let mut watch = WatchTime::default();
+    for collection in episodes {
+        watch.add(collection, options);
+    }
+    watch
+}
+
+/// 使用默认选项统计多个条目的观看时长，参见 [`library_watch_time_with`]
+pub fn library_watch_time<'a>(
+    subjects: impl IntoIterator<Item = (u64, &'a [UserEpisodeCollection])>,
+) -> LibraryWatchTime {
+    library_watch_time_with(subjects, WatchTimeOptions::default())
+}
+
+/// 统计多个条目的观看时长，`subjects` 为条目 ID 与其章节收藏
+pub fn library_watch_time_with<'a>(
+    subjects: impl IntoIterator<Item = (u64, &'a [UserEpisodeCollection])>,
+    options: WatchTimeOptions,
+) -> LibraryWatchTime {
+    let mut library = LibraryWatchTime::default();
+    for (subject_id, episodes) in subjects {
+        let watch = watch_time_with(episodes, options);
+        library.total.merge(&watch);
+        library
+            .subjects
+            .entry(subject_id)
+            .or_default()
+            .merge(&watch);
+    }
+    library
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(
+        id: u64,
+        seconds: Option<u64>,
+        r#type: EpisodeCollectionType,
+        updated_at: i64,
+    ) -> UserEpisodeCollection {
+        UserEpisodeCollection {
+            episode: Episode {
+                duration_seconds: seconds,
+                ..crate::mock::episode(id)
+            },
+            r#type,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_watch_time() {
+        use EpisodeCollectionType::*;
+
+        // 2024-01-31T20:00:00Z，东八区为 2024-02-01
+        let jan_31 = 1_706_731_200;
+        let subject_a = vec![
+            collection(1, Some(1440), Done, jan_31),
+            collection(2, Some(1440), Done, jan_31 + 32 * 86400),
+            collection(3, None, Done, jan_31),
+            collection(4, Some(1440), Wish, jan_31),
+        ];
+        let subject_b = vec![collection(5, Some(600), Done, 0)];
+
+        let watch = watch_time(&subject_a);
+        assert_eq!(watch.total, Duration::from_secs(2880));
+        assert_eq!((watch.episodes, watch.unknown), (3, 1));
+        assert_eq!(watch.month(2024, 1), Duration::from_secs(1440));
+        assert_eq!(watch.month(2024, 3), Duration::from_secs(1440));
+        assert_eq!(watch.year(2024), Duration::from_secs(2880));
+
+        let options = WatchTimeOptions::default()
+            .default_duration(Duration::from_secs(60))
+            .utc_offset(8 * 3600);
+        let watch = watch_time_with(&subject_a, options);
+        assert_eq!(watch.unknown, 0);
+        assert_eq!(watch.month(2024, 1), Duration::ZERO);
+        assert_eq!(watch.month(2024, 2), Duration::from_secs(1500));
+
+        let library = library_watch_time([(1, subject_a.as_slice()), (2, subject_b.as_slice())]);
+        assert_eq!(library.total.total, Duration::from_secs(3480));
+        assert_eq!(library.total.year(2024), Duration::from_secs(2880));
+        assert_eq!(
+            library
+                .ranked()
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(YearMonth::new(2024, 2).to_string(), "2024-02");
+    }
+}